@@ -0,0 +1,140 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use oort_api::prelude::*;
+use spacepoort::missile::ProportionalNavigation;
+use spacepoort::movement::*;
+use spacepoort::perception::Tracker;
+use spacepoort::random::Rng;
+use spacepoort::sim::{Body, MockMotor};
+use spacepoort::targeting::lead;
+
+fn contacts(count: usize) -> Vec<ScanResult> {
+    let mut rng = Rng::new(917);
+    (0..count)
+        .map(|_| ScanResult {
+            class: Class::Fighter,
+            position: vec2(rng.uniform(-1e4, 1e4), rng.uniform(-1e4, 1e4)),
+            velocity: vec2(rng.uniform(-300.0, 300.0), rng.uniform(-300.0, 300.0)),
+            rssi: 0.0,
+            snr: 0.0,
+        })
+        .collect()
+}
+
+fn tracker(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tracker_update");
+    for count in [10, 50, 200] {
+        let scans = contacts(count);
+        group.bench_with_input(BenchmarkId::from_parameter(count), &scans, |b, scans| {
+            b.iter(|| {
+                let mut tracker = Tracker::new();
+                for (tick, scan) in scans.iter().enumerate() {
+                    tracker.update(scan, tick as f64 * TICK_LENGTH);
+                }
+                black_box(tracker.tracks().len())
+            })
+        });
+    }
+    group.finish();
+}
+
+fn blend(c: &mut Criterion) {
+    let ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let mut group = c.benchmark_group("movement_blend");
+    for count in [2, 5, 10] {
+        let blend = MovementBlend {
+            moves: (0..count)
+                .map(|i| {
+                    let target = vec2(1_000.0, 0.0).rotate(i as f64);
+                    (Arrive { target, gain: 1.0 }, 1.0 / count as f64)
+                })
+                .collect(),
+        };
+        group.bench_with_input(BenchmarkId::from_parameter(count), &blend, |b, blend| {
+            b.iter(|| black_box(blend.execute(&ship).linear))
+        });
+    }
+    group.finish();
+}
+
+fn dispatch(c: &mut Criterion) {
+    let ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let target = vec2(1_000.0, 500.0);
+    let mut group = c.benchmark_group("strategy_dispatch");
+    group.bench_function("boxed", |b| {
+        b.iter(|| {
+            let blend = MovementBlend::<Box<dyn DynMove>>::new()
+                .boxed(Seek::new(target), 0.5)
+                .boxed(Arrive::new(-target), 0.25)
+                .boxed(KeepDistance::new(target, 500.0), 0.25)
+                .boxed(Align::new(1.0), 1.0);
+            black_box(blend.execute(&ship).linear)
+        })
+    });
+    group.bench_function("enum", |b| {
+        b.iter(|| {
+            let blend = MovementBlend::<Strategy<Body>>::new()
+                .strategy(Seek::new(target), 0.5)
+                .strategy(Arrive::new(-target), 0.25)
+                .strategy(KeepDistance::new(target, 500.0), 0.25)
+                .strategy(Align::new(1.0), 1.0);
+            black_box(blend.execute(&ship).linear)
+        })
+    });
+    group.bench_function("array", |b| {
+        b.iter(|| {
+            let blend = ArrayBlend::<Strategy<Body>, 4>::new([
+                (Seek::new(target).into(), 0.5),
+                (Arrive::new(-target).into(), 0.25),
+                (KeepDistance::new(target, 500.0).into(), 0.25),
+                (Align::new(1.0).into(), 1.0),
+            ]);
+            black_box(blend.execute(&ship).linear)
+        })
+    });
+    group.bench_function("tuple", |b| {
+        b.iter(|| {
+            let blend = Blend::weighted(
+                (
+                    Seek::new(target),
+                    Arrive::new(-target),
+                    KeepDistance::new(target, 500.0),
+                    Align::new(1.0),
+                ),
+                [0.5, 0.25, 0.25, 1.0],
+            );
+            black_box(blend.execute(&ship).linear)
+        })
+    });
+    group.finish();
+}
+
+fn solver(c: &mut Criterion) {
+    let offset = vec2(5_000.0, 1_200.0);
+    let velocity = vec2(-150.0, 220.0);
+    c.bench_function("lead", |b| {
+        b.iter(|| lead(black_box(offset), black_box(velocity), black_box(1_000.0)))
+    });
+}
+
+fn guidance(c: &mut Criterion) {
+    let missile = MockMotor::missile(vec2(0.0, 0.0), vec2(200.0, 0.0));
+    let target = Body {
+        position: vec2(4_000.0, 500.0),
+        velocity: vec2(0.0, 150.0),
+    };
+    c.bench_function("proportional_navigation", |b| {
+        b.iter(|| {
+            black_box(
+                ProportionalNavigation {
+                    target: black_box(target),
+                    gain: 4.0,
+                }
+                .execute(&missile)
+                .linear,
+            )
+        })
+    });
+}
+
+criterion_group!(benches, tracker, blend, dispatch, solver, guidance);
+criterion_main!(benches);
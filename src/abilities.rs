@@ -0,0 +1,195 @@
+use crate::math::sq_length;
+use crate::movement::Kinematic;
+use crate::warning::Warning;
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Spec {
+    pub duration: f64,
+    pub cooldown: f64,
+    pub acceleration: f64,
+}
+
+impl Spec {
+    pub fn of(ability: Ability) -> Spec {
+        match ability {
+            Ability::Boost => Spec {
+                duration: 2.0,
+                cooldown: 10.0,
+                acceleration: 100.0,
+            },
+            Ability::ShapedCharge => Spec {
+                duration: f64::INFINITY,
+                cooldown: f64::INFINITY,
+                acceleration: 0.0,
+            },
+            Ability::Decoy => Spec {
+                duration: 0.5,
+                cooldown: 10.0,
+                acceleration: 0.0,
+            },
+            Ability::Shield => Spec {
+                duration: 1.0,
+                cooldown: 5.0,
+                acceleration: 0.0,
+            },
+        }
+    }
+}
+
+struct Slot {
+    ability: Ability,
+    spec: Spec,
+    activated: Option<f64>,
+}
+
+pub struct Abilities {
+    pub decoy_time: f64,
+    pub decoy_range: f64,
+    pub shield_lead: f64,
+    slots: Vec<Slot>,
+}
+
+impl Abilities {
+    pub fn new(available: &[Ability]) -> Abilities {
+        Abilities {
+            decoy_time: 2.0,
+            decoy_range: 1_500.0,
+            shield_lead: 0.25,
+            slots: available
+                .iter()
+                .map(|&ability| Slot {
+                    ability,
+                    spec: Spec::of(ability),
+                    activated: None,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn for_class(class: Class) -> Abilities {
+        match class {
+            Class::Fighter => Abilities::new(&[Ability::Boost]),
+            Class::Missile => Abilities::new(&[Ability::ShapedCharge]),
+            Class::Torpedo => Abilities::new(&[Ability::Decoy]),
+            Class::Cruiser => Abilities::new(&[Ability::Shield]),
+            _ => Abilities::new(&[]),
+        }
+    }
+
+    fn slot(&self, ability: Ability) -> Option<&Slot> {
+        self.slots.iter().find(|slot| slot.ability == ability)
+    }
+
+    pub fn active(&self, ability: Ability, now: f64) -> bool {
+        self.slot(ability)
+            .and_then(|slot| slot.activated.map(|at| now - at < slot.spec.duration))
+            .unwrap_or(false)
+    }
+
+    pub fn available(&self, ability: Ability, now: f64) -> bool {
+        self.slot(ability)
+            .map(|slot| {
+                slot.activated
+                    .is_none_or(|at| now - at >= slot.spec.duration + slot.spec.cooldown)
+            })
+            .unwrap_or(false)
+    }
+
+    pub fn ready_in(&self, ability: Ability, now: f64) -> Option<f64> {
+        let slot = self.slot(ability)?;
+        Some(slot.activated.map_or(0.0, |at| {
+            (at + slot.spec.duration + slot.spec.cooldown - now).max(0.0)
+        }))
+    }
+
+    pub fn activate(&mut self, ability: Ability, now: f64) -> bool {
+        if !self.available(ability, now) {
+            return false;
+        }
+        activate_ability(ability);
+        if let Some(slot) = self.slots.iter_mut().find(|slot| slot.ability == ability) {
+            slot.activated = Some(now);
+        }
+        true
+    }
+
+    pub fn boost(&mut self, now: f64, time_to_go: f64) -> bool {
+        let duration = Spec::of(Ability::Boost).duration;
+        time_to_go > 2.0 * duration && self.activate(Ability::Boost, now)
+    }
+
+    pub fn shaped_charge(&mut self, now: f64, in_solution: bool) -> bool {
+        in_solution && self.activate(Ability::ShapedCharge, now)
+    }
+
+    pub fn decoy(&mut self, now: f64, warnings: &[Warning]) -> bool {
+        warnings
+            .iter()
+            .any(|warning| warning.time_to_impact.get() <= self.decoy_time)
+            && self.activate(Ability::Decoy, now)
+    }
+
+    pub fn terminal(&self, own: &impl Kinematic, threat: &impl Kinematic) -> bool {
+        let offset = threat.position() - own.position();
+        let closing = -(threat.velocity() - own.velocity()).dot(offset);
+        closing > 0.0 && sq_length(offset) <= self.decoy_range * self.decoy_range
+    }
+
+    pub fn decoy_against(
+        &mut self,
+        now: f64,
+        own: &impl Kinematic,
+        threat: &impl Kinematic,
+    ) -> bool {
+        self.terminal(own, threat) && self.activate(Ability::Decoy, now)
+    }
+
+    pub fn shield_plan(&self, now: f64, warnings: &[Warning]) -> Option<Window> {
+        let duration = Spec::of(Ability::Shield).duration;
+        let ready = self.ready_in(Ability::Shield, now)?;
+        let mut arrivals: Vec<f64> = warnings
+            .iter()
+            .map(|warning| warning.time_to_impact.get())
+            .filter(|time| time.is_finite())
+            .collect();
+        arrivals.sort_by(f64::total_cmp);
+        densest_window(&arrivals, duration - self.shield_lead, ready)
+    }
+
+    pub fn shield(&mut self, now: f64, warnings: &[Warning]) -> bool {
+        self.shield_plan(now, warnings)
+            .is_some_and(|window| window.start <= self.shield_lead)
+            && self.activate(Ability::Shield, now)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Window {
+    pub start: f64,
+    pub end: f64,
+    pub covered: usize,
+}
+
+pub fn densest_window(arrivals: &[f64], width: f64, earliest: f64) -> Option<Window> {
+    let mut best: Option<Window> = None;
+    let mut end = 0;
+    for (first, &start) in arrivals.iter().enumerate() {
+        end = end.max(first);
+        while end < arrivals.len() && arrivals[end] <= start + width {
+            end += 1;
+        }
+        if start < earliest {
+            continue;
+        }
+        let covered = end - first;
+        if best.is_none_or(|window| covered > window.covered) {
+            best = Some(Window {
+                start,
+                end: start + width,
+                covered,
+            });
+        }
+    }
+    best
+}
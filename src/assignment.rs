@@ -0,0 +1,110 @@
+use crate::movement::Kinematic;
+use crate::perception::{Track, TrackId};
+use crate::radio::{Bid, Header, Packet};
+use crate::tactics::Focus;
+use crate::weapons::Coordinator;
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Claim {
+    pub ship: u16,
+    pub position: Vec2,
+    pub score: f64,
+    pub received: f64,
+}
+
+pub struct Auction {
+    pub gate: f64,
+    pub timeout: f64,
+    pub focus: Focus,
+    claims: Vec<Claim>,
+    current: Option<(TrackId, f64)>,
+}
+
+impl Default for Auction {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Auction {
+    pub fn new() -> Auction {
+        Auction {
+            gate: 300.0,
+            timeout: 1.0,
+            focus: Focus::new(),
+            claims: Vec::new(),
+            current: None,
+        }
+    }
+
+    pub fn score(actor: &impl Kinematic, track: &Track) -> f64 {
+        let offset = track.position - actor.position();
+        let aspect = 1.0 + (offset.angle() - actor.heading()).cos();
+        Coordinator::value(track.class) * aspect / offset.length().max(1.0)
+    }
+
+    pub fn claims(&self) -> &[Claim] {
+        &self.claims
+    }
+
+    pub fn receive(&mut self, inbox: &[(Header, Packet)], now: f64) {
+        self.focus.observe(inbox, now);
+        for (header, packet) in inbox {
+            if let Packet::Bid(bid) = packet {
+                self.claims.retain(|claim| claim.ship != header.sender);
+                self.claims.push(Claim {
+                    ship: header.sender,
+                    position: bid.position,
+                    score: bid.score,
+                    received: now,
+                });
+            }
+        }
+        let timeout = self.timeout;
+        self.claims.retain(|claim| now - claim.received <= timeout);
+    }
+
+    fn outbid(&self, me: u16, track: &Track, score: f64) -> bool {
+        self.claims.iter().any(|claim| {
+            claim.ship != me
+                && claim.position.distance(track.position) < self.gate
+                && (claim.score > score || (claim.score == score && claim.ship < me))
+        })
+    }
+
+    pub fn resolve(
+        &mut self,
+        me: u16,
+        actor: &impl Kinematic,
+        tracks: &[Track],
+        now: f64,
+    ) -> Option<TrackId> {
+        let mut candidates: Vec<(&Track, f64)> = tracks
+            .iter()
+            .filter(|track| track.is_hull())
+            .map(|track| {
+                let score = self
+                    .focus
+                    .priority(track, Auction::score(actor, track), now);
+                (track, score)
+            })
+            .collect();
+        candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let (track, score) = candidates
+            .iter()
+            .find(|(track, score)| !self.outbid(me, track, *score))
+            .or(candidates.first())?;
+        self.current = Some((track.id, *score));
+        Some(track.id)
+    }
+
+    pub fn broadcast(&self, tracks: &[Track]) -> Option<Packet> {
+        let (id, score) = self.current?;
+        let track = tracks.iter().find(|track| track.id == id)?;
+        Some(Packet::Bid(Bid {
+            position: track.position,
+            score,
+        }))
+    }
+}
@@ -0,0 +1,395 @@
+use crate::fleet::Friendly;
+use crate::math::{cross, perpendicular};
+use crate::movement::*;
+use crate::perception::Track;
+use oort_api::prelude::*;
+
+const EPSILON: f64 = 1e-9;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Neighbor {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub radius: f64,
+    pub responsibility: f64,
+}
+
+impl Neighbor {
+    pub fn friendly(friend: &Friendly, radius: f64, now: f64) -> Neighbor {
+        Neighbor {
+            position: friend.predict(now),
+            velocity: friend.velocity,
+            radius,
+            responsibility: 0.5,
+        }
+    }
+
+    pub fn hazard(track: &Track, radius: f64, now: f64) -> Neighbor {
+        Neighbor {
+            position: track.predict(now).position,
+            velocity: track.velocity,
+            radius,
+            responsibility: 1.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Line {
+    point: Vec2,
+    direction: Vec2,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Orca {
+    pub radius: f64,
+    pub horizon: f64,
+    pub max_speed: f64,
+    pub response: f64,
+}
+
+impl Default for Orca {
+    fn default() -> Self {
+        Orca {
+            radius: 20.0,
+            horizon: 2.0,
+            max_speed: 1_000.0,
+            response: 0.5,
+        }
+    }
+}
+
+impl Orca {
+    fn half_plane(&self, position: Vec2, velocity: Vec2, neighbor: &Neighbor) -> Line {
+        let relative_position = neighbor.position - position;
+        let relative_velocity = velocity - neighbor.velocity;
+        let distance_squared = relative_position.dot(relative_position);
+        let radius = self.radius + neighbor.radius;
+        let radius_squared = radius * radius;
+        let (direction, u) = if distance_squared > radius_squared {
+            let w = relative_velocity - relative_position / self.horizon;
+            let w_length_squared = w.dot(w);
+            let dot = w.dot(relative_position);
+            if dot < 0.0 && dot * dot > radius_squared * w_length_squared {
+                let w_length = w_length_squared.sqrt();
+                let unit = w / w_length;
+                (
+                    vec2(unit.y, -unit.x),
+                    unit * (radius / self.horizon - w_length),
+                )
+            } else {
+                let leg = (distance_squared - radius_squared).sqrt();
+                let direction = if cross(relative_position, w) > 0.0 {
+                    vec2(
+                        relative_position.x * leg - relative_position.y * radius,
+                        relative_position.x * radius + relative_position.y * leg,
+                    ) / distance_squared
+                } else {
+                    -vec2(
+                        relative_position.x * leg + relative_position.y * radius,
+                        -relative_position.x * radius + relative_position.y * leg,
+                    ) / distance_squared
+                };
+                let projection = relative_velocity.dot(direction);
+                (direction, direction * projection - relative_velocity)
+            }
+        } else {
+            let w = relative_velocity - relative_position / TICK_LENGTH;
+            let w_length = w.length().max(EPSILON);
+            let unit = w / w_length;
+            (
+                vec2(unit.y, -unit.x),
+                unit * (radius / TICK_LENGTH - w_length),
+            )
+        };
+        Line {
+            point: velocity + u * neighbor.responsibility,
+            direction,
+        }
+    }
+
+    pub fn velocity(
+        &self,
+        position: Vec2,
+        velocity: Vec2,
+        preferred: Vec2,
+        neighbors: &[Neighbor],
+    ) -> Vec2 {
+        let lines: Vec<Line> = neighbors
+            .iter()
+            .map(|neighbor| self.half_plane(position, velocity, neighbor))
+            .collect();
+        let mut result = preferred;
+        let failed = program2(&lines, self.max_speed, preferred, false, &mut result);
+        if failed < lines.len() {
+            program3(&lines, failed, self.max_speed, &mut result);
+        }
+        result
+    }
+}
+
+fn program1(
+    lines: &[Line],
+    index: usize,
+    radius: f64,
+    optimal: Vec2,
+    direction_optimal: bool,
+    result: &mut Vec2,
+) -> bool {
+    let line = lines[index];
+    let dot = line.point.dot(line.direction);
+    let discriminant = dot * dot + radius * radius - line.point.dot(line.point);
+    if discriminant < 0.0 {
+        return false;
+    }
+    let root = discriminant.sqrt();
+    let mut left = -dot - root;
+    let mut right = -dot + root;
+    for other in &lines[..index] {
+        let denominator = cross(line.direction, other.direction);
+        let numerator = cross(other.direction, line.point - other.point);
+        if denominator.abs() <= EPSILON {
+            if numerator < 0.0 {
+                return false;
+            }
+            continue;
+        }
+        let t = numerator / denominator;
+        if denominator >= 0.0 {
+            right = right.min(t);
+        } else {
+            left = left.max(t);
+        }
+        if left > right {
+            return false;
+        }
+    }
+    let t = if direction_optimal {
+        if optimal.dot(line.direction) > 0.0 {
+            right
+        } else {
+            left
+        }
+    } else {
+        line.direction.dot(optimal - line.point).clamp(left, right)
+    };
+    *result = line.point + line.direction * t;
+    true
+}
+
+fn program2(
+    lines: &[Line],
+    radius: f64,
+    optimal: Vec2,
+    direction_optimal: bool,
+    result: &mut Vec2,
+) -> usize {
+    *result = if direction_optimal {
+        optimal * radius
+    } else if optimal.dot(optimal) > radius * radius {
+        optimal.normalize() * radius
+    } else {
+        optimal
+    };
+    for (i, line) in lines.iter().enumerate() {
+        if cross(line.direction, line.point - *result) > 0.0 {
+            let previous = *result;
+            if !program1(lines, i, radius, optimal, direction_optimal, result) {
+                *result = previous;
+                return i;
+            }
+        }
+    }
+    lines.len()
+}
+
+fn program3(lines: &[Line], begin: usize, radius: f64, result: &mut Vec2) {
+    let mut distance = 0.0;
+    for i in begin..lines.len() {
+        let line = lines[i];
+        if cross(line.direction, line.point - *result) <= distance {
+            continue;
+        }
+        let mut projected = Vec::with_capacity(i);
+        for other in &lines[..i] {
+            let determinant = cross(line.direction, other.direction);
+            let point = if determinant.abs() <= EPSILON {
+                if line.direction.dot(other.direction) > 0.0 {
+                    continue;
+                }
+                (line.point + other.point) * 0.5
+            } else {
+                line.point
+                    + line.direction
+                        * (cross(other.direction, line.point - other.point) / determinant)
+            };
+            projected.push(Line {
+                point,
+                direction: (other.direction - line.direction).normalize(),
+            });
+        }
+        let previous = *result;
+        let optimal = perpendicular(line.direction);
+        if program2(&projected, radius, optimal, true, result) < projected.len() {
+            *result = previous;
+        }
+        distance = cross(line.direction, line.point - *result);
+    }
+}
+
+pub struct Reciprocal<'a, M: Move> {
+    pub inner: M,
+    pub orca: &'a Orca,
+    pub neighbors: &'a [Neighbor],
+}
+
+impl<M: Move> Move for Reciprocal<'_, M> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let output = self.inner.execute(actor);
+        if self.neighbors.is_empty() {
+            return output;
+        }
+        let velocity = actor.velocity();
+        let preferred = velocity + output.linear * self.orca.response;
+        let chosen = self
+            .orca
+            .velocity(actor.position(), velocity, preferred, self.neighbors);
+        let max_acceleration = actor.max_linear_acceleration();
+        let linear = (chosen - velocity) / self.orca.response;
+        let length = linear.length();
+        Output {
+            linear: if length > max_acceleration {
+                linear * (max_acceleration / length)
+            } else {
+                linear
+            },
+            angular: output.angular,
+        }
+    }
+}
+
+pub fn time_to_collision(
+    relative_position: Vec2,
+    relative_velocity: Vec2,
+    radius: f64,
+) -> Option<f64> {
+    let a = relative_velocity.dot(relative_velocity);
+    let b = relative_position.dot(relative_velocity);
+    let c = relative_position.dot(relative_position) - radius * radius;
+    if c <= 0.0 {
+        return Some(0.0);
+    }
+    if a <= EPSILON || b <= 0.0 {
+        return None;
+    }
+    let discriminant = b * b - a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    Some((b - discriminant.sqrt()) / a)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VelocityObstacle {
+    pub radius: f64,
+    pub horizon: f64,
+    pub max_speed: f64,
+    pub rings: usize,
+    pub directions: usize,
+    pub response: f64,
+}
+
+impl Default for VelocityObstacle {
+    fn default() -> Self {
+        VelocityObstacle {
+            radius: 20.0,
+            horizon: 5.0,
+            max_speed: 1_000.0,
+            rings: 4,
+            directions: 16,
+            response: 0.5,
+        }
+    }
+}
+
+impl VelocityObstacle {
+    pub fn collision(&self, position: Vec2, velocity: Vec2, hazards: &[Neighbor]) -> Option<f64> {
+        hazards
+            .iter()
+            .filter_map(|hazard| {
+                time_to_collision(
+                    hazard.position - position,
+                    velocity - hazard.velocity,
+                    self.radius + hazard.radius,
+                )
+            })
+            .filter(|time| *time <= self.horizon)
+            .reduce(f64::min)
+    }
+
+    pub fn candidates(&self, velocity: Vec2, preferred: Vec2) -> Vec<Vec2> {
+        let mut candidates = vec![preferred, velocity];
+        let step = std::f64::consts::TAU / self.directions.max(1) as f64;
+        for ring in 1..=self.rings {
+            let speed = self.max_speed * ring as f64 / self.rings as f64;
+            for direction in 0..self.directions {
+                candidates.push(vec2(speed, 0.0).rotate(step * direction as f64));
+            }
+        }
+        candidates
+    }
+
+    pub fn velocity(
+        &self,
+        position: Vec2,
+        velocity: Vec2,
+        preferred: Vec2,
+        hazards: &[Neighbor],
+    ) -> Vec2 {
+        let mut best = preferred;
+        let mut best_cost = f64::INFINITY;
+        for candidate in self.candidates(velocity, preferred) {
+            let deviation = candidate.distance(preferred);
+            let cost = match self.collision(position, candidate, hazards) {
+                None => deviation,
+                Some(time) => deviation + self.max_speed * (self.horizon / time.max(EPSILON)),
+            };
+            if cost < best_cost {
+                best = candidate;
+                best_cost = cost;
+            }
+        }
+        best
+    }
+}
+
+pub struct Avoid<'a, M: Move> {
+    pub inner: M,
+    pub obstacle: &'a VelocityObstacle,
+    pub hazards: &'a [Neighbor],
+}
+
+impl<M: Move> Move for Avoid<'_, M> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let output = self.inner.execute(actor);
+        if self.hazards.is_empty() {
+            return output;
+        }
+        let velocity = actor.velocity();
+        let preferred = velocity + output.linear * self.obstacle.response;
+        let chosen = self
+            .obstacle
+            .velocity(actor.position(), velocity, preferred, self.hazards);
+        let max_acceleration = actor.max_linear_acceleration();
+        let linear = (chosen - velocity) / self.obstacle.response;
+        let length = linear.length();
+        Output {
+            linear: if length > max_acceleration {
+                linear * (max_acceleration / length)
+            } else {
+                linear
+            },
+            angular: output.angular,
+        }
+    }
+}
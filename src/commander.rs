@@ -0,0 +1,156 @@
+use crate::fleet::{Election, Roster};
+use crate::formation::{spacing, Formation, Shape};
+use crate::movement::Kinematic;
+use crate::perception::Track;
+use crate::radio::{Header, Packet, TargetAssignment};
+use crate::weapons::Coordinator;
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Posture {
+    Engage,
+    Screen,
+    Withdraw,
+}
+
+pub struct Commander {
+    pub engage_ratio: f64,
+    pub withdraw_ratio: f64,
+    pub screen_range: f64,
+    pub rally: Vec2,
+    pub screen_shape: Shape,
+    pub withdraw_shape: Shape,
+    posture: Posture,
+}
+
+impl Default for Commander {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Commander {
+    pub fn new() -> Commander {
+        Commander {
+            engage_ratio: 0.8,
+            withdraw_ratio: 0.4,
+            screen_range: 10_000.0,
+            rally: vec2(0.0, 0.0),
+            screen_shape: Shape::Wedge,
+            withdraw_shape: Shape::LineAbreast,
+            posture: Posture::Screen,
+        }
+    }
+
+    pub fn posture(&self) -> Posture {
+        self.posture
+    }
+
+    pub fn strength(tracks: &[Track]) -> f64 {
+        tracks
+            .iter()
+            .filter(|track| track.is_hull())
+            .map(|track| Coordinator::value(track.class))
+            .sum()
+    }
+
+    pub fn assess(
+        &mut self,
+        friends: &[Class],
+        actor: &impl Kinematic,
+        tracks: &[Track],
+    ) -> Posture {
+        let ours: f64 = friends.iter().map(|class| Coordinator::value(*class)).sum();
+        let theirs = Commander::strength(tracks);
+        let in_range = tracks
+            .iter()
+            .filter(|track| track.is_hull())
+            .any(|track| track.position.distance(actor.position()) < self.screen_range);
+        let ratio = if theirs > 0.0 {
+            ours / theirs
+        } else {
+            f64::INFINITY
+        };
+        self.posture = if ratio < self.withdraw_ratio {
+            Posture::Withdraw
+        } else if in_range && ratio >= self.engage_ratio {
+            Posture::Engage
+        } else {
+            Posture::Screen
+        };
+        self.posture
+    }
+
+    pub fn orders(
+        &self,
+        election: &Election,
+        roster: &Roster,
+        actor: &impl Kinematic,
+        tracks: &[Track],
+        now: f64,
+    ) -> Vec<Packet> {
+        if !election.is_leader() {
+            return Vec::new();
+        }
+        let nearest = tracks
+            .iter()
+            .filter(|track| track.is_hull())
+            .min_by(|a, b| {
+                let distance = |track: &Track| track.position.distance(actor.position());
+                distance(a).total_cmp(&distance(b))
+            });
+        match self.posture {
+            Posture::Engage => {
+                let mut targets: Vec<&Track> = tracks.iter().filter(|t| t.is_hull()).collect();
+                if targets.is_empty() {
+                    return Vec::new();
+                }
+                election
+                    .members()
+                    .into_iter()
+                    .map(|ship| {
+                        let position = roster
+                            .get(ship)
+                            .map_or(actor.position(), |friend| friend.predict(now));
+                        targets.sort_by(|a, b| {
+                            a.position
+                                .distance(position)
+                                .total_cmp(&b.position.distance(position))
+                        });
+                        let target = targets.remove(0);
+                        targets.push(target);
+                        Packet::Assignment(TargetAssignment {
+                            ship,
+                            position: target.position,
+                            velocity: target.velocity,
+                        })
+                    })
+                    .collect()
+            }
+            Posture::Screen => {
+                let heading = nearest.map_or(actor.heading(), |track| {
+                    (track.position - actor.position()).angle()
+                });
+                vec![Formation::command(
+                    self.screen_shape,
+                    spacing(Class::Fighter) * 2.0,
+                    heading,
+                    actor.position(),
+                )]
+            }
+            Posture::Withdraw => vec![Formation::command(
+                self.withdraw_shape,
+                spacing(Class::Fighter),
+                (self.rally - actor.position()).angle(),
+                self.rally,
+            )],
+        }
+    }
+}
+
+pub fn assignment_for(me: u16, inbox: &[(Header, Packet)]) -> Option<TargetAssignment> {
+    inbox.iter().rev().find_map(|(_, packet)| match packet {
+        Packet::Assignment(assignment) if assignment.ship == me => Some(*assignment),
+        _ => None,
+    })
+}
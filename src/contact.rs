@@ -0,0 +1,71 @@
+use crate::math::Vec2;
+
+#[cfg(feature = "oort")]
+pub use oort_api::prelude::{Class, ScanResult};
+
+#[cfg(not(feature = "oort"))]
+pub use fallback::*;
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContactReport {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serial::class"))]
+    pub class: Class,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serial::vec2"))]
+    pub position: Vec2,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serial::vec2"))]
+    pub velocity: Vec2,
+}
+
+pub fn class_code(class: Class) -> u16 {
+    match class {
+        Class::Fighter => 0,
+        Class::Frigate => 1,
+        Class::Cruiser => 2,
+        Class::Asteroid => 3,
+        Class::Target => 4,
+        Class::Missile => 5,
+        Class::Torpedo => 6,
+        _ => 7,
+    }
+}
+
+pub fn class_from_code(code: u16) -> Class {
+    match code {
+        0 => Class::Fighter,
+        1 => Class::Frigate,
+        2 => Class::Cruiser,
+        3 => Class::Asteroid,
+        4 => Class::Target,
+        5 => Class::Missile,
+        6 => Class::Torpedo,
+        _ => Class::Unknown,
+    }
+}
+
+#[cfg(not(feature = "oort"))]
+mod fallback {
+    use crate::math::Vec2;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum Class {
+        Fighter,
+        Frigate,
+        Cruiser,
+        Asteroid,
+        Target,
+        Missile,
+        Torpedo,
+        Planet,
+        Unknown,
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ScanResult {
+        pub class: Class,
+        pub position: Vec2,
+        pub velocity: Vec2,
+        pub rssi: f64,
+        pub snr: f64,
+    }
+}
@@ -0,0 +1,182 @@
+use crate::math::*;
+use crate::movement::{Kinematic, Motor, SteeringLimits};
+use crate::time::Seconds;
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Value {
+    Number(f64),
+    Vector(Vec2),
+    Flag(bool),
+    Id(u32),
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Number(value)
+    }
+}
+
+impl From<Vec2> for Value {
+    fn from(value: Vec2) -> Self {
+        Value::Vector(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Flag(value)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Value::Id(value)
+    }
+}
+
+#[derive(Default)]
+pub struct Blackboard {
+    entries: Vec<(&'static str, Value)>,
+}
+
+impl Blackboard {
+    pub fn new() -> Blackboard {
+        Blackboard::default()
+    }
+
+    pub fn set(&mut self, key: &'static str, value: impl Into<Value>) {
+        let value = value.into();
+        match self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key, value)),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.entries
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn number(&self, key: &str) -> Option<f64> {
+        match self.get(key)? {
+            Value::Number(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn vector(&self, key: &str) -> Option<Vec2> {
+        match self.get(key)? {
+            Value::Vector(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn flag(&self, key: &str) -> bool {
+        matches!(self.get(key), Some(Value::Flag(true)))
+    }
+
+    pub fn id(&self, key: &str) -> Option<u32> {
+        match self.get(key)? {
+            Value::Id(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) {
+        self.entries.retain(|(k, _)| *k != key);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+pub struct TickContext<D = ()> {
+    pub time: f64,
+    pub dt: f64,
+    pub tick: u32,
+    pub ship: u16,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub heading: f64,
+    pub angular_velocity: f64,
+    pub forward: Vec2,
+    pub max_linear_acceleration: f64,
+    pub max_angular_acceleration: f64,
+    pub max_angular_velocity: f64,
+    pub limits: SteeringLimits,
+    pub draw: D,
+    pub blackboard: Blackboard,
+}
+
+impl TickContext {
+    pub fn capture(actor: &(impl Kinematic + Motor), time: f64) -> TickContext {
+        TickContext::with_draw(actor, time, ())
+    }
+}
+
+impl<D> TickContext<D> {
+    pub fn with_draw(actor: &(impl Kinematic + Motor), time: f64, draw: D) -> TickContext<D> {
+        let heading = actor.heading();
+        TickContext {
+            time,
+            dt: TICK_LENGTH,
+            tick: Seconds(time).ticks().get() as u32,
+            ship: 0,
+            position: actor.position(),
+            velocity: actor.velocity(),
+            heading,
+            angular_velocity: actor.angular_velocity(),
+            forward: vec2(1.0, 0.0).rotate(heading),
+            max_linear_acceleration: actor.max_linear_acceleration(),
+            max_angular_acceleration: actor.max_angular_acceleration(),
+            max_angular_velocity: actor.max_angular_velocity(),
+            limits: actor.limits(),
+            draw,
+            blackboard: Blackboard::new(),
+        }
+    }
+
+    pub fn with_ship(mut self, ship: u16) -> TickContext<D> {
+        self.ship = ship;
+        self
+    }
+
+    pub fn with_blackboard(mut self, blackboard: Blackboard) -> TickContext<D> {
+        self.blackboard = blackboard;
+        self
+    }
+}
+
+impl<D> Kinematic for TickContext<D> {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.heading
+    }
+    fn angular_velocity(&self) -> f64 {
+        self.angular_velocity
+    }
+}
+
+impl<D> Motor for TickContext<D> {
+    fn max_linear_acceleration(&self) -> f64 {
+        self.max_linear_acceleration
+    }
+    fn max_angular_acceleration(&self) -> f64 {
+        self.max_angular_acceleration
+    }
+    fn max_angular_velocity(&self) -> f64 {
+        self.max_angular_velocity
+    }
+    fn limits(&self) -> SteeringLimits {
+        self.limits
+    }
+}
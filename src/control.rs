@@ -0,0 +1,415 @@
+use crate::math::*;
+use crate::movement::{Kinematic, Motor, Move, Output};
+use crate::time::Seconds;
+
+pub mod discrete {
+    use crate::math::*;
+
+    pub fn ticks(seconds: f64) -> f64 {
+        seconds / TICK_LENGTH
+    }
+
+    pub fn seconds(ticks: f64) -> f64 {
+        ticks * TICK_LENGTH
+    }
+
+    pub fn decay(rate: f64) -> f64 {
+        (-rate * TICK_LENGTH).exp()
+    }
+
+    pub fn smoothing(time_constant: f64) -> f64 {
+        if time_constant <= 0.0 {
+            return 1.0;
+        }
+        1.0 - (-TICK_LENGTH / time_constant).exp()
+    }
+
+    pub fn proportional(gain: f64) -> f64 {
+        1.0 - decay(gain)
+    }
+
+    pub fn integral(gain: f64) -> f64 {
+        gain * TICK_LENGTH
+    }
+
+    pub fn derivative(gain: f64) -> f64 {
+        gain / TICK_LENGTH
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pid<T: Scalar = f64> {
+    pub kp: T,
+    pub ki: T,
+    pub kd: T,
+    pub setpoint: T,
+    pub min_output: T,
+    pub max_output: T,
+    pub integral_limit: T,
+    pub derivative_filter: T,
+    integral: T,
+    previous: Option<T>,
+    derivative: T,
+}
+
+impl<T: Scalar> Pid<T> {
+    pub fn new(kp: T, ki: T, kd: T) -> Pid<T> {
+        Pid {
+            kp,
+            ki,
+            kd,
+            setpoint: T::ZERO,
+            min_output: -T::INFINITY,
+            max_output: T::INFINITY,
+            integral_limit: T::INFINITY,
+            derivative_filter: T::ZERO,
+            integral: T::ZERO,
+            previous: None,
+            derivative: T::ZERO,
+        }
+    }
+
+    pub fn with_limits(mut self, min_output: T, max_output: T) -> Pid<T> {
+        self.min_output = min_output;
+        self.max_output = max_output;
+        self
+    }
+
+    pub fn with_integral_limit(mut self, limit: T) -> Pid<T> {
+        self.integral_limit = limit;
+        self
+    }
+
+    pub fn with_derivative_filter(mut self, time_constant: T) -> Pid<T> {
+        self.derivative_filter = time_constant;
+        self
+    }
+
+    pub fn integral(&self) -> T {
+        self.integral
+    }
+
+    pub fn reset(&mut self) {
+        self.integral = T::ZERO;
+        self.previous = None;
+        self.derivative = T::ZERO;
+    }
+
+    pub fn update(&mut self, measurement: T) -> T {
+        self.step(self.setpoint - measurement, -measurement)
+    }
+
+    pub fn update_error(&mut self, error: T) -> T {
+        self.step(error, error)
+    }
+
+    fn step(&mut self, error: T, signal: T) -> T {
+        if !error.is_finite() || !signal.is_finite() {
+            return T::ZERO.clamp(self.min_output, self.max_output);
+        }
+        let tick = T::from_f64(TICK_LENGTH);
+        let raw = match self.previous {
+            Some(previous) => (signal - previous) / tick,
+            None => T::ZERO,
+        };
+        self.previous = Some(signal);
+        let smoothing = T::from_f64(discrete::smoothing(self.derivative_filter.to_f64()));
+        self.derivative = self.derivative + smoothing * (raw - self.derivative);
+
+        let integral =
+            (self.integral + error * tick).clamp(-self.integral_limit, self.integral_limit);
+        let unclamped = self.kp * error + self.ki * integral + self.kd * self.derivative;
+        let output = unclamped.clamp(self.min_output, self.max_output);
+        let winding = (unclamped > self.max_output && error > T::ZERO)
+            || (unclamped < self.min_output && error < T::ZERO);
+        if !winding {
+            self.integral = integral;
+        }
+        output
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Attitude<T: Scalar = f64> {
+    pub angle_gain: T,
+    pub rate_gain: T,
+}
+
+impl<T: Scalar> Default for Attitude<T> {
+    fn default() -> Self {
+        Attitude {
+            angle_gain: T::from_f64(4.0),
+            rate_gain: T::ONE,
+        }
+    }
+}
+
+impl<T: Scalar> Attitude<T> {
+    pub fn rate(&self, error: T, max_acceleration: T, max_rate: T) -> T {
+        let braking = (T::from_f64(2.0) * max_acceleration * error.abs()).sqrt();
+        (self.angle_gain * error.abs()).min(braking).min(max_rate) * error.signum()
+    }
+
+    pub fn torque(&self, desired_rate: T, rate: T, max_acceleration: T) -> T {
+        (self.rate_gain * (desired_rate - rate) / T::from_f64(TICK_LENGTH))
+            .clamp(-max_acceleration, max_acceleration)
+    }
+
+    pub fn update(&self, error: T, rate: T, max_acceleration: T, max_rate: T) -> T {
+        let desired_rate = self.rate(error, max_acceleration, max_rate);
+        self.torque(desired_rate, rate, max_acceleration)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Lqr {
+    pub position_weight: f64,
+    pub velocity_weight: f64,
+    pub heading_weight: f64,
+    pub rate_weight: f64,
+    pub linear_effort: f64,
+    pub angular_effort: f64,
+}
+
+impl Default for Lqr {
+    fn default() -> Self {
+        Lqr {
+            position_weight: 1.0,
+            velocity_weight: 1.0,
+            heading_weight: 1.0,
+            rate_weight: 0.1,
+            linear_effort: 0.01,
+            angular_effort: 0.01,
+        }
+    }
+}
+
+impl Lqr {
+    pub fn gains(state_weight: f64, rate_weight: f64, effort: f64) -> (f64, f64) {
+        let effort = effort.max(f64::EPSILON);
+        let kp = (state_weight / effort).sqrt();
+        let kd = (rate_weight / effort + 2.0 * kp).sqrt();
+        (kp, kd)
+    }
+
+    pub fn linear(&self, position_error: Vec2, velocity_error: Vec2) -> Vec2 {
+        let (kp, kd) = Lqr::gains(
+            self.position_weight,
+            self.velocity_weight,
+            self.linear_effort,
+        );
+        position_error * kp + velocity_error * kd
+    }
+
+    pub fn angular(&self, heading_error: f64, rate_error: f64) -> f64 {
+        let (kp, kd) = Lqr::gains(self.heading_weight, self.rate_weight, self.angular_effort);
+        heading_error * kp + rate_error * kd
+    }
+
+    pub fn cost(
+        &self,
+        position_error: Vec2,
+        velocity_error: Vec2,
+        heading_error: f64,
+        rate_error: f64,
+    ) -> f64 {
+        self.position_weight * position_error.dot(position_error)
+            + self.velocity_weight * velocity_error.dot(velocity_error)
+            + self.heading_weight * heading_error * heading_error
+            + self.rate_weight * rate_error * rate_error
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Thrusters {
+    pub forward: f64,
+    pub backward: f64,
+    pub lateral: f64,
+}
+
+impl Thrusters {
+    #[cfg(feature = "oort")]
+    pub fn current() -> Thrusters {
+        use oort_api::prelude::*;
+        Thrusters {
+            forward: max_forward_acceleration(),
+            backward: max_backward_acceleration(),
+            lateral: max_lateral_acceleration(),
+        }
+    }
+
+    pub fn limit(&self, body: Vec2) -> f64 {
+        let axial = if body.x >= 0.0 {
+            self.forward
+        } else {
+            self.backward
+        };
+        let mut scale: f64 = 1.0;
+        if body.x.abs() > axial {
+            scale = scale.min(axial / body.x.abs());
+        }
+        if body.y.abs() > self.lateral {
+            scale = scale.min(self.lateral / body.y.abs());
+        }
+        scale
+    }
+
+    pub fn clamp(&self, heading: f64, world: Vec2) -> Vec2 {
+        let body = world.rotate(-heading);
+        world * self.limit(body)
+    }
+}
+
+pub struct VelocityController {
+    pub thrusters: Thrusters,
+    pub gain: f64,
+    pub attitude: Attitude,
+    pub persistence: f64,
+    saturated: f64,
+}
+
+impl VelocityController {
+    pub fn new(thrusters: Thrusters) -> VelocityController {
+        VelocityController {
+            thrusters,
+            gain: 1.0,
+            attitude: Attitude::default(),
+            persistence: 0.5,
+            saturated: 0.0,
+        }
+    }
+
+    pub fn rotating(&self) -> bool {
+        self.saturated >= self.persistence
+    }
+
+    pub fn update(&mut self, actor: &(impl Kinematic + Motor), desired: Vec2) -> Output {
+        let demand = (desired - actor.velocity()) * (self.gain / TICK_LENGTH);
+        let body = demand.rotate(-actor.heading());
+        let lateral_saturated = body.y.abs() > self.thrusters.lateral;
+        self.saturated = if lateral_saturated {
+            self.saturated + TICK_LENGTH
+        } else {
+            0.0
+        };
+        let angular = if self.rotating() {
+            let error = angle_diff(actor.heading(), demand.angle());
+            self.attitude.update(
+                error,
+                actor.angular_velocity(),
+                actor.max_angular_acceleration(),
+                actor.max_angular_velocity(),
+            )
+        } else {
+            0.0
+        };
+        Output {
+            linear: demand * self.thrusters.limit(body),
+            angular,
+        }
+    }
+}
+
+pub trait Trajectory {
+    fn sample(&self, time: Seconds) -> (Vec2, Vec2);
+}
+
+impl<F: Fn(Seconds) -> (Vec2, Vec2)> Trajectory for F {
+    fn sample(&self, time: Seconds) -> (Vec2, Vec2) {
+        self(time)
+    }
+}
+
+pub struct Tracking<'a, T: Trajectory> {
+    pub trajectory: &'a T,
+    pub time: Seconds,
+    pub position_gain: f64,
+    pub velocity_gain: f64,
+}
+
+impl<T: Trajectory> Move for Tracking<'_, T> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let max_acceleration = actor.max_linear_acceleration();
+        let (position, velocity) = self.trajectory.sample(self.time);
+        let (_, next_velocity) = self.trajectory.sample(self.time + Seconds::TICK);
+        let feedforward = (next_velocity - velocity) / TICK_LENGTH;
+        let command = feedforward
+            + (position - actor.position()) * self.position_gain
+            + (velocity - actor.velocity()) * self.velocity_gain;
+        let length = command.length();
+        Output {
+            linear: if length > max_acceleration {
+                command * (max_acceleration / length)
+            } else {
+                command
+            },
+            angular: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deadband<T: Scalar = f64> {
+    pub enter: T,
+    pub exit: T,
+    idle: bool,
+}
+
+impl<T: Scalar> Deadband<T> {
+    pub fn new(enter: T, exit: T) -> Deadband<T> {
+        Deadband {
+            enter,
+            exit: exit.max(enter),
+            idle: true,
+        }
+    }
+
+    pub fn idle(&self) -> bool {
+        self.idle
+    }
+
+    pub fn active(&mut self, magnitude: T) -> bool {
+        self.idle = if self.idle {
+            magnitude <= self.exit
+        } else {
+            magnitude < self.enter
+        };
+        !self.idle
+    }
+}
+
+pub struct Actuation {
+    pub linear: Deadband,
+    pub angular: Deadband,
+}
+
+impl Default for Actuation {
+    fn default() -> Self {
+        Actuation {
+            linear: Deadband::new(0.5, 2.0),
+            angular: Deadband::new(0.01, 0.05),
+        }
+    }
+}
+
+impl Actuation {
+    pub fn apply(&mut self, output: Output) -> Output {
+        Output {
+            linear: if self.linear.active(output.linear.length()) {
+                output.linear
+            } else {
+                vec2(0.0, 0.0)
+            },
+            angular: if self.angular.active(output.angular.abs()) {
+                output.angular
+            } else {
+                0.0
+            },
+        }
+    }
+}
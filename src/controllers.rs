@@ -0,0 +1,1023 @@
+use crate::abilities::Abilities;
+use crate::assignment::Auction;
+use crate::context::TickContext;
+use crate::control::Attitude;
+use crate::defense::{Engagement, PointDefense, Turret};
+use crate::draw::{DebugDraw, OortDraw};
+use crate::evasion::AdaptiveEvasion;
+use crate::fleet::Election;
+use crate::formation::{Formation, Shape};
+use crate::fusion::Sharing;
+use crate::hardware::Hardware;
+use crate::math::perpendicular;
+use crate::missile::{Performance, TargetState};
+use crate::movement::*;
+use crate::perception::{
+    motion_analysis, triangulate, Beam, Motion, RadarScheduler, Sector, Strobe, Track, TrackId,
+    Tracker,
+};
+use crate::planning::{Arena, FlipAndBurn, TransitPhase};
+use crate::profile::Profile;
+use crate::radio::{Header, Packet, Radio};
+use crate::tactics::FocusFire;
+use crate::targeting::{closest_approach, intercept, Intercept, InterceptCache};
+use crate::time::Seconds;
+use crate::warning::{MissileWarning, Warning};
+use crate::weapons::{Coordinator, Gun, Launcher, Volley};
+use crate::Oort;
+use oort_api::prelude::*;
+
+fn nearest(
+    actor: &impl Kinematic,
+    tracks: &[Track],
+    filter: impl Fn(&Track) -> bool,
+) -> Option<TrackId> {
+    tracks
+        .iter()
+        .filter(|track| filter(track))
+        .min_by(|a, b| {
+            actor
+                .position()
+                .distance(a.position)
+                .total_cmp(&actor.position().distance(b.position))
+        })
+        .map(|track| track.id)
+}
+
+pub struct Gunnery {
+    pub gun: usize,
+    pub bullet_speed: f64,
+    pub bullet_lifetime: f64,
+    pub target_radius: f64,
+    pub max_age: f64,
+    pub radar_width: f64,
+    pub attitude: Attitude,
+    pub tracker: Tracker,
+    pub radar: RadarScheduler,
+    pub cache: InterceptCache,
+    target: Option<TrackId>,
+}
+
+impl Default for Gunnery {
+    fn default() -> Self {
+        Gunnery::new()
+    }
+}
+
+impl Gunnery {
+    pub fn new() -> Gunnery {
+        Gunnery {
+            gun: 0,
+            bullet_speed: 1_000.0,
+            bullet_lifetime: 5.0,
+            target_radius: 10.0,
+            max_age: 0.5,
+            radar_width: 0.05,
+            attitude: Attitude::default(),
+            tracker: Tracker::new(),
+            radar: RadarScheduler::new(),
+            cache: InterceptCache::new(),
+            target: None,
+        }
+    }
+
+    pub fn tune(&mut self, profile: &Profile) {
+        self.tracker.gate = profile.track_gate;
+        self.radar.revisit = profile.radar_revisit;
+    }
+
+    pub fn reachable(&self, time: f64) -> bool {
+        time > 0.0 && time <= self.bullet_lifetime
+    }
+
+    pub fn tolerance(&self, time: f64) -> f64 {
+        self.target_radius
+            .atan2(self.bullet_speed * time.max(TICK_LENGTH))
+    }
+
+    pub fn command(&self, actor: &(impl Kinematic + Motor), track: &Track) -> (Output, bool) {
+        self.aim(actor, track, intercept(actor, track, self.bullet_speed))
+    }
+
+    fn aim(
+        &self,
+        actor: &(impl Kinematic + Motor),
+        track: &Track,
+        solution: Option<Intercept>,
+    ) -> (Output, bool) {
+        let (bearing, feedforward) = Face::new(track.clone()).line_of_sight(actor);
+        let Some(solution) = solution else {
+            let output = Align::new(bearing)
+                .feedforward(feedforward)
+                .attitude(self.attitude)
+                .execute(actor);
+            return (output, false);
+        };
+        let output = Align::new(solution.heading)
+            .feedforward(feedforward)
+            .attitude(self.attitude)
+            .execute(actor);
+        let time = solution.time.get();
+        let error = angle_diff(actor.heading(), solution.heading).abs();
+        let fire =
+            self.reachable(time) && error <= self.tolerance(time) && track.age <= self.max_age;
+        (output, fire)
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let context =
+            TickContext::with_draw(actor, current_time(), OortDraw).with_ship(id() as u16);
+        self.drive(&context, &mut Oort::default());
+    }
+
+    pub fn drive(&mut self, context: &TickContext<impl DebugDraw>, hardware: &mut impl Hardware) {
+        let now = context.time;
+        if let Some(contact) = hardware.scan() {
+            self.tracker.update_from(context.position, &contact, now);
+        }
+        for &id in self.tracker.prune(now).iter() {
+            self.cache.forget(id);
+        }
+        self.target = self
+            .target
+            .filter(|id| self.tracker.get(*id).is_some())
+            .or_else(|| nearest(context, self.tracker.tracks(), Track::is_hull));
+        let Some(track) = self
+            .target
+            .and_then(|id| self.tracker.get(id))
+            .map(|track| track.predict(now))
+        else {
+            let beam = self
+                .radar
+                .next(context.position, self.tracker.tracks(), now);
+            hardware.aim_radar(&beam);
+            return;
+        };
+        hardware.aim_radar(&Beam {
+            heading: (track.position - context.position).angle(),
+            width: self.radar_width,
+            min_distance: 0.0,
+            max_distance: self.radar.range,
+        });
+        context.draw.diamond(track.position, 50.0, 0xff0000);
+        let solution = self
+            .cache
+            .intercept(track.id, context, &track, self.bullet_speed);
+        let (output, fire_now) = self.aim(context, &track, solution);
+        hardware.torque(output.angular);
+        if fire_now {
+            hardware.fire(self.gun);
+        }
+    }
+}
+
+pub struct Squadron {
+    pub me: u16,
+    pub engage_range: f64,
+    pub radio: Radio,
+    pub tracker: Tracker,
+    pub radar: RadarScheduler,
+    pub election: Election,
+    pub formation: Formation,
+    pub sharing: Sharing,
+    pub auction: Auction,
+    pub focus_fire: FocusFire,
+    pub gun: Gun,
+    target: Option<TrackId>,
+}
+
+impl Squadron {
+    pub fn new(me: u16, size: usize) -> Squadron {
+        Squadron {
+            me,
+            engage_range: 5_000.0,
+            radio: Radio::new(0),
+            tracker: Tracker::new(),
+            radar: RadarScheduler::new(),
+            election: Election::new(me),
+            formation: Formation::shaped(Shape::Wedge, size.saturating_sub(1), Class::Fighter),
+            sharing: Sharing::new(),
+            auction: Auction::new(),
+            focus_fire: FocusFire::new(),
+            gun: Gun {
+                index: 0,
+                speed: 1000.0,
+                tolerance: 0.02,
+                turn_gain: 10.0,
+                max_age: 1.0,
+            },
+            target: None,
+        }
+    }
+
+    pub fn target(&self) -> Option<TrackId> {
+        self.target
+    }
+
+    pub fn observe(&mut self, inbox: &[(Header, Packet)], now: f64) {
+        self.election.observe(inbox, now);
+        self.formation.observe(inbox, self.election.leader(), now);
+        self.sharing.receive(&mut self.tracker, inbox, now);
+        self.auction.receive(inbox, now);
+        self.tracker.prune(now);
+    }
+
+    pub fn plan(&mut self, actor: &impl Kinematic, health: f64, now: f64) -> Vec<Packet> {
+        let mut outbox = Vec::new();
+        if self.election.is_leader() {
+            outbox.push(Formation::publish(actor, health));
+            outbox.extend(
+                self.focus_fire
+                    .designate(actor.position(), self.tracker.tracks()),
+            );
+        }
+        outbox.extend(self.sharing.broadcast(&self.tracker, now));
+        let range = self.engage_range;
+        let tracks: Vec<Track> = self
+            .tracker
+            .tracks()
+            .iter()
+            .filter(|track| track.position.distance(actor.position()) <= range)
+            .map(|track| track.predict(now))
+            .collect();
+        self.target = self.auction.resolve(self.me, actor, &tracks, now);
+        outbox.extend(self.auction.broadcast(&tracks));
+        outbox
+    }
+
+    pub fn steer(&self, actor: &(impl Kinematic + Motor), now: f64) -> Option<Output> {
+        if let Some(track) = self.target.and_then(|id| self.tracker.get(id)) {
+            return Some(Seek::new(track.predict(now).position).execute(actor));
+        }
+        if self.election.is_leader() {
+            return None;
+        }
+        let offset = self.formation.slot(
+            self.me,
+            &self.election.members(),
+            self.election.leader(),
+            now,
+        )?;
+        self.formation.steer(actor, offset, now)
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let now = current_time();
+        self.formation
+            .arena
+            .get_or_insert_with(|| Arena::current(1_000.0));
+        if let Some(contact) = scan() {
+            self.tracker.update_from(actor.position(), &contact, now);
+        }
+        self.radio.poll(current_tick());
+        let inbox = self.radio.inbox().to_vec();
+        self.observe(&inbox, now);
+        for packet in self.plan(actor, health(), now) {
+            self.radio.queue(packet);
+        }
+        self.radio.flush(current_tick());
+        if let Some(output) = self.steer(actor, now) {
+            accelerate(output.linear);
+        }
+        match self.target.and_then(|id| self.tracker.get(id)) {
+            Some(track) => {
+                let track = track.predict(now);
+                self.gun.engage_track(actor, &track);
+                set_radar_heading((track.position - actor.position()).angle());
+                set_radar_width(0.05);
+            }
+            None => self
+                .radar
+                .next(actor.position(), self.tracker.tracks(), now)
+                .apply(),
+        }
+    }
+}
+
+pub fn no_escape_range(missile: &Performance, flee_acceleration: f64) -> f64 {
+    const STEPS: usize = 64;
+    (1..=STEPS)
+        .map(|step| {
+            let time = missile.lifetime * step as f64 / STEPS as f64;
+            missile.reach(time) - 0.5 * flee_acceleration * time * time
+        })
+        .fold(0.0, f64::max)
+}
+
+pub struct FrigateDuel {
+    pub main_gun: Gun,
+    pub gun_range: f64,
+    pub enemy_missile: Performance,
+    pub margin: f64,
+    pub point_defense: PointDefense,
+    pub abilities: Abilities,
+    pub tracker: Tracker,
+    pub radar: RadarScheduler,
+    target: Option<TrackId>,
+    leakers: Vec<Warning>,
+}
+
+impl Default for FrigateDuel {
+    fn default() -> Self {
+        FrigateDuel::new()
+    }
+}
+
+impl FrigateDuel {
+    pub fn new() -> FrigateDuel {
+        let turret = |index| Turret {
+            index,
+            bullet_speed: 1000.0,
+            range: 2_500.0,
+        };
+        FrigateDuel {
+            main_gun: Gun {
+                index: 0,
+                speed: 4000.0,
+                tolerance: 0.01,
+                turn_gain: 5.0,
+                max_age: 1.5,
+            },
+            gun_range: 8_000.0,
+            enemy_missile: Performance {
+                acceleration: 300.0,
+                delta_v: 3_000.0,
+                lifetime: 20.0,
+            },
+            margin: 500.0,
+            point_defense: PointDefense {
+                turrets: vec![turret(1), turret(2)],
+                warning: MissileWarning {
+                    danger_radius: 100.0,
+                    horizon: Seconds(10.0),
+                    bullet_speed: 900.0,
+                },
+                leak_time: 0.5,
+                arc: Sector::full(),
+            },
+            abilities: Abilities::new(&[Ability::Shield]),
+            tracker: Tracker::new(),
+            radar: RadarScheduler::new(),
+            target: None,
+            leakers: Vec::new(),
+        }
+    }
+
+    pub fn target(&self) -> Option<TrackId> {
+        self.target
+    }
+
+    pub fn leakers(&self) -> &[Warning] {
+        &self.leakers
+    }
+
+    pub fn standoff(&self, flee_acceleration: f64) -> f64 {
+        let inner = no_escape_range(&self.enemy_missile, flee_acceleration) + self.margin;
+        if inner >= self.gun_range {
+            self.gun_range
+        } else {
+            0.5 * (inner + self.gun_range)
+        }
+    }
+
+    pub fn allocate(
+        &self,
+        actor: &impl Kinematic,
+        tracks: &[Track],
+        hull: Option<&Track>,
+    ) -> (Vec<Engagement>, Vec<Warning>) {
+        let (mut engagements, leakers) = self.point_defense.assign(actor, tracks);
+        let Some(hull) = hull else {
+            return (engagements, leakers);
+        };
+        for turret in &self.point_defense.turrets {
+            if engagements.iter().any(|e| e.turret == turret.index) {
+                continue;
+            }
+            let Some(solution) = intercept(actor, hull, turret.bullet_speed) else {
+                continue;
+            };
+            if solution.time.get() * turret.bullet_speed <= turret.range {
+                engagements.push(Engagement {
+                    turret: turret.index,
+                    track: hull.id,
+                    heading: solution.heading,
+                });
+            }
+        }
+        (engagements, leakers)
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let now = current_time();
+        if let Some(contact) = scan() {
+            self.tracker.update_from(actor.position(), &contact, now);
+        }
+        self.tracker.prune(now);
+        self.radar
+            .next(actor.position(), self.tracker.tracks(), now)
+            .apply();
+        self.target = self
+            .target
+            .filter(|id| self.tracker.get(*id).is_some())
+            .or_else(|| nearest(actor, self.tracker.tracks(), Track::is_hull));
+        let hull = self
+            .target
+            .and_then(|id| self.tracker.get(id))
+            .map(|track| track.predict(now));
+        let (engagements, leakers) = self.allocate(actor, self.tracker.tracks(), hull.as_ref());
+        for engagement in &engagements {
+            aim(engagement.turret, engagement.heading);
+            fire(engagement.turret);
+        }
+        self.abilities.shield(now, &leakers);
+        self.leakers = leakers;
+        let Some(hull) = hull else {
+            return;
+        };
+        let result = KeepDistance {
+            target: hull.position,
+            distance: self.standoff(actor.max_linear_acceleration()),
+        }
+        .execute(actor);
+        accelerate(result.linear);
+        self.main_gun.engage_track(actor, &hull);
+    }
+}
+
+pub struct CruiserDuel {
+    pub turret: Turret,
+    pub missiles: Volley,
+    pub torpedoes: Volley,
+    pub point_defense: PointDefense,
+    pub abilities: Abilities,
+    pub tracker: Tracker,
+    pub radar: RadarScheduler,
+    pub range: f64,
+    pub derating: f64,
+    pub saturation: usize,
+    pub slack: f64,
+    target: Option<TrackId>,
+    wave: Option<f64>,
+}
+
+impl Default for CruiserDuel {
+    fn default() -> Self {
+        CruiserDuel::new()
+    }
+}
+
+impl CruiserDuel {
+    pub fn new() -> CruiserDuel {
+        let launcher = |index, channel, acceleration, delta_v, lifetime| {
+            Launcher::new(
+                index,
+                Performance {
+                    acceleration,
+                    delta_v,
+                    lifetime,
+                },
+                channel,
+            )
+        };
+        let turret = Turret {
+            index: 0,
+            bullet_speed: 2000.0,
+            range: 4_000.0,
+        };
+        CruiserDuel {
+            turret,
+            missiles: Volley {
+                launchers: vec![
+                    launcher(1, 1, 300.0, 3_000.0, 20.0),
+                    launcher(2, 1, 300.0, 3_000.0, 20.0),
+                ],
+                min_ready: 2,
+            },
+            torpedoes: Volley {
+                launchers: vec![launcher(3, 2, 70.0, 4_000.0, 60.0)],
+                min_ready: 1,
+            },
+            point_defense: PointDefense {
+                turrets: vec![turret],
+                warning: MissileWarning {
+                    danger_radius: 200.0,
+                    horizon: Seconds(15.0),
+                    bullet_speed: 900.0,
+                },
+                leak_time: 1.0,
+                arc: Sector::full(),
+            },
+            abilities: Abilities::for_class(Class::Cruiser),
+            tracker: Tracker::new(),
+            radar: RadarScheduler::new(),
+            range: 8_000.0,
+            derating: 0.5,
+            saturation: 2,
+            slack: 0.5,
+            target: None,
+            wave: None,
+        }
+    }
+
+    pub fn target(&self) -> Option<TrackId> {
+        self.target
+    }
+
+    pub fn wave(&self) -> Option<f64> {
+        self.wave
+    }
+
+    pub fn open_wave(&mut self, now: f64, torpedo_time: f64) {
+        self.wave = Some(now + torpedo_time);
+    }
+
+    pub fn release(&self, now: f64, missile_time: f64) -> bool {
+        self.wave
+            .is_some_and(|impact| now + missile_time >= impact - self.slack)
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let now = current_time();
+        if let Some(contact) = scan() {
+            self.tracker.update_from(actor.position(), &contact, now);
+        }
+        for &id in self.tracker.prune(now).iter() {
+            self.missiles.forget(id);
+            self.torpedoes.forget(id);
+        }
+        self.missiles.reassign(actor, self.tracker.tracks(), now);
+        self.torpedoes.reassign(actor, self.tracker.tracks(), now);
+        self.radar
+            .next(actor.position(), self.tracker.tracks(), now)
+            .apply();
+        let (engagements, leakers) = self.point_defense.assign(actor, self.tracker.tracks());
+        self.abilities.shield(now, &leakers);
+        self.abilities.decoy(now, &leakers);
+        self.target = self
+            .target
+            .filter(|id| self.tracker.get(*id).is_some())
+            .or_else(|| nearest(actor, self.tracker.tracks(), Track::is_target));
+        let hull = self
+            .target
+            .and_then(|id| self.tracker.get(id))
+            .map(|track| track.predict(now));
+        if let Some(engagement) = engagements.first() {
+            aim(engagement.turret, engagement.heading);
+            fire(engagement.turret);
+        } else if let Some(hull) = &hull {
+            if let Some(solution) = intercept(actor, hull, self.turret.bullet_speed) {
+                aim(self.turret.index, solution.heading);
+                fire(self.turret.index);
+            }
+        }
+        let Some(hull) = hull else {
+            self.wave = None;
+            return;
+        };
+        let torpedo_time = self.torpedoes.flight_time(actor, &hull);
+        let missile_time = self.missiles.flight_time(actor, &hull);
+        for launcher in &self.torpedoes.launchers {
+            launcher.uplink.transmit(hull.id, &hull);
+        }
+        for launcher in &self.missiles.launchers {
+            launcher.uplink.transmit(hull.id, &hull);
+        }
+        match (self.wave, torpedo_time, missile_time) {
+            (None, Some(torpedo_time), Some(_))
+                if self.missiles.ready() >= self.saturation
+                    && self.torpedoes.engage(actor, &hull) > 0 =>
+            {
+                self.open_wave(now, torpedo_time);
+            }
+            (Some(impact), _, Some(missile_time)) if self.release(now, missile_time) => {
+                let fired = self.missiles.engage(actor, &hull);
+                if fired > 0 || now > impact {
+                    self.wave = None;
+                }
+            }
+            (Some(impact), _, None) if now > impact => self.wave = None,
+            _ => {}
+        }
+        let result = KeepDistance {
+            target: hull.position,
+            distance: self.range,
+        }
+        .execute(&Derated {
+            actor,
+            factor: self.derating,
+        });
+        accelerate(result.linear);
+    }
+}
+
+pub struct BaseDefense {
+    pub asset: Vec2,
+    pub rank: usize,
+    pub defenders: usize,
+    pub station_radius: f64,
+    pub danger_radius: f64,
+    pub gun: Gun,
+    pub tracker: Tracker,
+    pub radar: RadarScheduler,
+    target: Option<TrackId>,
+}
+
+impl BaseDefense {
+    pub fn new(asset: Vec2, rank: usize, defenders: usize) -> BaseDefense {
+        BaseDefense {
+            asset,
+            rank,
+            defenders: defenders.max(1),
+            station_radius: 1_000.0,
+            danger_radius: 500.0,
+            gun: Gun {
+                index: 0,
+                speed: 1000.0,
+                tolerance: 0.02,
+                turn_gain: 10.0,
+                max_age: 1.0,
+            },
+            tracker: Tracker::new(),
+            radar: RadarScheduler::new(),
+            target: None,
+        }
+    }
+
+    pub fn target(&self) -> Option<TrackId> {
+        self.target
+    }
+
+    pub fn sector(&self) -> Sector {
+        let width = std::f64::consts::TAU / self.defenders as f64;
+        Sector {
+            start: (self.rank % self.defenders) as f64 * width - width / 2.0,
+            width,
+        }
+    }
+
+    pub fn station(&self) -> Vec2 {
+        self.asset + vec2(self.station_radius, 0.0).rotate(self.sector().center())
+    }
+
+    pub fn arrival(&self, track: &Track) -> Option<f64> {
+        let asset = TargetState {
+            position: self.asset,
+            velocity: vec2(0.0, 0.0),
+        };
+        let approach = closest_approach(&asset, track);
+        (approach.distance <= self.danger_radius).then_some(approach.time.get())
+    }
+
+    pub fn priority(&self, tracks: &[Track]) -> Option<TrackId> {
+        let sector = self.sector();
+        tracks
+            .iter()
+            .filter(|track| sector.contains((track.position - self.asset).angle()))
+            .filter_map(|track| Some((track.id, self.arrival(track)?)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id)
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let now = current_time();
+        if let Some(contact) = scan() {
+            self.tracker.update_from(actor.position(), &contact, now);
+        }
+        self.tracker.prune(now);
+        let tracks: Vec<Track> = self
+            .tracker
+            .tracks()
+            .iter()
+            .map(|track| track.predict(now))
+            .collect();
+        self.target = self.priority(&tracks);
+        let result = Arrive::new(self.station()).execute(actor);
+        accelerate(result.linear);
+        match self
+            .target
+            .and_then(|id| tracks.iter().find(|t| t.id == id))
+        {
+            Some(track) => {
+                self.gun.engage_track(actor, track);
+                set_radar_heading((track.position - actor.position()).angle());
+                set_radar_width(0.05);
+            }
+            None => self
+                .radar
+                .next(actor.position(), self.tracker.tracks(), now)
+                .apply(),
+        }
+    }
+}
+
+pub struct Furball {
+    pub gunnery: Gunnery,
+    pub evasion: AdaptiveEvasion,
+    pub kill_range: f64,
+    pub threat_range: f64,
+    pub pursuit: f64,
+    pub margin: f64,
+    kill: Option<TrackId>,
+    threat: Option<TrackId>,
+}
+
+impl Default for Furball {
+    fn default() -> Self {
+        Furball::new()
+    }
+}
+
+impl Furball {
+    pub fn new() -> Furball {
+        Furball {
+            gunnery: Gunnery::new(),
+            evasion: AdaptiveEvasion::new(seed() as u64),
+            kill_range: 2_000.0,
+            threat_range: 3_000.0,
+            pursuit: 0.3,
+            margin: 1_000.0,
+            kill: None,
+            threat: None,
+        }
+    }
+
+    pub fn kill(&self) -> Option<TrackId> {
+        self.kill
+    }
+
+    pub fn threat(&self) -> Option<TrackId> {
+        self.threat
+    }
+
+    pub fn best_kill(&self, actor: &impl Kinematic, tracks: &[Track]) -> Option<TrackId> {
+        let score = |track: &Track| {
+            let offset = track.position - actor.position();
+            let error = angle_diff(actor.heading(), offset.angle()).abs();
+            Coordinator::value(track.class) / (offset.length().max(1.0) * (1.0 + error))
+        };
+        tracks
+            .iter()
+            .filter(|track| {
+                track.is_hull() && track.position.distance(actor.position()) <= self.kill_range
+            })
+            .max_by(|a, b| score(a).total_cmp(&score(b)))
+            .map(|track| track.id)
+    }
+
+    pub fn worst_threat(&self, actor: &impl Kinematic, tracks: &[Track]) -> Option<TrackId> {
+        let danger = |track: &Track| {
+            let approach = closest_approach(actor, track);
+            Coordinator::value(track.class)
+                / ((1.0 + approach.time.get()) * (1.0 + approach.distance / 100.0))
+        };
+        tracks
+            .iter()
+            .filter(|track| track.position.distance(actor.position()) <= self.threat_range)
+            .max_by(|a, b| danger(a).total_cmp(&danger(b)))
+            .map(|track| track.id)
+    }
+
+    pub fn bearing(actor: &impl Kinematic, threat: Option<&Track>) -> Option<f64> {
+        threat.map(|track| angle_diff(actor.heading(), (track.position - actor.position()).angle()))
+    }
+
+    pub fn jink(&self, actor: &(impl Kinematic + Motor), threat: Option<&Track>) -> Vec2 {
+        let axis = match threat {
+            Some(track) => track.position - actor.position(),
+            None if actor.velocity().length() > 1.0 => actor.velocity(),
+            None => vec2(1.0, 0.0).rotate(actor.heading()),
+        };
+        let bearing = Furball::bearing(actor, threat);
+        perpendicular(axis.normalize())
+            * self
+                .evasion
+                .lateral(bearing, actor.max_linear_acceleration())
+    }
+
+    pub fn boundary(&self, actor: &(impl Kinematic + Motor), arena: &Arena) -> Option<Vec2> {
+        let stop = actor.position()
+            + actor.velocity() * (0.5 * actor.braking_time(actor.velocity().length()).get());
+        if arena.contains(stop) {
+            return None;
+        }
+        let inward = arena.clamp(stop) - stop;
+        Some(inward.normalize() * actor.max_linear_acceleration())
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let now = current_time();
+        if let Some(contact) = scan() {
+            self.gunnery
+                .tracker
+                .update_from(actor.position(), &contact, now);
+        }
+        self.gunnery.tracker.prune(now);
+        let tracks: Vec<Track> = self
+            .gunnery
+            .tracker
+            .tracks()
+            .iter()
+            .map(|track| track.predict(now))
+            .collect();
+        self.kill = self.best_kill(actor, &tracks);
+        self.threat = self.worst_threat(actor, &tracks);
+        let find = |id: Option<TrackId>| id.and_then(|id| tracks.iter().find(|t| t.id == id));
+        let bearing = Furball::bearing(actor, find(self.threat));
+        self.evasion.observe(health(), bearing, now);
+        let mut linear = self.jink(actor, find(self.threat));
+        match find(self.kill) {
+            Some(track) => {
+                let (output, fire_now) = self.gunnery.command(actor, track);
+                torque(output.angular);
+                if fire_now {
+                    fire(self.gunnery.gun);
+                }
+                linear += Seek::new(track.position).execute(actor).linear * self.pursuit;
+                set_radar_heading((track.position - actor.position()).angle());
+                set_radar_width(self.gunnery.radar_width);
+            }
+            None => self
+                .gunnery
+                .radar
+                .next(actor.position(), self.gunnery.tracker.tracks(), now)
+                .apply(),
+        }
+        if let Some(inward) = self.boundary(actor, &Arena::current(self.margin)) {
+            linear = inward;
+        }
+        accelerate(linear);
+    }
+}
+
+pub struct Transit {
+    pub strategy: FlipAndBurn,
+    pub abilities: Abilities,
+}
+
+impl Transit {
+    pub fn new(goal: Vec2) -> Transit {
+        Transit {
+            strategy: FlipAndBurn::new(goal),
+            abilities: Abilities::for_class(class()),
+        }
+    }
+
+    pub fn phase(&self, actor: &(impl Kinematic + Motor)) -> TransitPhase {
+        self.strategy.phase(actor)
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let output = self.strategy.execute(actor);
+        accelerate(output.linear);
+        torque(output.angular);
+        if self.phase(actor) == TransitPhase::Burn {
+            self.abilities
+                .boost(current_time(), self.strategy.eta(actor) / 2.0);
+        }
+    }
+}
+
+pub struct EcmDuel {
+    pub gunnery: Gunnery,
+    pub ecm_period: f64,
+    pub ecm_duty: f64,
+    pub jam_snr: f64,
+    pub burn_through: f64,
+    pub strobe_window: f64,
+    pub min_spread: f64,
+    pub min_conditioning: f64,
+    strobes: Vec<Strobe>,
+    passive: Option<Vec2>,
+    motion: Option<Motion>,
+}
+
+impl Default for EcmDuel {
+    fn default() -> Self {
+        EcmDuel::new()
+    }
+}
+
+impl EcmDuel {
+    pub fn new() -> EcmDuel {
+        EcmDuel {
+            gunnery: Gunnery::new(),
+            ecm_period: 2.0,
+            ecm_duty: 0.5,
+            jam_snr: 0.0,
+            burn_through: 3_000.0,
+            strobe_window: 5.0,
+            min_spread: 0.05,
+            min_conditioning: 1e-9,
+            strobes: Vec::new(),
+            passive: None,
+            motion: None,
+        }
+    }
+
+    pub fn strobes(&self) -> &[Strobe] {
+        &self.strobes
+    }
+
+    pub fn passive(&self) -> Option<Vec2> {
+        self.passive
+    }
+
+    pub fn motion(&self) -> Option<Motion> {
+        self.motion
+    }
+
+    pub fn ecm(&self, now: f64) -> bool {
+        (now / self.ecm_period).fract() < self.ecm_duty
+    }
+
+    pub fn jammed(&self, contact: &ScanResult) -> bool {
+        contact.snr < self.jam_snr
+    }
+
+    pub fn strobe(&mut self, origin: Vec2, bearing: f64, now: f64) {
+        let window = self.strobe_window;
+        self.strobes.retain(|strobe| now - strobe.time <= window);
+        self.strobes.push(Strobe {
+            origin,
+            bearing,
+            time: now,
+        });
+        self.motion = motion_analysis(&self.strobes, self.min_conditioning);
+        self.passive = self
+            .motion
+            .map(|motion| motion.position)
+            .or_else(|| triangulate(&self.strobes, self.min_spread))
+            .or(self.passive);
+    }
+
+    pub fn committed(&self, actor: &impl Kinematic, track: &Track) -> bool {
+        actor.position().distance(track.position) <= self.burn_through
+            && track.age <= self.gunnery.max_age
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let now = current_time();
+        set_radar_ecm_mode(if self.ecm(now) {
+            EcmMode::Noise
+        } else {
+            EcmMode::None
+        });
+        if let Some(contact) = scan() {
+            if self.jammed(&contact) {
+                let bearing = (contact.position - actor.position()).angle();
+                self.strobe(actor.position(), bearing, now);
+                if let Some(motion) = self.motion {
+                    let estimate = ScanResult {
+                        position: motion.position,
+                        velocity: motion.velocity,
+                        ..contact
+                    };
+                    self.gunnery
+                        .tracker
+                        .update_from(actor.position(), &estimate, now);
+                }
+            } else {
+                self.gunnery
+                    .tracker
+                    .update_from(actor.position(), &contact, now);
+            }
+        }
+        self.gunnery.tracker.prune(now);
+        let track = nearest(actor, self.gunnery.tracker.tracks(), Track::is_hull)
+            .and_then(|id| self.gunnery.tracker.get(id))
+            .map(|track| track.predict(now));
+        let aim_point = track.as_ref().map(|track| track.position).or(self.passive);
+        let Some(aim_point) = aim_point else {
+            self.gunnery
+                .radar
+                .next(actor.position(), self.gunnery.tracker.tracks(), now)
+                .apply();
+            return;
+        };
+        set_radar_heading((aim_point - actor.position()).angle());
+        set_radar_width(self.gunnery.radar_width);
+        match track.filter(|track| self.committed(actor, track)) {
+            Some(track) => {
+                let (output, fire_now) = self.gunnery.command(actor, &track);
+                torque(output.angular);
+                if fire_now {
+                    fire(self.gunnery.gun);
+                }
+                let result = KeepDistance {
+                    target: track.position,
+                    distance: 0.5 * self.burn_through,
+                }
+                .execute(actor);
+                accelerate(result.linear);
+            }
+            None => {
+                let output = Align::new((aim_point - actor.position()).angle())
+                    .attitude(self.gunnery.attitude)
+                    .execute(actor);
+                torque(output.angular);
+                accelerate(Seek::new(aim_point).execute(actor).linear);
+            }
+        }
+    }
+}
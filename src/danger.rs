@@ -0,0 +1,192 @@
+use crate::movement::*;
+use crate::perception::Track;
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Envelope {
+    Gun {
+        range: f64,
+        cone: f64,
+        rear: f64,
+        weight: f64,
+    },
+    Munition {
+        acceleration: f64,
+        horizon: f64,
+        fuze: f64,
+        weight: f64,
+    },
+}
+
+impl Envelope {
+    pub fn for_class(class: Class) -> Vec<Envelope> {
+        let gun = |range, weight| Envelope::Gun {
+            range,
+            cone: 0.5,
+            rear: 0.3,
+            weight,
+        };
+        let munition = |acceleration, horizon, fuze, weight| Envelope::Munition {
+            acceleration,
+            horizon,
+            fuze,
+            weight,
+        };
+        match class {
+            Class::Fighter => vec![gun(2_000.0, 1.0), munition(300.0, 10.0, 100.0, 0.5)],
+            Class::Frigate => vec![gun(4_000.0, 2.0), munition(300.0, 15.0, 100.0, 1.0)],
+            Class::Cruiser => vec![gun(3_000.0, 2.0), munition(300.0, 20.0, 200.0, 2.0)],
+            Class::Missile => vec![munition(300.0, 5.0, 100.0, 3.0)],
+            Class::Torpedo => vec![munition(70.0, 10.0, 200.0, 4.0)],
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn risk(&self, threat: &impl Kinematic, point: Vec2) -> f64 {
+        let offset = point - threat.position();
+        let distance = offset.length();
+        match *self {
+            Envelope::Gun {
+                range,
+                cone,
+                rear,
+                weight,
+            } => {
+                if distance >= range {
+                    return 0.0;
+                }
+                let falloff = 1.0 - distance / range;
+                let velocity = threat.velocity();
+                let aligned = distance <= 0.0
+                    || velocity.length() < 1.0
+                    || angle_diff(velocity.angle(), offset.angle()).abs() <= cone;
+                weight * falloff * if aligned { 1.0 } else { rear }
+            }
+            Envelope::Munition {
+                acceleration,
+                horizon,
+                fuze,
+                weight,
+            } => {
+                let velocity = threat.velocity();
+                let speed_squared = velocity.dot(velocity);
+                let time = if speed_squared > 0.0 {
+                    (offset.dot(velocity) / speed_squared).clamp(0.0, horizon)
+                } else {
+                    0.0
+                };
+                let reach = fuze + 0.5 * acceleration * time * time;
+                let miss = (offset - velocity * time).length();
+                if miss >= reach {
+                    return 0.0;
+                }
+                weight * (1.0 - miss / reach) * (1.0 - time / horizon).max(0.0)
+            }
+        }
+    }
+}
+
+pub struct DangerMap {
+    pub origin: Vec2,
+    pub cell: f64,
+    pub width: usize,
+    pub height: usize,
+    risk: Vec<f64>,
+}
+
+impl DangerMap {
+    pub fn new(center: Vec2, extent: f64, cell: f64) -> DangerMap {
+        let size = ((2.0 * extent / cell).ceil() as usize).max(1);
+        DangerMap {
+            origin: center - vec2(extent, extent),
+            cell,
+            width: size,
+            height: size,
+            risk: vec![0.0; size * size],
+        }
+    }
+
+    pub fn build(center: Vec2, extent: f64, cell: f64, tracks: &[Track]) -> DangerMap {
+        let mut map = DangerMap::new(center, extent, cell);
+        for track in tracks {
+            for envelope in Envelope::for_class(track.class) {
+                map.add(track, &envelope);
+            }
+        }
+        map
+    }
+
+    pub fn clear(&mut self) {
+        self.risk.fill(0.0);
+    }
+
+    pub fn add(&mut self, threat: &impl Kinematic, envelope: &Envelope) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let risk = envelope.risk(threat, self.center(x, y));
+                self.risk[y * self.width + x] += risk;
+            }
+        }
+    }
+
+    pub fn center(&self, x: usize, y: usize) -> Vec2 {
+        self.origin + vec2(x as f64 + 0.5, y as f64 + 0.5) * self.cell
+    }
+
+    pub fn cell_of(&self, point: Vec2) -> Option<(usize, usize)> {
+        let local = (point - self.origin) / self.cell;
+        if local.x < 0.0 || local.y < 0.0 {
+            return None;
+        }
+        let (x, y) = (local.x as usize, local.y as usize);
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    pub fn cell_risk(&self, x: usize, y: usize) -> f64 {
+        self.risk[y * self.width + x]
+    }
+
+    pub fn max(&self) -> f64 {
+        self.risk.iter().copied().fold(0.0, f64::max)
+    }
+
+    pub fn risk(&self, point: Vec2) -> f64 {
+        let local = (point - self.origin) / self.cell - vec2(0.5, 0.5);
+        let clamp = |value: f64, size: usize| value.clamp(0.0, (size - 1) as f64);
+        let (fx, fy) = (clamp(local.x, self.width), clamp(local.y, self.height));
+        let (x0, y0) = (fx as usize, fy as usize);
+        let (x1, y1) = ((x0 + 1).min(self.width - 1), (y0 + 1).min(self.height - 1));
+        let (tx, ty) = (fx - x0 as f64, fy - y0 as f64);
+        let top = self.cell_risk(x0, y0) * (1.0 - tx) + self.cell_risk(x1, y0) * tx;
+        let bottom = self.cell_risk(x0, y1) * (1.0 - tx) + self.cell_risk(x1, y1) * tx;
+        top * (1.0 - ty) + bottom * ty
+    }
+
+    pub fn gradient(&self, point: Vec2) -> Vec2 {
+        let h = self.cell;
+        vec2(
+            self.risk(point + vec2(h, 0.0)) - self.risk(point - vec2(h, 0.0)),
+            self.risk(point + vec2(0.0, h)) - self.risk(point - vec2(0.0, h)),
+        ) / (2.0 * h)
+    }
+}
+
+pub struct AvoidDanger<'a> {
+    pub map: &'a DangerMap,
+    pub gain: f64,
+}
+
+impl Move for AvoidDanger<'_> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let gradient = self.map.gradient(actor.position());
+        let slope = gradient.length();
+        if slope <= 0.0 || !slope.is_finite() {
+            return Output::zero();
+        }
+        let urgency = (self.map.risk(actor.position()) * self.gain).min(1.0);
+        Output {
+            linear: -gradient / slope * (urgency * actor.max_linear_acceleration()),
+            angular: 0.0,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use crate::movement::Kinematic;
+use crate::perception::{Sector, Track, TrackId};
+use crate::targeting::intercept;
+use crate::warning::{MissileWarning, Munition, Warning};
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Turret {
+    pub index: usize,
+    pub bullet_speed: f64,
+    pub range: f64,
+}
+
+pub struct Engagement {
+    pub turret: usize,
+    pub track: TrackId,
+    pub heading: f64,
+}
+
+pub struct PointDefense {
+    pub turrets: Vec<Turret>,
+    pub warning: MissileWarning,
+    pub leak_time: f64,
+    pub arc: Sector,
+}
+
+impl PointDefense {
+    pub fn threats(&self, own: &impl Kinematic, tracks: &[Track]) -> Vec<Warning> {
+        let mut threats = self.warning.scan(own, tracks);
+        threats.retain(|warning| {
+            warning.munition != Munition::Bullet
+                && tracks
+                    .iter()
+                    .find(|track| track.id == warning.track)
+                    .is_some_and(|track| {
+                        self.arc.contains((track.position - own.position()).angle())
+                    })
+        });
+        threats
+    }
+
+    pub fn assign(
+        &self,
+        own: &impl Kinematic,
+        tracks: &[Track],
+    ) -> (Vec<Engagement>, Vec<Warning>) {
+        let threats = self.threats(own, tracks);
+        let mut engagements = Vec::new();
+        let mut covered = vec![false; threats.len()];
+        for turret in &self.turrets {
+            let candidate = threats
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !covered[*i] || covered.iter().all(|c| *c))
+                .find_map(|(i, threat)| {
+                    let track = tracks.iter().find(|t| t.id == threat.track)?;
+                    let solution = intercept(own, track, turret.bullet_speed)?;
+                    (solution.time.get() * turret.bullet_speed <= turret.range)
+                        .then_some((i, solution))
+                });
+            if let Some((i, solution)) = candidate {
+                covered[i] = true;
+                engagements.push(Engagement {
+                    turret: turret.index,
+                    track: threats[i].track,
+                    heading: solution.heading,
+                });
+            }
+        }
+        let leakers = threats
+            .iter()
+            .zip(&covered)
+            .filter(|(threat, covered)| !**covered || threat.time_to_impact.get() < self.leak_time)
+            .map(|(threat, _)| *threat)
+            .collect();
+        (engagements, leakers)
+    }
+
+    pub fn engage(&self, own: &impl Kinematic, tracks: &[Track]) -> Vec<Warning> {
+        let (engagements, leakers) = self.assign(own, tracks);
+        for engagement in &engagements {
+            aim(engagement.turret, engagement.heading);
+            fire(engagement.turret);
+        }
+        leakers
+    }
+}
@@ -0,0 +1,153 @@
+use oort_api::prelude::*;
+
+pub trait DebugDraw {
+    fn line(&self, from: Vec2, to: Vec2, color: u32);
+    fn square(&self, center: Vec2, size: f64, color: u32);
+    fn diamond(&self, center: Vec2, size: f64, color: u32);
+    fn polygon(&self, center: Vec2, size: f64, sides: i32, angle: f64, color: u32);
+    fn text(&self, position: Vec2, color: u32, text: &str);
+}
+
+pub struct OortDraw;
+
+impl DebugDraw for OortDraw {
+    fn line(&self, from: Vec2, to: Vec2, color: u32) {
+        draw_line(from, to, color);
+    }
+    fn square(&self, center: Vec2, size: f64, color: u32) {
+        draw_square(center, size, color);
+    }
+    fn diamond(&self, center: Vec2, size: f64, color: u32) {
+        draw_diamond(center, size, color);
+    }
+    fn polygon(&self, center: Vec2, size: f64, sides: i32, angle: f64, color: u32) {
+        draw_polygon(center, size, sides, angle, color);
+    }
+    fn text(&self, position: Vec2, color: u32, text: &str) {
+        draw_text!(position, color, "{}", text);
+    }
+}
+
+pub struct NoDraw;
+
+impl DebugDraw for NoDraw {
+    fn line(&self, _from: Vec2, _to: Vec2, _color: u32) {}
+    fn square(&self, _center: Vec2, _size: f64, _color: u32) {}
+    fn diamond(&self, _center: Vec2, _size: f64, _color: u32) {}
+    fn polygon(&self, _center: Vec2, _size: f64, _sides: i32, _angle: f64, _color: u32) {}
+    fn text(&self, _position: Vec2, _color: u32, _text: &str) {}
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layer {
+    Steering,
+    Tracks,
+    Radar,
+    Path,
+    Formation,
+    Friendlies,
+    Assignments,
+    Danger,
+    Hud,
+}
+
+impl Layer {
+    pub const ALL: [Layer; 9] = [
+        Layer::Steering,
+        Layer::Tracks,
+        Layer::Radar,
+        Layer::Path,
+        Layer::Formation,
+        Layer::Friendlies,
+        Layer::Assignments,
+        Layer::Danger,
+        Layer::Hud,
+    ];
+
+    fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Layers {
+    mask: u32,
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Layers::all()
+    }
+}
+
+impl Layers {
+    pub fn all() -> Layers {
+        Layers::only(&Layer::ALL)
+    }
+
+    pub fn none() -> Layers {
+        Layers { mask: 0 }
+    }
+
+    pub fn only(layers: &[Layer]) -> Layers {
+        Layers {
+            mask: layers.iter().fold(0, |mask, layer| mask | layer.bit()),
+        }
+    }
+
+    pub fn enabled(&self, layer: Layer) -> bool {
+        self.mask & layer.bit() != 0
+    }
+
+    pub fn set(&mut self, layer: Layer, enabled: bool) {
+        if enabled {
+            self.mask |= layer.bit();
+        } else {
+            self.mask &= !layer.bit();
+        }
+    }
+
+    pub fn toggle(&mut self, layer: Layer) {
+        self.mask ^= layer.bit();
+    }
+
+    pub fn on<'a, D: DebugDraw>(&self, layer: Layer, draw: &'a D) -> Gated<'a, D> {
+        Gated {
+            draw,
+            enabled: self.enabled(layer),
+        }
+    }
+}
+
+pub struct Gated<'a, D: DebugDraw> {
+    draw: &'a D,
+    enabled: bool,
+}
+
+impl<D: DebugDraw> DebugDraw for Gated<'_, D> {
+    fn line(&self, from: Vec2, to: Vec2, color: u32) {
+        if self.enabled {
+            self.draw.line(from, to, color);
+        }
+    }
+    fn square(&self, center: Vec2, size: f64, color: u32) {
+        if self.enabled {
+            self.draw.square(center, size, color);
+        }
+    }
+    fn diamond(&self, center: Vec2, size: f64, color: u32) {
+        if self.enabled {
+            self.draw.diamond(center, size, color);
+        }
+    }
+    fn polygon(&self, center: Vec2, size: f64, sides: i32, angle: f64, color: u32) {
+        if self.enabled {
+            self.draw.polygon(center, size, sides, angle, color);
+        }
+    }
+    fn text(&self, position: Vec2, color: u32, text: &str) {
+        if self.enabled {
+            self.draw.text(position, color, text);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::movement::*;
+use crate::perception::Track;
+use crate::targeting::closest_approach;
+use crate::time::Seconds;
+use crate::weapons::Coordinator;
+use oort_api::prelude::*;
+
+pub struct Escort<'a, C: Kinematic + Copy> {
+    pub charge: C,
+    pub threats: &'a [Track],
+    pub standoff: f64,
+    pub side: Vec2,
+    pub screen_radius: f64,
+    pub gain: f64,
+}
+
+impl<C: Kinematic + Copy> Escort<'_, C> {
+    pub fn inbound(&self) -> Option<&Track> {
+        self.threats
+            .iter()
+            .filter(|track| track.is_munition())
+            .map(|track| (track, closest_approach(&self.charge, track)))
+            .filter(|(_, approach)| {
+                approach.time > Seconds::ZERO && approach.distance < self.screen_radius
+            })
+            .min_by(|a, b| a.1.time.total_cmp(&b.1.time))
+            .map(|(track, _)| track)
+    }
+
+    pub fn threat(&self) -> Option<&Track> {
+        let danger = |track: &Track| {
+            Coordinator::value(track.class)
+                / track.position.distance(self.charge.position()).max(1.0)
+        };
+        self.threats
+            .iter()
+            .filter(|track| track.is_hull())
+            .max_by(|a, b| danger(a).total_cmp(&danger(b)))
+    }
+
+    pub fn station(&self) -> Vec2 {
+        match self.threat() {
+            Some(threat) => {
+                let bearing = (threat.position - self.charge.position()).normalize();
+                (bearing * self.standoff).rotate(-self.charge.heading())
+            }
+            None => self.side,
+        }
+    }
+}
+
+impl<C: Kinematic + Copy> Move for Escort<'_, C> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        if let Some(missile) = self.inbound() {
+            let approach = closest_approach(actor, missile);
+            let target = missile.position + missile.velocity * approach.time;
+            if approach.time > Seconds::ZERO {
+                return Seek { target }.execute(actor);
+            }
+        }
+        OffsetPursuit {
+            leader: self.charge,
+            offset: self.station(),
+            gain: self.gain,
+        }
+        .execute(actor)
+    }
+}
@@ -0,0 +1,212 @@
+use crate::math::perpendicular;
+use crate::movement::*;
+use crate::perception::Track;
+use crate::random;
+use oort_api::prelude::*;
+
+pub struct Retreat<'a> {
+    pub threat: Vec2,
+    pub obstacles: &'a [Track],
+    pub cover_radius: f64,
+    pub cover_range: f64,
+    pub side: f64,
+    pub deflection: f64,
+}
+
+impl Retreat<'_> {
+    pub fn cover(&self, position: Vec2) -> Option<Vec2> {
+        self.obstacles
+            .iter()
+            .filter(|obstacle| obstacle.class == Class::Asteroid)
+            .filter(|obstacle| obstacle.position.distance(position) < self.cover_range)
+            .map(|obstacle| {
+                let shadow = (obstacle.position - self.threat).normalize();
+                obstacle.position + shadow * self.cover_radius
+            })
+            .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)))
+    }
+}
+
+impl Move for Retreat<'_> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let position = actor.position();
+        let away = match self.cover(position) {
+            Some(cover) if cover.distance(position) > 1.0 => (cover - position).normalize(),
+            _ => (position - self.threat).normalize(),
+        };
+        let lateral = perpendicular(away) * self.side.signum();
+        let direction = away * self.deflection.cos() + lateral * self.deflection.sin();
+        Output {
+            linear: direction * actor.max_linear_acceleration(),
+            angular: 0.0,
+        }
+    }
+}
+
+pub struct SelfPreservation {
+    pub retreat_below: f64,
+    pub resume_above: f64,
+    pub safe_range: f64,
+    pub jink_period: f64,
+    max_health: f64,
+    retreating: bool,
+}
+
+impl Default for SelfPreservation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelfPreservation {
+    pub fn new() -> SelfPreservation {
+        SelfPreservation {
+            retreat_below: 0.3,
+            resume_above: 0.6,
+            safe_range: 5_000.0,
+            jink_period: 1.5,
+            max_health: 0.0,
+            retreating: false,
+        }
+    }
+
+    pub fn retreating(&self) -> bool {
+        self.retreating
+    }
+
+    pub fn update(&mut self, health: f64, threat_range: Option<f64>) -> bool {
+        self.max_health = self.max_health.max(health);
+        let fraction = if self.max_health > 0.0 {
+            health / self.max_health
+        } else {
+            1.0
+        };
+        let safe = threat_range.is_none_or(|range| range > self.safe_range);
+        self.retreating = if self.retreating {
+            fraction < self.resume_above && !safe
+        } else {
+            fraction < self.retreat_below && !safe
+        };
+        self.retreating
+    }
+
+    pub fn side(&self, now: f64) -> f64 {
+        if (now / self.jink_period).floor() as i64 % 2 == 0 {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+const SECTORS: usize = 4;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Hit {
+    pub time: f64,
+    pub damage: f64,
+    pub bearing: Option<f64>,
+    pub side: f64,
+    pub period: f64,
+    pub amplitude: f64,
+}
+
+pub struct AdaptiveEvasion {
+    pub period: f64,
+    pub amplitude: f64,
+    pub min_period: f64,
+    pub max_period: f64,
+    pub max_amplitude: f64,
+    pub max_bias: f64,
+    pub learning_rate: f64,
+    pub seed: u64,
+    bias: [f64; SECTORS + 1],
+    hits: Vec<Hit>,
+    health: Option<f64>,
+    phase: f64,
+    clock: Option<f64>,
+}
+
+impl AdaptiveEvasion {
+    pub fn new(seed: u64) -> AdaptiveEvasion {
+        AdaptiveEvasion {
+            period: 1.5,
+            amplitude: 0.7,
+            min_period: 0.5,
+            max_period: 3.0,
+            max_amplitude: 1.0,
+            max_bias: 0.8,
+            learning_rate: 0.25,
+            seed,
+            bias: [0.0; SECTORS + 1],
+            hits: Vec::new(),
+            health: None,
+            phase: 0.0,
+            clock: None,
+        }
+    }
+
+    pub fn hits(&self) -> &[Hit] {
+        &self.hits
+    }
+
+    fn sector(bearing: Option<f64>) -> usize {
+        let Some(bearing) = bearing.filter(|bearing| bearing.is_finite()) else {
+            return SECTORS;
+        };
+        let turn = (bearing / std::f64::consts::TAU).rem_euclid(1.0);
+        ((turn * SECTORS as f64) as usize).min(SECTORS - 1)
+    }
+
+    pub fn bias(&self, bearing: Option<f64>) -> f64 {
+        self.bias[Self::sector(bearing)]
+    }
+
+    pub fn side(&self, bearing: Option<f64>) -> f64 {
+        let duty = 0.5 * (1.0 + self.bias(bearing));
+        if self.phase.rem_euclid(1.0) < duty {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+
+    pub fn lateral(&self, bearing: Option<f64>, max_acceleration: f64) -> f64 {
+        self.side(bearing) * self.amplitude * max_acceleration
+    }
+
+    pub fn observe(&mut self, health: f64, bearing: Option<f64>, now: f64) -> Option<Hit> {
+        if let Some(clock) = self.clock {
+            self.phase += ((now - clock) / self.period).max(0.0);
+        }
+        self.clock = Some(now);
+        let damage = self
+            .health
+            .replace(health)
+            .map_or(0.0, |before| before - health);
+        if damage.is_nan() || damage <= 0.0 {
+            return None;
+        }
+        let hit = Hit {
+            time: now,
+            damage,
+            bearing,
+            side: self.side(bearing),
+            period: self.period,
+            amplitude: self.amplitude,
+        };
+        self.hits.push(hit);
+        self.adapt(&hit);
+        Some(hit)
+    }
+
+    fn adapt(&mut self, hit: &Hit) {
+        let rate = self.learning_rate;
+        let bias = &mut self.bias[Self::sector(hit.bearing)];
+        *bias = (*bias - rate * hit.side).clamp(-self.max_bias, self.max_bias);
+        self.amplitude += rate * (self.max_amplitude - self.amplitude);
+        let shift = random::noise(self.seed, self.hits.len() as u64);
+        let factor = 1.0 + rate * (1.0 + shift.abs()) * shift.signum();
+        self.period = (self.period * factor).clamp(self.min_period, self.max_period);
+    }
+}
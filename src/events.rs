@@ -0,0 +1,105 @@
+use crate::perception::TrackId;
+use oort_api::prelude::*;
+use std::collections::VecDeque;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Event {
+    TrackCreated {
+        id: TrackId,
+        class: Class,
+    },
+    TrackLost {
+        id: TrackId,
+    },
+    TargetSwitched {
+        from: Option<TrackId>,
+        to: Option<TrackId>,
+    },
+    WeaponFired {
+        index: usize,
+        target: TrackId,
+    },
+    AbilityUsed(Ability),
+    MessageReceived {
+        sender: u16,
+        kind: u8,
+    },
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Entry {
+    pub time: f64,
+    pub event: Event,
+}
+
+pub struct EventLog {
+    pub capacity: usize,
+    entries: VecDeque<Entry>,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        EventLog::new(128)
+    }
+}
+
+impl EventLog {
+    pub fn new(capacity: usize) -> EventLog {
+        EventLog {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, time: f64, event: Event) {
+        if self.capacity == 0 {
+            return;
+        }
+        while self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(Entry { time, event });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter()
+    }
+
+    pub fn since(&self, time: f64) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().filter(move |entry| entry.time >= time)
+    }
+
+    pub fn matching<'a>(
+        &'a self,
+        predicate: impl Fn(&Event) -> bool + 'a,
+    ) -> impl Iterator<Item = &'a Entry> {
+        self.entries
+            .iter()
+            .filter(move |entry| predicate(&entry.event))
+    }
+
+    pub fn last(&self, count: usize) -> impl Iterator<Item = &Entry> {
+        self.entries
+            .iter()
+            .skip(self.entries.len().saturating_sub(count))
+    }
+
+    pub fn dump(&self, count: usize) -> String {
+        self.last(count)
+            .map(|entry| format!("{:>7.2} {:?}", entry.time, entry.event))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
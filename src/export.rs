@@ -0,0 +1,214 @@
+use crate::math::*;
+use crate::movement::{Kinematic, Output};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub const PREFIX: char = '@';
+
+#[derive(Clone, Copy, Debug)]
+pub enum Record {
+    State {
+        tick: u32,
+        position: Vec2,
+        velocity: Vec2,
+        heading: f64,
+        angular_velocity: f64,
+    },
+    Track {
+        tick: u32,
+        id: u32,
+        class: u16,
+        position: Vec2,
+        velocity: Vec2,
+        age: f64,
+    },
+    Steering {
+        tick: u32,
+        linear: Vec2,
+        angular: f64,
+    },
+}
+
+impl Record {
+    pub fn state(tick: u32, actor: &impl Kinematic) -> Record {
+        Record::State {
+            tick,
+            position: actor.position(),
+            velocity: actor.velocity(),
+            heading: actor.heading(),
+            angular_velocity: actor.angular_velocity(),
+        }
+    }
+
+    pub fn steering(tick: u32, output: &Output) -> Record {
+        Record::Steering {
+            tick,
+            linear: output.linear,
+            angular: output.angular,
+        }
+    }
+
+    pub fn tick(&self) -> u32 {
+        match *self {
+            Record::State { tick, .. }
+            | Record::Track { tick, .. }
+            | Record::Steering { tick, .. } => tick,
+        }
+    }
+
+    fn fields(&self) -> (char, Vec<f64>) {
+        match *self {
+            Record::State {
+                tick,
+                position,
+                velocity,
+                heading,
+                angular_velocity,
+            } => (
+                'S',
+                [
+                    tick as f64,
+                    position.x,
+                    position.y,
+                    velocity.x,
+                    velocity.y,
+                    heading,
+                    angular_velocity,
+                ]
+                .to_vec(),
+            ),
+            Record::Track {
+                tick,
+                id,
+                class,
+                position,
+                velocity,
+                age,
+            } => (
+                'T',
+                [
+                    tick as f64,
+                    id as f64,
+                    class as f64,
+                    position.x,
+                    position.y,
+                    velocity.x,
+                    velocity.y,
+                    age,
+                ]
+                .to_vec(),
+            ),
+            Record::Steering {
+                tick,
+                linear,
+                angular,
+            } => ('O', [tick as f64, linear.x, linear.y, angular].to_vec()),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let (kind, fields) = self.fields();
+        let mut line = format!("{}{}", PREFIX, kind);
+        for value in fields {
+            line.push(' ');
+            line.push_str(&format!("{}", value as f32));
+        }
+        line
+    }
+
+    pub fn decode(line: &str) -> Option<Record> {
+        let mut words = line.split_whitespace();
+        let tag = words.next()?.strip_prefix(PREFIX)?;
+        let values: Vec<f64> = words
+            .map(|word| word.parse::<f32>().map(f64::from))
+            .collect::<Result<_, _>>()
+            .ok()?;
+        match (tag, values.as_slice()) {
+            ("S", &[tick, px, py, vx, vy, heading, angular_velocity]) => Some(Record::State {
+                tick: tick as u32,
+                position: vec2(px, py),
+                velocity: vec2(vx, vy),
+                heading,
+                angular_velocity,
+            }),
+            ("T", &[tick, id, class, px, py, vx, vy, age]) => Some(Record::Track {
+                tick: tick as u32,
+                id: id as u32,
+                class: class as u16,
+                position: vec2(px, py),
+                velocity: vec2(vx, vy),
+                age,
+            }),
+            ("O", &[tick, lx, ly, angular]) => Some(Record::Steering {
+                tick: tick as u32,
+                linear: vec2(lx, ly),
+                angular,
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Vec<Record> {
+    text.lines().filter_map(Record::decode).collect()
+}
+
+#[cfg(feature = "oort")]
+pub struct Exporter {
+    pub enabled: bool,
+    pub interval: u32,
+    pub tracks: bool,
+}
+
+#[cfg(feature = "oort")]
+impl Default for Exporter {
+    fn default() -> Self {
+        Exporter {
+            enabled: false,
+            interval: 1,
+            tracks: true,
+        }
+    }
+}
+
+#[cfg(feature = "oort")]
+impl Exporter {
+    pub fn records(
+        &self,
+        tick: u32,
+        actor: &impl Kinematic,
+        tracks: &[crate::perception::Track],
+        output: &Output,
+    ) -> Vec<Record> {
+        if !self.enabled || !tick.is_multiple_of(self.interval.max(1)) {
+            return Vec::new();
+        }
+        let mut records = Vec::with_capacity(tracks.len() + 2);
+        records.push(Record::state(tick, actor));
+        if self.tracks {
+            records.extend(tracks.iter().map(|track| Record::Track {
+                tick,
+                id: track.id,
+                class: crate::radio::class_code(track.class),
+                position: track.position,
+                velocity: track.velocity,
+                age: track.age,
+            }));
+        }
+        records.push(Record::steering(tick, output));
+        records
+    }
+
+    pub fn emit(
+        &self,
+        tick: u32,
+        actor: &impl Kinematic,
+        tracks: &[crate::perception::Track],
+        output: &Output,
+    ) {
+        for record in self.records(tick, actor, tracks, output) {
+            oort_api::prelude::debug!("{}", record.encode());
+        }
+    }
+}
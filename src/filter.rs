@@ -0,0 +1,702 @@
+use crate::math::*;
+use crate::movement::Kinematic;
+use crate::storage::FixedVec;
+use alloc::vec::Vec;
+
+type Matrix<T = f64> = [[T; 2]; 2];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Outlier {
+    Reject,
+    Downweight,
+}
+
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gate {
+    pub threshold: f64,
+    pub policy: Outlier,
+    pub max_misses: u32,
+}
+
+impl Default for Gate {
+    fn default() -> Self {
+        Gate {
+            threshold: 13.8,
+            policy: Outlier::Reject,
+            max_misses: 3,
+        }
+    }
+}
+
+impl Gate {
+    pub fn sigma(sigma: f64) -> Gate {
+        Gate {
+            threshold: sigma * sigma,
+            ..Gate::default()
+        }
+    }
+
+    pub fn open() -> Gate {
+        Gate {
+            threshold: f64::INFINITY,
+            ..Gate::default()
+        }
+    }
+
+    pub fn with_policy(mut self, policy: Outlier) -> Gate {
+        self.policy = policy;
+        self
+    }
+
+    fn judge(&self, distance: f64, misses: &mut u32) -> (Verdict, f64) {
+        if !distance.is_nan() && distance <= self.threshold {
+            *misses = 0;
+            return (Verdict::Accepted, 1.0);
+        }
+        match self.policy {
+            Outlier::Reject => {
+                *misses += 1;
+                if *misses > self.max_misses {
+                    (Verdict::Reset, 1.0)
+                } else {
+                    (Verdict::Rejected, 1.0)
+                }
+            }
+            Outlier::Downweight => {
+                *misses = 0;
+                (Verdict::Downweighted, distance / self.threshold)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Accepted,
+    Downweighted,
+    Rejected,
+    Reset,
+}
+
+impl Verdict {
+    pub fn applied(self) -> bool {
+        self != Verdict::Rejected
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct DynKalman<T: Scalar = f64> {
+    pub process_noise: T,
+    pub position_noise: T,
+    pub velocity_noise: T,
+    pub gate: Gate,
+    pub position: T,
+    pub velocity: T,
+    covariance: Matrix<T>,
+    misses: u32,
+    initialized: bool,
+}
+
+impl<T: Scalar> Default for DynKalman<T> {
+    fn default() -> Self {
+        DynKalman::new(
+            T::from_f64(3_600.0),
+            T::from_f64(2_500.0),
+            T::from_f64(400.0),
+        )
+    }
+}
+
+impl<T: Scalar> DynKalman<T> {
+    pub fn new(process_noise: T, position_noise: T, velocity_noise: T) -> DynKalman<T> {
+        DynKalman {
+            process_noise,
+            position_noise,
+            velocity_noise,
+            gate: Gate::default(),
+            position: T::ZERO,
+            velocity: T::ZERO,
+            covariance: [[T::ZERO; 2]; 2],
+            misses: 0,
+            initialized: false,
+        }
+    }
+
+    pub fn with_gate(mut self, gate: Gate) -> DynKalman<T> {
+        self.gate = gate;
+        self
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    pub fn misses(&self) -> u32 {
+        self.misses
+    }
+
+    pub fn covariance(&self) -> Matrix<T> {
+        self.covariance
+    }
+
+    pub fn reset(&mut self, position: T, velocity: T) {
+        self.position = position;
+        self.velocity = velocity;
+        self.covariance = [
+            [self.position_noise, T::ZERO],
+            [T::ZERO, self.velocity_noise],
+        ];
+        self.misses = 0;
+        self.initialized = true;
+    }
+
+    pub fn predict(&mut self, dt: T) {
+        if !self.initialized || dt <= T::ZERO || !dt.is_finite() {
+            return;
+        }
+        self.position = self.position + self.velocity * dt;
+        self.covariance = propagate(self.covariance, dt, self.process_noise);
+    }
+
+    fn noise(&self, inflation: T) -> [T; 2] {
+        [
+            self.position_noise * inflation,
+            self.velocity_noise * inflation,
+        ]
+    }
+
+    pub fn innovation(&self, position: T, velocity: T) -> T {
+        let s = residual_covariance(self.covariance, self.noise(T::ONE));
+        nis(s, [position - self.position, velocity - self.velocity])
+    }
+
+    pub fn update(&mut self, position: T, velocity: T) -> Verdict {
+        if !position.is_finite() || !velocity.is_finite() {
+            return Verdict::Rejected;
+        }
+        if !self.initialized {
+            self.reset(position, velocity);
+            return Verdict::Reset;
+        }
+        let distance = self.innovation(position, velocity).to_f64();
+        let (verdict, inflation) = self.gate.judge(distance, &mut self.misses);
+        if verdict == Verdict::Rejected {
+            return verdict;
+        }
+        let correction = match verdict {
+            Verdict::Reset => None,
+            _ => correct(self.covariance, self.noise(T::from_f64(inflation))),
+        };
+        let Some((gain, covariance)) = correction else {
+            self.reset(position, velocity);
+            return Verdict::Reset;
+        };
+        let [y0, y1] = [position - self.position, velocity - self.velocity];
+        self.position = self.position + gain[0][0] * y0 + gain[0][1] * y1;
+        self.velocity = self.velocity + gain[1][0] * y0 + gain[1][1] * y1;
+        self.covariance = covariance;
+        verdict
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VecKalman {
+    pub process_noise: f64,
+    pub position_noise: f64,
+    pub velocity_noise: f64,
+    pub gate: Gate,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    covariance: Matrix,
+    misses: u32,
+    initialized: bool,
+}
+
+impl Default for VecKalman {
+    fn default() -> Self {
+        let noise = NoiseModel::default();
+        VecKalman::new(3_600.0, noise.position.floor, noise.velocity.floor)
+    }
+}
+
+impl VecKalman {
+    pub fn new(process_noise: f64, position_noise: f64, velocity_noise: f64) -> VecKalman {
+        VecKalman {
+            process_noise,
+            position_noise,
+            velocity_noise,
+            gate: Gate {
+                threshold: 18.5,
+                ..Gate::default()
+            },
+            position: vec2(0.0, 0.0),
+            velocity: vec2(0.0, 0.0),
+            covariance: [[0.0; 2]; 2],
+            misses: 0,
+            initialized: false,
+        }
+    }
+
+    pub fn with_gate(mut self, gate: Gate) -> VecKalman {
+        self.gate = gate;
+        self
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.initialized
+    }
+
+    pub fn misses(&self) -> u32 {
+        self.misses
+    }
+
+    pub fn covariance(&self) -> Matrix {
+        self.covariance
+    }
+
+    pub fn reset(&mut self, position: Vec2, velocity: Vec2) {
+        self.position = position;
+        self.velocity = velocity;
+        self.covariance = [[self.position_noise, 0.0], [0.0, self.velocity_noise]];
+        self.misses = 0;
+        self.initialized = true;
+    }
+
+    pub fn predict(&mut self, dt: f64) {
+        if !self.initialized || dt <= 0.0 || !dt.is_finite() {
+            return;
+        }
+        self.position += self.velocity * dt;
+        self.covariance = propagate(self.covariance, dt, self.process_noise);
+    }
+
+    fn noise(&self, inflation: f64) -> [f64; 2] {
+        [
+            self.position_noise * inflation,
+            self.velocity_noise * inflation,
+        ]
+    }
+
+    pub fn innovation(&self, position: Vec2, velocity: Vec2) -> f64 {
+        let s = residual_covariance(self.covariance, self.noise(1.0));
+        let (dp, dv) = (position - self.position, velocity - self.velocity);
+        nis(s, [dp.x, dv.x]) + nis(s, [dp.y, dv.y])
+    }
+
+    pub fn update(&mut self, position: Vec2, velocity: Vec2) -> Verdict {
+        let finite = [position.x, position.y, velocity.x, velocity.y];
+        if !finite.iter().all(|value| value.is_finite()) {
+            return Verdict::Rejected;
+        }
+        if !self.initialized {
+            self.reset(position, velocity);
+            return Verdict::Reset;
+        }
+        let (verdict, inflation) = self
+            .gate
+            .judge(self.innovation(position, velocity), &mut self.misses);
+        if verdict == Verdict::Rejected {
+            return verdict;
+        }
+        let correction = match verdict {
+            Verdict::Reset => None,
+            _ => correct(self.covariance, self.noise(inflation)),
+        };
+        let Some((gain, covariance)) = correction else {
+            self.reset(position, velocity);
+            return Verdict::Reset;
+        };
+        let (dp, dv) = (position - self.position, velocity - self.velocity);
+        self.position += dp * gain[0][0] + dv * gain[0][1];
+        self.velocity += dp * gain[1][0] + dv * gain[1][1];
+        self.covariance = covariance;
+        verdict
+    }
+}
+
+impl Kinematic for VecKalman {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.velocity.angle()
+    }
+    fn angular_velocity(&self) -> f64 {
+        0.0
+    }
+}
+
+fn propagate<T: Scalar>(p: Matrix<T>, dt: T, q: T) -> Matrix<T> {
+    let [[p00, p01], [_, p11]] = p;
+    let (two, three) = (T::from_f64(2.0), T::from_f64(3.0));
+    let p00 = p00 + two * dt * p01 + dt * dt * p11 + q * dt * dt * dt / three;
+    let p01 = p01 + dt * p11 + q * dt * dt / two;
+    let p11 = p11 + q * dt;
+    [[p00, p01], [p01, p11]]
+}
+
+fn residual_covariance<T: Scalar>(p: Matrix<T>, [r0, r1]: [T; 2]) -> Matrix<T> {
+    [[p[0][0] + r0, p[0][1]], [p[1][0], p[1][1] + r1]]
+}
+
+fn correct<T: Scalar>(p: Matrix<T>, noise: [T; 2]) -> Option<(Matrix<T>, Matrix<T>)> {
+    let [[s00, s01], [_, s11]] = residual_covariance(p, noise);
+    let determinant = s00 * s11 - s01 * s01;
+    if determinant <= T::ZERO || !determinant.is_finite() {
+        return None;
+    }
+    let inverse = [
+        [s11 / determinant, -s01 / determinant],
+        [-s01 / determinant, s00 / determinant],
+    ];
+    let gain = [
+        [
+            p[0][0] * inverse[0][0] + p[0][1] * inverse[1][0],
+            p[0][0] * inverse[0][1] + p[0][1] * inverse[1][1],
+        ],
+        [
+            p[1][0] * inverse[0][0] + p[1][1] * inverse[1][0],
+            p[1][0] * inverse[0][1] + p[1][1] * inverse[1][1],
+        ],
+    ];
+    let p00 = (T::ONE - gain[0][0]) * p[0][0] - gain[0][1] * p[1][0];
+    let p01 = (T::ONE - gain[0][0]) * p[0][1] - gain[0][1] * p[1][1];
+    let p11 = -gain[1][0] * p[0][1] + (T::ONE - gain[1][1]) * p[1][1];
+    Some((gain, [[p00, p01], [p01, p11]]))
+}
+
+fn nis<T: Scalar>([[s00, s01], [_, s11]]: Matrix<T>, [y0, y1]: [T; 2]) -> T {
+    let determinant = s00 * s11 - s01 * s01;
+    if determinant <= T::ZERO {
+        return T::INFINITY;
+    }
+    let two = T::from_f64(2.0);
+    (y0 * y0 * s11 - two * y0 * y1 * s01 + y1 * y1 * s00) / determinant
+}
+
+pub trait Filter {
+    fn reset(&mut self, position: Vec2, velocity: Vec2);
+    fn predict(&mut self, dt: f64);
+    fn update(&mut self, position: Vec2, velocity: Vec2) -> Verdict;
+    fn estimate(&self) -> (Vec2, Vec2);
+}
+
+impl Filter for VecKalman {
+    fn reset(&mut self, position: Vec2, velocity: Vec2) {
+        VecKalman::reset(self, position, velocity)
+    }
+    fn predict(&mut self, dt: f64) {
+        VecKalman::predict(self, dt)
+    }
+    fn update(&mut self, position: Vec2, velocity: Vec2) -> Verdict {
+        VecKalman::update(self, position, velocity)
+    }
+    fn estimate(&self) -> (Vec2, Vec2) {
+        (self.position, self.velocity)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Entry<K, F> {
+    pub key: K,
+    pub filter: F,
+    pub updated: f64,
+}
+
+pub struct FilterBank<K, F = VecKalman, const N: usize = 64> {
+    pub template: F,
+    entries: FixedVec<Entry<K, F>, N>,
+}
+
+impl<K: Copy + Default + PartialEq, F: Filter + Copy + Default, const N: usize> Default
+    for FilterBank<K, F, N>
+{
+    fn default() -> Self {
+        FilterBank::new(F::default())
+    }
+}
+
+impl<K: Copy + Default + PartialEq, F: Filter + Copy, const N: usize> FilterBank<K, F, N> {
+    pub fn new(template: F) -> FilterBank<K, F, N> {
+        FilterBank {
+            template,
+            entries: FixedVec::new(Entry {
+                key: K::default(),
+                filter: template,
+                updated: f64::NEG_INFINITY,
+            }),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[Entry<K, F>] {
+        &self.entries
+    }
+
+    pub fn get(&self, key: K) -> Option<&F> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key == key)
+            .map(|entry| &entry.filter)
+    }
+
+    pub fn update(&mut self, key: K, position: Vec2, velocity: Vec2, now: f64) -> Verdict {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.key == key) {
+            entry.filter.predict(now - entry.updated);
+            entry.updated = now;
+            return entry.filter.update(position, velocity);
+        }
+        if self.entries.is_full() {
+            let stalest = self
+                .entries
+                .iter()
+                .enumerate()
+                .min_by(|a, b| a.1.updated.total_cmp(&b.1.updated))
+                .map(|(i, _)| i);
+            if let Some(i) = stalest {
+                self.entries.swap_remove(i);
+            }
+        }
+        let mut filter = self.template;
+        filter.reset(position, velocity);
+        let _ = self.entries.push(Entry {
+            key,
+            filter,
+            updated: now,
+        });
+        Verdict::Reset
+    }
+
+    pub fn predict(&mut self, now: f64) {
+        for entry in self.entries.iter_mut() {
+            entry.filter.predict(now - entry.updated);
+            entry.updated = now;
+        }
+    }
+
+    pub fn estimate(&self, key: K, now: f64) -> Option<(Vec2, Vec2)> {
+        let entry = self.entries.iter().find(|entry| entry.key == key)?;
+        let (position, velocity) = entry.filter.estimate();
+        Some((position + velocity * (now - entry.updated), velocity))
+    }
+
+    pub fn remove(&mut self, key: K) -> bool {
+        let before = self.entries.len();
+        self.entries.retain(|entry| entry.key != key);
+        self.entries.len() < before
+    }
+
+    pub fn sync(&mut self, mut live: impl FnMut(K) -> bool) {
+        self.entries.retain(|entry| live(entry.key));
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variance {
+    pub floor: f64,
+    pub per_range: f64,
+    pub per_range_squared: f64,
+}
+
+impl Variance {
+    pub fn constant(floor: f64) -> Variance {
+        Variance {
+            floor,
+            per_range: 0.0,
+            per_range_squared: 0.0,
+        }
+    }
+
+    pub fn at(&self, range: f64) -> f64 {
+        (self.floor + self.per_range * range + self.per_range_squared * range * range).max(0.0)
+    }
+
+    pub fn linear(floor: f64, slope: f64) -> Variance {
+        Variance {
+            floor: floor * floor,
+            per_range: 2.0 * floor * slope,
+            per_range_squared: slope * slope,
+        }
+    }
+
+    fn fit(bins: &[Bin], variance: fn(&Bin) -> f64) -> Option<Variance> {
+        let (mut sw, mut sx, mut sy, mut sxx, mut sxy) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        let mut pooled = (0.0, 0.0);
+        for bin in bins {
+            let v = variance(bin);
+            if v.is_nan() || v <= 0.0 {
+                continue;
+            }
+            let (x, y, w) = (bin.range, v.sqrt(), bin.count / v);
+            sw += w;
+            sx += w * x;
+            sy += w * y;
+            sxx += w * x * x;
+            sxy += w * x * y;
+            pooled = (pooled.0 + v * bin.count, pooled.1 + bin.count);
+        }
+        if pooled.1 < 2.0 {
+            return None;
+        }
+        let constant = Variance::constant(pooled.0 / pooled.1);
+        let determinant = sw * sxx - sx * sx;
+        if determinant <= 1e-12 * sw * sxx {
+            return Some(constant);
+        }
+        let slope = (sw * sxy - sx * sy) / determinant;
+        let floor = (sy - slope * sx) / sw;
+        if slope <= 0.0 {
+            return Some(constant);
+        }
+        Some(Variance::linear(floor.max(0.0), slope))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NoiseModel {
+    pub position: Variance,
+    pub velocity: Variance,
+}
+
+impl Default for NoiseModel {
+    fn default() -> Self {
+        NoiseModel {
+            position: Variance::constant(2_500.0),
+            velocity: Variance::constant(400.0),
+        }
+    }
+}
+
+impl NoiseModel {
+    pub fn apply(&self, filter: &mut VecKalman, range: f64) {
+        filter.position_noise = self.position.at(range);
+        filter.velocity_noise = self.velocity.at(range);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sample {
+    pub range: f64,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+struct Bin {
+    range: f64,
+    count: f64,
+    position: f64,
+    velocity: f64,
+}
+
+pub struct Calibration {
+    pub bin_width: f64,
+    samples: Vec<Sample>,
+}
+
+impl Default for Calibration {
+    fn default() -> Self {
+        Calibration {
+            bin_width: 1_000.0,
+            samples: Vec::new(),
+        }
+    }
+}
+
+impl Calibration {
+    pub fn new() -> Calibration {
+        Calibration::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn record(&mut self, origin: Vec2, position: Vec2, velocity: Vec2) {
+        let finite = [position.x, position.y, velocity.x, velocity.y];
+        if finite.iter().all(|value| value.is_finite()) {
+            self.samples.push(Sample {
+                range: origin.distance(position),
+                position,
+                velocity,
+            });
+        }
+    }
+
+    fn bins(&self, reference: Vec2, drift: Vec2) -> Vec<Bin> {
+        let mut bins: Vec<(i64, Bin)> = Vec::new();
+        for sample in &self.samples {
+            let key = (sample.range / self.bin_width.max(1e-9)) as i64;
+            let index = match bins.iter().position(|(k, _)| *k == key) {
+                Some(index) => index,
+                None => {
+                    bins.push((
+                        key,
+                        Bin {
+                            range: 0.0,
+                            count: 0.0,
+                            position: 0.0,
+                            velocity: 0.0,
+                        },
+                    ));
+                    bins.len() - 1
+                }
+            };
+            let bin = &mut bins[index].1;
+            bin.range += sample.range;
+            bin.count += 2.0;
+            bin.position += sq_length(sample.position - reference);
+            bin.velocity += sq_length(sample.velocity - drift);
+        }
+        bins.into_iter()
+            .map(|(_, bin)| Bin {
+                range: bin.range / (bin.count / 2.0),
+                position: bin.position / bin.count,
+                velocity: bin.velocity / bin.count,
+                ..bin
+            })
+            .collect()
+    }
+
+    pub fn model(&self) -> Option<NoiseModel> {
+        let n = self.samples.len() as f64;
+        if n < 3.0 {
+            return None;
+        }
+        let mean = |f: fn(&Sample) -> Vec2| {
+            self.samples
+                .iter()
+                .fold(vec2(0.0, 0.0), |sum, sample| sum + f(sample))
+                / n
+        };
+        let bins = self.bins(
+            mean(|sample| sample.position),
+            mean(|sample| sample.velocity),
+        );
+        Some(NoiseModel {
+            position: Variance::fit(&bins, |bin| bin.position)?,
+            velocity: Variance::fit(&bins, |bin| bin.velocity)?,
+        })
+    }
+}
@@ -0,0 +1,268 @@
+use crate::movement::Kinematic;
+use crate::perception::Sector;
+use crate::radio::{Header, Packet, RoleAssignment, Status};
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Role {
+    Leader,
+    Wingman,
+    Striker,
+    Screen,
+    Scout,
+    Rearguard,
+}
+
+impl Role {
+    pub fn code(self) -> u8 {
+        match self {
+            Role::Leader => 0,
+            Role::Wingman => 1,
+            Role::Striker => 2,
+            Role::Screen => 3,
+            Role::Scout => 4,
+            Role::Rearguard => 5,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Role> {
+        Some(match code {
+            0 => Role::Leader,
+            1 => Role::Wingman,
+            2 => Role::Striker,
+            3 => Role::Screen,
+            4 => Role::Scout,
+            5 => Role::Rearguard,
+            _ => return None,
+        })
+    }
+}
+
+pub struct Election {
+    pub me: u16,
+    pub timeout: f64,
+    heard: Vec<(u16, f64)>,
+    roles: Vec<(u16, Role)>,
+    cursor: usize,
+}
+
+impl Election {
+    pub fn new(me: u16) -> Election {
+        Election {
+            me,
+            timeout: 2.0,
+            heard: Vec::new(),
+            roles: Vec::new(),
+            cursor: 0,
+        }
+    }
+
+    pub fn observe(&mut self, inbox: &[(Header, Packet)], now: f64) {
+        for (header, packet) in inbox {
+            match self
+                .heard
+                .iter_mut()
+                .find(|(ship, _)| *ship == header.sender)
+            {
+                Some(entry) => entry.1 = now,
+                None => self.heard.push((header.sender, now)),
+            }
+            if let Packet::Role(assignment) = packet {
+                if header.sender == self.leader() {
+                    self.set_role(assignment.ship, assignment.role);
+                }
+            }
+        }
+        let timeout = self.timeout;
+        self.heard.retain(|(_, heard)| now - heard <= timeout);
+        let alive = self.members();
+        self.roles.retain(|(ship, _)| alive.contains(ship));
+    }
+
+    fn set_role(&mut self, ship: u16, role: Role) {
+        match self.roles.iter_mut().find(|(s, _)| *s == ship) {
+            Some(entry) => entry.1 = role,
+            None => self.roles.push((ship, role)),
+        }
+    }
+
+    pub fn members(&self) -> Vec<u16> {
+        let mut members: Vec<u16> = self.heard.iter().map(|(ship, _)| *ship).collect();
+        members.push(self.me);
+        members.sort_unstable();
+        members.dedup();
+        members
+    }
+
+    pub fn leader(&self) -> u16 {
+        self.heard
+            .iter()
+            .map(|(ship, _)| *ship)
+            .fold(self.me, u16::min)
+    }
+
+    pub fn is_leader(&self) -> bool {
+        self.leader() == self.me
+    }
+
+    pub fn role_of(&self, ship: u16) -> Role {
+        if ship == self.leader() {
+            return Role::Leader;
+        }
+        self.roles
+            .iter()
+            .find(|(s, _)| *s == ship)
+            .map_or(Role::Wingman, |(_, role)| *role)
+    }
+
+    pub fn role(&self) -> Role {
+        self.role_of(self.me)
+    }
+
+    pub fn assign(&mut self, plan: impl Fn(usize, u16) -> Role) {
+        if !self.is_leader() {
+            return;
+        }
+        for (rank, ship) in self.members().into_iter().enumerate() {
+            self.set_role(ship, plan(rank, ship));
+        }
+    }
+
+    pub fn broadcast(&mut self) -> Option<Packet> {
+        if !self.is_leader() || self.roles.is_empty() {
+            return None;
+        }
+        self.cursor = (self.cursor + 1) % self.roles.len();
+        let (ship, role) = self.roles[self.cursor];
+        Some(Packet::Role(RoleAssignment { ship, role }))
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Friendly {
+    pub ship: u16,
+    pub health: f64,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub heard: f64,
+}
+
+impl Friendly {
+    pub fn predict(&self, now: f64) -> Vec2 {
+        self.position + self.velocity * (now - self.heard)
+    }
+}
+
+impl Kinematic for Friendly {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.velocity.angle()
+    }
+    fn angular_velocity(&self) -> f64 {
+        0.0
+    }
+}
+
+pub struct Roster {
+    pub period: f64,
+    pub timeout: f64,
+    friends: Vec<Friendly>,
+    last_beat: Option<f64>,
+}
+
+impl Default for Roster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Roster {
+    pub fn new() -> Roster {
+        Roster {
+            period: 0.5,
+            timeout: 2.0,
+            friends: Vec::new(),
+            last_beat: None,
+        }
+    }
+
+    pub fn observe(&mut self, inbox: &[(Header, Packet)], now: f64) -> Vec<u16> {
+        for (header, packet) in inbox {
+            let index = match self.friends.iter().position(|f| f.ship == header.sender) {
+                Some(index) => index,
+                None => {
+                    let Packet::Status(status) = packet else {
+                        continue;
+                    };
+                    self.friends.push(Friendly {
+                        ship: header.sender,
+                        health: status.health as f64,
+                        position: status.position,
+                        velocity: status.velocity,
+                        heard: now,
+                    });
+                    continue;
+                }
+            };
+            let friend = &mut self.friends[index];
+            friend.heard = now;
+            if let Packet::Status(status) = packet {
+                friend.health = status.health as f64;
+                friend.position = status.position;
+                friend.velocity = status.velocity;
+            }
+        }
+        let timeout = self.timeout;
+        let lost = self
+            .friends
+            .iter()
+            .filter(|friend| now - friend.heard > timeout)
+            .map(|friend| friend.ship)
+            .collect();
+        self.friends.retain(|friend| now - friend.heard <= timeout);
+        lost
+    }
+
+    pub fn heartbeat(&mut self, actor: &impl Kinematic, health: f64, now: f64) -> Option<Packet> {
+        if self.last_beat.is_some_and(|last| now - last < self.period) {
+            return None;
+        }
+        self.last_beat = Some(now);
+        Some(Packet::Status(Status {
+            health: health.clamp(0.0, u16::MAX as f64) as u16,
+            position: actor.position(),
+            velocity: actor.velocity(),
+        }))
+    }
+
+    pub fn friends(&self) -> &[Friendly] {
+        &self.friends
+    }
+
+    pub fn get(&self, ship: u16) -> Option<&Friendly> {
+        self.friends.iter().find(|friend| friend.ship == ship)
+    }
+}
+
+pub fn partition(members: &[u16], me: u16, orientation: f64) -> Option<Sector> {
+    let rank = members.iter().position(|ship| *ship == me)?;
+    let width = std::f64::consts::TAU / members.len() as f64;
+    Some(Sector {
+        start: orientation + width * rank as f64,
+        width,
+    })
+}
+
+pub fn responsible(members: &[u16], origin: Vec2, point: Vec2, orientation: f64) -> Option<u16> {
+    let width = std::f64::consts::TAU / members.len().max(1) as f64;
+    let bearing = (point - origin).angle() - orientation;
+    let rank = (bearing.rem_euclid(std::f64::consts::TAU) / width) as usize;
+    members
+        .get(rank.min(members.len().saturating_sub(1)))
+        .copied()
+}
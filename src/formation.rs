@@ -0,0 +1,313 @@
+use crate::movement::*;
+use crate::planning::Arena;
+use crate::radio::{FormationCommand, Header, Packet, Status};
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct LeaderState {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub heading: f64,
+    pub received: f64,
+}
+
+impl LeaderState {
+    pub fn extrapolate(&self, now: f64) -> LeaderState {
+        LeaderState {
+            position: self.position + self.velocity * (now - self.received),
+            ..*self
+        }
+    }
+}
+
+impl Kinematic for LeaderState {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.heading
+    }
+    fn angular_velocity(&self) -> f64 {
+        0.0
+    }
+}
+
+struct Transition {
+    from: Vec<Vec2>,
+    start: f64,
+    duration: f64,
+}
+
+pub struct Formation {
+    pub offsets: Vec<Vec2>,
+    pub gain: f64,
+    pub timeout: f64,
+    pub transition_time: f64,
+    pub arena: Option<Arena>,
+    leader: Option<LeaderState>,
+    transition: Option<Transition>,
+    applied: Option<(u16, f64, f64)>,
+}
+
+impl Formation {
+    pub fn new(offsets: Vec<Vec2>) -> Formation {
+        Formation {
+            offsets,
+            gain: 1.0,
+            timeout: 1.0,
+            transition_time: 5.0,
+            arena: None,
+            leader: None,
+            transition: None,
+            applied: None,
+        }
+    }
+
+    pub fn leader(&self) -> Option<&LeaderState> {
+        self.leader.as_ref()
+    }
+
+    pub fn observe(&mut self, inbox: &[(Header, Packet)], leader: u16, now: f64) {
+        for (header, packet) in inbox {
+            if header.sender != leader {
+                continue;
+            }
+            let status = match packet {
+                Packet::Status(status) => status,
+                Packet::Formation(command) => {
+                    self.apply(command, now);
+                    continue;
+                }
+                _ => continue,
+            };
+            let heading = if status.velocity.length() > 1.0 {
+                status.velocity.angle()
+            } else {
+                self.leader.map_or(0.0, |leader| leader.heading)
+            };
+            self.leader = Some(LeaderState {
+                position: status.position,
+                velocity: status.velocity,
+                heading,
+                received: now,
+            });
+        }
+        if self
+            .leader
+            .is_some_and(|leader| now - leader.received > self.timeout)
+        {
+            self.leader = None;
+        }
+    }
+
+    pub fn publish(actor: &impl Kinematic, health: f64) -> Packet {
+        Packet::Status(Status {
+            health: health.clamp(0.0, u16::MAX as f64) as u16,
+            position: actor.position(),
+            velocity: actor.velocity(),
+        })
+    }
+
+    pub fn command(shape: Shape, spacing: f64, heading: f64, anchor: Vec2) -> Packet {
+        Packet::Formation(FormationCommand {
+            shape: shape.code(),
+            anchor,
+            heading,
+            spacing,
+        })
+    }
+
+    pub fn slot(&self, me: u16, members: &[u16], leader: u16, now: f64) -> Option<Vec2> {
+        let rank = members
+            .iter()
+            .filter(|ship| **ship != leader)
+            .position(|ship| *ship == me)?;
+        self.offset(rank, now)
+    }
+
+    pub fn offset(&self, rank: usize, now: f64) -> Option<Vec2> {
+        let to = *self.offsets.get(rank)?;
+        let Some(transition) = &self.transition else {
+            return Some(to);
+        };
+        let Some(&from) = transition.from.get(rank) else {
+            return Some(to);
+        };
+        let progress = ((now - transition.start) / transition.duration).clamp(0.0, 1.0);
+        let progress = progress * progress * (3.0 - 2.0 * progress);
+        let radius = from.length() + (to.length() - from.length()) * progress;
+        let angle = from.angle() + angle_diff(from.angle(), to.angle()) * progress;
+        Some(vec2(radius, 0.0).rotate(angle))
+    }
+
+    fn transition_to(&mut self, target: Vec<Vec2>, now: f64) {
+        let from: Vec<Vec2> = (0..self.offsets.len())
+            .map(|rank| self.offset(rank, now).unwrap_or(self.offsets[rank]))
+            .collect();
+        self.offsets = match_slots(&from, target);
+        self.transition = Some(Transition {
+            from,
+            start: now,
+            duration: self.transition_time,
+        });
+    }
+
+    pub fn rotate(&mut self, angle: f64, now: f64) {
+        let target = self
+            .offsets
+            .iter()
+            .map(|offset| offset.rotate(angle))
+            .collect();
+        self.transition_to(target, now);
+    }
+
+    pub fn scale(&mut self, factor: f64, now: f64) {
+        let target = self.offsets.iter().map(|offset| *offset * factor).collect();
+        self.transition_to(target, now);
+    }
+
+    pub fn reform(&mut self, shape: Shape, spacing: f64, heading: f64, now: f64) {
+        let turn = self
+            .leader
+            .map_or(0.0, |leader| angle_diff(leader.heading, heading));
+        let target = shape
+            .slots(self.offsets.len(), spacing)
+            .into_iter()
+            .map(|offset| offset.rotate(turn))
+            .collect();
+        self.transition_to(target, now);
+    }
+
+    pub fn apply(&mut self, command: &FormationCommand, now: f64) {
+        let key = (command.shape, command.heading, command.spacing);
+        if self.applied == Some(key) {
+            return;
+        }
+        self.applied = Some(key);
+        if let Some(shape) = Shape::from_code(command.shape) {
+            self.reform(shape, command.spacing, command.heading, now);
+        }
+    }
+
+    pub fn steer(
+        &self,
+        actor: &(impl Kinematic + Motor),
+        offset: Vec2,
+        now: f64,
+    ) -> Option<Output> {
+        let leader = self.leader?.extrapolate(now);
+        let offset = self.arena.map_or(offset, |arena| {
+            let slot = arena.clamp(leader.position + offset.rotate(leader.heading));
+            (slot - leader.position).rotate(-leader.heading)
+        });
+        Some(
+            OffsetPursuit {
+                leader,
+                offset,
+                gain: self.gain,
+            }
+            .execute(actor),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    LineAbreast,
+    Wedge,
+    EchelonLeft,
+    EchelonRight,
+    Circle,
+}
+
+impl Shape {
+    pub fn code(self) -> u16 {
+        match self {
+            Shape::LineAbreast => 0,
+            Shape::Wedge => 1,
+            Shape::EchelonLeft => 2,
+            Shape::EchelonRight => 3,
+            Shape::Circle => 4,
+        }
+    }
+
+    pub fn from_code(code: u16) -> Option<Shape> {
+        Some(match code {
+            0 => Shape::LineAbreast,
+            1 => Shape::Wedge,
+            2 => Shape::EchelonLeft,
+            3 => Shape::EchelonRight,
+            4 => Shape::Circle,
+            _ => return None,
+        })
+    }
+
+    pub fn slots(self, count: usize, spacing: f64) -> Vec<Vec2> {
+        (0..count)
+            .map(|i| {
+                let rank = (i / 2 + 1) as f64 * spacing;
+                let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+                let step = (i + 1) as f64 * spacing;
+                match self {
+                    Shape::LineAbreast => vec2(0.0, side * rank),
+                    Shape::Wedge => vec2(-rank, side * rank),
+                    Shape::EchelonLeft => vec2(-step, step),
+                    Shape::EchelonRight => vec2(-step, -step),
+                    Shape::Circle => {
+                        let radius = (spacing * count as f64 / std::f64::consts::TAU).max(spacing);
+                        let angle = std::f64::consts::TAU * i as f64 / count as f64;
+                        vec2(radius, 0.0).rotate(angle)
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+fn match_slots(from: &[Vec2], to: Vec<Vec2>) -> Vec<Vec2> {
+    let count = from.len().min(to.len());
+    if count == 0 {
+        return to;
+    }
+    let by_angle = |slots: &[Vec2]| {
+        let mut order: Vec<usize> = (0..count).collect();
+        order.sort_by(|a, b| slots[*a].angle().total_cmp(&slots[*b].angle()));
+        order
+    };
+    let from_order = by_angle(from);
+    let to_order = by_angle(&to);
+    let cost = |shift: usize| {
+        (0..count)
+            .map(|i| {
+                let a = from[from_order[i]];
+                let b = to[to_order[(i + shift) % count]];
+                (a - b).dot(a - b)
+            })
+            .sum::<f64>()
+    };
+    let shift = (0..count)
+        .min_by(|a, b| cost(*a).total_cmp(&cost(*b)))
+        .unwrap_or(0);
+    let mut matched = to.clone();
+    for i in 0..count {
+        matched[from_order[i]] = to[to_order[(i + shift) % count]];
+    }
+    matched
+}
+
+pub fn spacing(class: Class) -> f64 {
+    match class {
+        Class::Frigate => 300.0,
+        Class::Cruiser => 600.0,
+        _ => 100.0,
+    }
+}
+
+impl Formation {
+    pub fn shaped(shape: Shape, count: usize, class: Class) -> Formation {
+        Formation::new(shape.slots(count, spacing(class)))
+    }
+}
@@ -0,0 +1,218 @@
+use crate::perception::{triangulate, Strobe, Track, TrackId, Tracker};
+use crate::radio::{BearingReport, ContactReport, Header, Packet};
+use oort_api::prelude::*;
+
+pub struct Sharing {
+    pub min_hits: u32,
+    pub latency: f64,
+    cursor: usize,
+}
+
+impl Default for Sharing {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sharing {
+    pub fn new() -> Sharing {
+        Sharing {
+            min_hits: 3,
+            latency: TICK_LENGTH,
+            cursor: 0,
+        }
+    }
+
+    pub fn eligible(&self, track: &Track, now: f64) -> bool {
+        track.shared_by.is_none() && track.hits >= self.min_hits && now - track.updated <= 1.0
+    }
+
+    pub fn broadcast(&mut self, tracker: &Tracker, now: f64) -> Option<Packet> {
+        let tracks = tracker.tracks();
+        let count = tracks.len();
+        let offset = (0..count)
+            .find(|offset| self.eligible(&tracks[(self.cursor + offset) % count], now))?;
+        let track = tracks[(self.cursor + offset) % count].predict(now);
+        self.cursor = (self.cursor + offset + 1) % count;
+        Some(Packet::Contact(ContactReport {
+            class: track.class,
+            position: track.position,
+            velocity: track.velocity,
+        }))
+    }
+
+    pub fn receive(&self, tracker: &mut Tracker, inbox: &[(Header, Packet)], now: f64) -> usize {
+        inbox
+            .iter()
+            .filter_map(|(header, packet)| match packet {
+                Packet::Contact(report) => Some((header.sender, report)),
+                _ => None,
+            })
+            .filter_map(|(sender, report)| tracker.fuse(report, sender, self.latency, now))
+            .count()
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Fix {
+    pub track: Option<TrackId>,
+    pub class: Class,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub time: f64,
+}
+
+pub struct CrossFix {
+    pub window: f64,
+    pub memory: f64,
+    pub min_spread: f64,
+    own: Vec<(Class, Strobe)>,
+    fixes: Vec<Fix>,
+}
+
+impl Default for CrossFix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CrossFix {
+    pub fn new() -> CrossFix {
+        CrossFix {
+            window: 2.0 * TICK_LENGTH,
+            memory: 1.0,
+            min_spread: 0.05,
+            own: Vec::new(),
+            fixes: Vec::new(),
+        }
+    }
+
+    pub fn fixes(&self) -> &[Fix] {
+        &self.fixes
+    }
+
+    pub fn observe(&mut self, class: Class, origin: Vec2, bearing: f64, now: f64) -> Packet {
+        let memory = self.memory;
+        self.own.retain(|(_, strobe)| now - strobe.time <= memory);
+        self.own.push((
+            class,
+            Strobe {
+                origin,
+                bearing,
+                time: now,
+            },
+        ));
+        Packet::Bearing(BearingReport {
+            class,
+            origin,
+            bearing,
+            time: now,
+        })
+    }
+
+    pub fn fix(&self, report: &BearingReport, tracker: &Tracker) -> Option<Fix> {
+        let remote = Strobe {
+            origin: report.origin,
+            bearing: report.bearing,
+            time: report.time,
+        };
+        let tracks: Vec<Track> = tracker
+            .tracks()
+            .iter()
+            .filter(|track| track.class == report.class)
+            .map(|track| track.predict(report.time))
+            .collect();
+        let references: Vec<Vec2> = tracks
+            .iter()
+            .map(|track| track.position)
+            .chain(
+                self.fixes
+                    .iter()
+                    .filter(|fix| fix.class == report.class)
+                    .map(|fix| fix.position + fix.velocity * (report.time - fix.time)),
+            )
+            .collect();
+        let score = |(own, position): &(Strobe, Vec2)| {
+            if references.is_empty() {
+                (own.time - report.time).abs()
+            } else {
+                references
+                    .iter()
+                    .map(|reference| reference.distance(*position))
+                    .fold(f64::INFINITY, f64::min)
+            }
+        };
+        let (own, position) = self
+            .own
+            .iter()
+            .filter(|(class, strobe)| {
+                *class == report.class && (strobe.time - report.time).abs() <= self.window
+            })
+            .filter_map(|(_, own)| Some((*own, triangulate(&[*own, remote], self.min_spread)?)))
+            .min_by(|a, b| score(a).total_cmp(&score(b)))?;
+        let time = own.time.max(report.time);
+        let track = tracks
+            .iter()
+            .map(|track| (track.id, track.position.distance(position)))
+            .filter(|(_, distance)| *distance < tracker.gate)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(id, _)| id);
+        let velocity = self
+            .fixes
+            .iter()
+            .find(|fix| track.is_some() && fix.track == track)
+            .filter(|fix| time > fix.time && time - fix.time <= self.memory)
+            .map(|fix| (position - fix.position) / (time - fix.time))
+            .unwrap_or(vec2(0.0, 0.0));
+        Some(Fix {
+            track,
+            class: report.class,
+            position,
+            velocity,
+            time,
+        })
+    }
+
+    fn remember(&mut self, fix: Fix) {
+        let memory = self.memory;
+        self.fixes
+            .retain(|old| old.track != fix.track && fix.time - old.time <= memory);
+        self.fixes.push(fix);
+    }
+
+    pub fn receive(
+        &mut self,
+        tracker: &mut Tracker,
+        inbox: &[(Header, Packet)],
+        me: u16,
+        now: f64,
+    ) -> usize {
+        let mut fixed = 0;
+        for (header, packet) in inbox {
+            let Packet::Bearing(report) = packet else {
+                continue;
+            };
+            if header.sender == me || now - report.time > self.memory {
+                continue;
+            }
+            let Some(fix) = self.fix(report, tracker) else {
+                continue;
+            };
+            let contact = ScanResult {
+                class: fix.class,
+                position: fix.position + fix.velocity * (now - fix.time),
+                velocity: fix.velocity,
+                rssi: 0.0,
+                snr: 0.0,
+            };
+            if let Some(id) = tracker.update(&contact, now) {
+                self.remember(Fix {
+                    track: Some(id),
+                    ..fix
+                });
+                fixed += 1;
+            }
+        }
+        fixed
+    }
+}
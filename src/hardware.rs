@@ -0,0 +1,37 @@
+use crate::perception::Beam;
+use crate::Oort;
+use oort_api::prelude::*;
+
+pub trait Hardware {
+    fn health(&self) -> f64;
+    fn scan(&mut self) -> Option<ScanResult>;
+    fn aim_radar(&mut self, beam: &Beam);
+    fn accelerate(&mut self, acceleration: Vec2);
+    fn torque(&mut self, acceleration: f64);
+    fn turn(&mut self, rate: f64);
+    fn fire(&mut self, gun: usize);
+}
+
+impl Hardware for Oort {
+    fn health(&self) -> f64 {
+        health()
+    }
+    fn scan(&mut self) -> Option<ScanResult> {
+        scan()
+    }
+    fn aim_radar(&mut self, beam: &Beam) {
+        beam.apply();
+    }
+    fn accelerate(&mut self, acceleration: Vec2) {
+        accelerate(acceleration);
+    }
+    fn torque(&mut self, acceleration: f64) {
+        torque(acceleration);
+    }
+    fn turn(&mut self, rate: f64) {
+        turn(rate);
+    }
+    fn fire(&mut self, gun: usize) {
+        fire(gun);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::draw::{DebugDraw, OortDraw};
+use crate::math::degrees;
+use crate::movement::{Kinematic, Output};
+use crate::perception::Track;
+use crate::profiler::{Clock, Profiler, Subsystem};
+use oort_api::prelude::*;
+
+pub struct Hud<D: DebugDraw = OortDraw> {
+    pub draw: D,
+    pub offset: Vec2,
+    pub line_height: f64,
+    pub rows: usize,
+    pub header: u32,
+    pub text: u32,
+}
+
+impl Default for Hud {
+    fn default() -> Self {
+        Hud::new(OortDraw)
+    }
+}
+
+impl<D: DebugDraw> Hud<D> {
+    pub fn new(draw: D) -> Hud<D> {
+        Hud {
+            draw,
+            offset: vec2(200.0, 200.0),
+            line_height: 40.0,
+            rows: 8,
+            header: 0xffffff,
+            text: 0xaaaaaa,
+        }
+    }
+
+    pub fn with_draw<E: DebugDraw>(&self, draw: E) -> Hud<E> {
+        Hud {
+            draw,
+            offset: self.offset,
+            line_height: self.line_height,
+            rows: self.rows,
+            header: self.header,
+            text: self.text,
+        }
+    }
+
+    pub fn track_row(own: &impl Kinematic, track: &Track, now: f64) -> String {
+        format!(
+            "{:>4} {:<9} {:>7.0} {:>5.1} {:>6.0}",
+            track.id,
+            format!("{:?}", track.class),
+            own.position().distance(track.position),
+            track.staleness(now),
+            track.uncertainty(now),
+        )
+    }
+
+    pub fn tracks(&self, anchor: Vec2, own: &impl Kinematic, tracks: &[Track], now: f64) -> Vec2 {
+        let mut cursor = anchor;
+        self.line(
+            &mut cursor,
+            self.header,
+            "  ID CLASS       RANGE   AGE  SIGMA",
+        );
+        let mut sorted: Vec<&Track> = tracks.iter().collect();
+        sorted.sort_by(|a, b| {
+            own.position()
+                .distance(a.position)
+                .total_cmp(&own.position().distance(b.position))
+        });
+        for track in sorted.into_iter().take(self.rows) {
+            self.line(&mut cursor, self.text, &Self::track_row(own, track, now));
+        }
+        if tracks.len() > self.rows {
+            self.line(
+                &mut cursor,
+                self.text,
+                &format!("  +{} more", tracks.len() - self.rows),
+            );
+        }
+        cursor
+    }
+
+    pub fn steering(
+        &self,
+        anchor: Vec2,
+        strategy: &str,
+        weights: &[(&str, f64)],
+        output: &Output,
+    ) -> Vec2 {
+        let mut cursor = anchor;
+        self.line(&mut cursor, self.header, strategy);
+        let total: f64 = weights.iter().map(|(_, weight)| weight.abs()).sum();
+        for (name, weight) in weights {
+            let share = if total > 0.0 {
+                weight.abs() / total
+            } else {
+                0.0
+            };
+            self.line(
+                &mut cursor,
+                self.text,
+                &format!("  {:<12} {:>5.2} {:>3.0}%", name, weight, share * 100.0),
+            );
+        }
+        self.line(
+            &mut cursor,
+            self.text,
+            &format!(
+                "  accel {:>6.1} @ {:>4.0}  torque {:>6.2}",
+                output.linear.length(),
+                degrees(output.linear.angle()),
+                output.angular
+            ),
+        );
+        cursor
+    }
+
+    pub fn draw(
+        &self,
+        own: &impl Kinematic,
+        tracks: &[Track],
+        now: f64,
+        strategy: &str,
+        weights: &[(&str, f64)],
+        output: &Output,
+    ) {
+        let cursor = self.tracks(own.position() + self.offset, own, tracks, now);
+        self.steering(
+            cursor - vec2(0.0, self.line_height),
+            strategy,
+            weights,
+            output,
+        );
+    }
+
+    pub fn profile<C: Clock>(&self, anchor: Vec2, profiler: &Profiler<C>) -> Vec2 {
+        let mut cursor = anchor;
+        let total = profiler.total().max(1) as f64;
+        self.line(
+            &mut cursor,
+            self.header,
+            &format!("PROFILE total {}", profiler.total()),
+        );
+        for subsystem in Subsystem::ALL {
+            let sample = profiler.sample(subsystem);
+            self.line(
+                &mut cursor,
+                self.text,
+                &format!(
+                    "  {:<10} {:>8} {:>3.0}% max {}",
+                    subsystem.name(),
+                    sample.last,
+                    sample.last as f64 / total * 100.0,
+                    sample.max
+                ),
+            );
+        }
+        cursor
+    }
+
+    fn line(&self, cursor: &mut Vec2, color: u32, text: &str) {
+        self.draw.text(*cursor, color, text);
+        *cursor -= vec2(0.0, self.line_height);
+    }
+}
@@ -1,56 +1,1032 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "oort")]
+pub mod abilities;
+#[cfg(feature = "oort")]
+pub mod assignment;
+#[cfg(feature = "oort")]
+pub mod avoidance;
+#[cfg(feature = "oort")]
+pub mod commander;
+pub mod contact;
+pub mod context;
+pub mod control;
+#[cfg(feature = "oort")]
+pub mod controllers;
+#[cfg(feature = "oort")]
+pub mod danger;
+#[cfg(feature = "oort")]
+pub mod defense;
+#[cfg(feature = "oort")]
+pub mod draw;
+#[cfg(feature = "oort")]
+pub mod escort;
+#[cfg(feature = "oort")]
+pub mod evasion;
+#[cfg(feature = "oort")]
+pub mod events;
+pub mod export;
+pub mod filter;
+#[cfg(feature = "oort")]
+pub mod fleet;
+#[cfg(feature = "oort")]
+pub mod formation;
+#[cfg(feature = "oort")]
+pub mod fusion;
+#[cfg(feature = "oort")]
+pub mod hardware;
+#[cfg(feature = "oort")]
+pub mod hud;
+pub mod math;
+#[cfg(feature = "oort")]
+pub mod missile;
+#[cfg(feature = "oort")]
+pub mod mission;
+pub mod opponent;
+#[cfg(feature = "oort")]
+pub mod overlay;
+pub mod perception;
+pub mod planning;
+pub mod prelude;
+#[cfg(feature = "oort")]
+pub mod profile;
+#[cfg(feature = "std")]
+pub mod profiler;
+#[cfg(feature = "oort")]
+pub mod radio;
+pub mod random;
+#[cfg(feature = "oort")]
+pub mod registry;
+pub mod rollout;
+#[cfg(feature = "scenarios")]
+pub mod scenario;
+#[cfg(feature = "serde")]
+pub mod serial;
+#[cfg(feature = "oort")]
+pub mod ships;
+pub mod sim;
+pub mod storage;
+#[cfg(feature = "oort")]
+pub mod tactics;
+pub mod targeting;
+#[cfg(feature = "oort")]
+pub mod telemetry;
+pub mod time;
+pub mod trace;
+#[cfg(feature = "scenarios")]
+pub mod tuning;
+#[cfg(feature = "oort")]
+pub mod tutorials;
+#[cfg(feature = "oort")]
+pub mod warning;
+#[cfg(feature = "oort")]
+pub mod weapons;
+
 pub mod movement {
-    use oort_api::prelude::*;
+    use crate::control::{Attitude, Lqr};
+    use crate::math::*;
+    use crate::time::Seconds;
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
     pub trait Kinematic {
         fn position(&self) -> Vec2;
         fn velocity(&self) -> Vec2;
         fn heading(&self) -> f64;
         fn angular_velocity(&self) -> f64;
+
+        fn position_at(&self, time: Seconds) -> Vec2 {
+            self.position() + self.velocity() * time
+        }
     }
     pub trait Motor {
         fn max_linear_acceleration(&self) -> f64;
         fn max_angular_acceleration(&self) -> f64;
+        fn max_angular_velocity(&self) -> f64;
+
+        fn braking_time(&self, speed: f64) -> Seconds {
+            Seconds(speed.abs() / self.max_linear_acceleration())
+        }
+
+        fn limits(&self) -> SteeringLimits {
+            SteeringLimits::default()
+        }
+    }
+
+    #[derive(Clone, Copy, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub struct SteeringLimits {
+        pub stop_radius: f64,
+        pub stop_speed: f64,
+        pub stop_angle: f64,
+        pub stop_rate: f64,
+        pub slow_radius: f64,
+        pub slow_angle: f64,
+        pub max_angular_velocity: f64,
     }
+
+    impl Default for SteeringLimits {
+        fn default() -> Self {
+            SteeringLimits {
+                stop_radius: 1.0,
+                stop_speed: 1.0,
+                stop_angle: 0.001,
+                stop_rate: 0.001,
+                slow_radius: 0.0,
+                slow_angle: 0.0,
+                max_angular_velocity: core::f64::consts::TAU,
+            }
+        }
+    }
+
+    pub struct Derated<'a, A> {
+        pub actor: &'a A,
+        pub factor: f64,
+    }
+
+    impl<A: Kinematic> Kinematic for Derated<'_, A> {
+        fn position(&self) -> Vec2 {
+            self.actor.position()
+        }
+        fn velocity(&self) -> Vec2 {
+            self.actor.velocity()
+        }
+        fn heading(&self) -> f64 {
+            self.actor.heading()
+        }
+        fn angular_velocity(&self) -> f64 {
+            self.actor.angular_velocity()
+        }
+    }
+
+    impl<A: Motor> Motor for Derated<'_, A> {
+        fn max_linear_acceleration(&self) -> f64 {
+            self.actor.max_linear_acceleration() * self.factor
+        }
+        fn max_angular_acceleration(&self) -> f64 {
+            self.actor.max_angular_acceleration() * self.factor
+        }
+        fn max_angular_velocity(&self) -> f64 {
+            self.actor.max_angular_velocity()
+        }
+        fn limits(&self) -> SteeringLimits {
+            self.actor.limits()
+        }
+    }
+
     pub struct Output {
         pub linear: Vec2,
         pub angular: f64,
     }
 
+    impl Output {
+        pub fn zero() -> Output {
+            Output {
+                linear: vec2(0.0, 0.0),
+                angular: 0.0,
+            }
+        }
+
+        pub fn is_finite(&self) -> bool {
+            self.linear.x.is_finite() && self.linear.y.is_finite() && self.angular.is_finite()
+        }
+
+        pub fn sanitized(self) -> Output {
+            let finite = self.linear.x.is_finite() && self.linear.y.is_finite();
+            Output {
+                linear: if finite { self.linear } else { vec2(0.0, 0.0) },
+                angular: if self.angular.is_finite() {
+                    self.angular
+                } else {
+                    0.0
+                },
+            }
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Reason {
+        Degenerate,
+        NonFinite,
+        Unreachable,
+    }
+
+    pub enum Steering {
+        Done,
+        Output(Output),
+        Blocked(Reason),
+    }
+
+    impl Steering {
+        pub fn checked(output: Output) -> Steering {
+            if output.is_finite() {
+                Steering::Output(output)
+            } else {
+                Steering::Blocked(Reason::NonFinite)
+            }
+        }
+
+        pub fn is_done(&self) -> bool {
+            matches!(self, Steering::Done)
+        }
+
+        pub fn output(self) -> Output {
+            match self {
+                Steering::Output(output) => output,
+                Steering::Done | Steering::Blocked(_) => Output::zero(),
+            }
+        }
+    }
+
     pub trait Move {
         fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output;
+
+        fn steer(&self, actor: &(impl Kinematic + Motor)) -> Steering {
+            Steering::checked(self.execute(actor))
+        }
+
+        fn name(&self) -> &'static str {
+            let path = core::any::type_name::<Self>();
+            let path = path.split('<').next().unwrap_or(path);
+            path.rsplit("::").next().unwrap_or(path)
+        }
+    }
+
+    pub trait Actor: Kinematic + Motor {}
+
+    impl<T: Kinematic + Motor> Actor for T {}
+
+    pub struct ActorRef<'a>(pub &'a dyn Actor);
+
+    impl Kinematic for ActorRef<'_> {
+        fn position(&self) -> Vec2 {
+            self.0.position()
+        }
+        fn velocity(&self) -> Vec2 {
+            self.0.velocity()
+        }
+        fn heading(&self) -> f64 {
+            self.0.heading()
+        }
+        fn angular_velocity(&self) -> f64 {
+            self.0.angular_velocity()
+        }
+    }
+
+    impl Motor for ActorRef<'_> {
+        fn max_linear_acceleration(&self) -> f64 {
+            self.0.max_linear_acceleration()
+        }
+        fn max_angular_acceleration(&self) -> f64 {
+            self.0.max_angular_acceleration()
+        }
+        fn max_angular_velocity(&self) -> f64 {
+            self.0.max_angular_velocity()
+        }
+        fn limits(&self) -> SteeringLimits {
+            self.0.limits()
+        }
+    }
+
+    pub trait DynMove {
+        fn execute_dyn(&self, actor: &dyn Actor) -> Output;
+        fn steer_dyn(&self, actor: &dyn Actor) -> Steering;
+        fn name_dyn(&self) -> &'static str;
+    }
+
+    impl<M: Move> DynMove for M {
+        fn execute_dyn(&self, actor: &dyn Actor) -> Output {
+            self.execute(&ActorRef(actor))
+        }
+        fn steer_dyn(&self, actor: &dyn Actor) -> Steering {
+            self.steer(&ActorRef(actor))
+        }
+        fn name_dyn(&self) -> &'static str {
+            self.name()
+        }
+    }
+
+    impl Move for Box<dyn DynMove + '_> {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            self.as_ref().execute_dyn(actor)
+        }
+        fn steer(&self, actor: &(impl Kinematic + Motor)) -> Steering {
+            self.as_ref().steer_dyn(actor)
+        }
+        fn name(&self) -> &'static str {
+            self.as_ref().name_dyn()
+        }
+    }
+
+    impl Move for &dyn DynMove {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            (*self).execute_dyn(actor)
+        }
+        fn steer(&self, actor: &(impl Kinematic + Motor)) -> Steering {
+            (*self).steer_dyn(actor)
+        }
+        fn name(&self) -> &'static str {
+            (*self).name_dyn()
+        }
+    }
+
+    pub struct MovementBlend<M: Move> {
+        pub moves: Vec<(M, f64)>,
+    }
+
+    impl<M: Move> Default for MovementBlend<M> {
+        fn default() -> Self {
+            MovementBlend::new()
+        }
+    }
+
+    impl<M: Move> MovementBlend<M> {
+        pub fn new() -> MovementBlend<M> {
+            MovementBlend { moves: Vec::new() }
+        }
+
+        pub fn with(mut self, strategy: M, weight: f64) -> MovementBlend<M> {
+            self.moves.push((strategy, weight));
+            self
+        }
+    }
+
+    impl<'a> MovementBlend<Box<dyn DynMove + 'a>> {
+        pub fn boxed(mut self, strategy: impl Move + 'a, weight: f64) -> Self {
+            self.moves.push((Box::new(strategy), weight));
+            self
+        }
+    }
+
+    fn weighted(strategy: &impl Move, weight: f64, actor: &(impl Kinematic + Motor)) -> Output {
+        let output = strategy.execute(actor);
+        if output.is_finite() && weight.is_finite() {
+            Output {
+                linear: output.linear * weight,
+                angular: output.angular * weight,
+            }
+        } else {
+            Output::zero()
+        }
+    }
+
+    fn combine(outputs: impl Iterator<Item = Output>, actor: &(impl Kinematic + Motor)) -> Output {
+        let mut linear = vec2(0.0, 0.0);
+        let mut angular = 0.0;
+        for output in outputs {
+            linear += output.linear;
+            angular += output.angular;
+        }
+        let max_linear = actor.max_linear_acceleration();
+        let max_angular = actor.max_angular_acceleration();
+        let length = linear.length();
+        Output {
+            linear: if length > max_linear {
+                linear * (max_linear / length)
+            } else {
+                linear
+            },
+            angular: angular.clamp(-max_angular, max_angular),
+        }
+    }
+
+    impl<M: Move> MovementBlend<M> {
+        pub fn components(&self, actor: &(impl Kinematic + Motor)) -> Vec<(&'static str, Output)> {
+            self.moves
+                .iter()
+                .map(|(strategy, weight)| (strategy.name(), weighted(strategy, *weight, actor)))
+                .collect()
+        }
+    }
+
+    impl<M: Move> Move for MovementBlend<M> {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            combine(
+                self.moves
+                    .iter()
+                    .map(|(strategy, weight)| weighted(strategy, *weight, actor)),
+                actor,
+            )
+        }
+    }
+
+    pub struct ArrayBlend<M: Move, const N: usize> {
+        pub moves: [(M, f64); N],
+    }
+
+    impl<M: Move, const N: usize> ArrayBlend<M, N> {
+        pub fn new(moves: [(M, f64); N]) -> ArrayBlend<M, N> {
+            ArrayBlend { moves }
+        }
+
+        pub fn components(&self, actor: &(impl Kinematic + Motor)) -> [(&'static str, Output); N] {
+            core::array::from_fn(|i| {
+                let (strategy, weight) = &self.moves[i];
+                (strategy.name(), weighted(strategy, *weight, actor))
+            })
+        }
+    }
+
+    impl<M: Move, const N: usize> Move for ArrayBlend<M, N> {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            combine(
+                self.moves
+                    .iter()
+                    .map(|(strategy, weight)| weighted(strategy, *weight, actor)),
+                actor,
+            )
+        }
+    }
+
+    pub trait Pipeline {
+        type Weights: Copy;
+        const UNIT: Self::Weights;
+
+        fn blend(&self, weights: &Self::Weights, actor: &(impl Kinematic + Motor)) -> Output;
+        fn first(&self, threshold: f64, actor: &(impl Kinematic + Motor)) -> Steering;
+    }
+
+    fn significant(output: &Output, threshold: f64) -> bool {
+        output.linear.length() > threshold || output.angular.abs() > threshold
+    }
+
+    macro_rules! pipeline {
+        ($n:literal; $($m:ident $i:tt),+) => {
+            impl<$($m: Move),+> Pipeline for ($($m,)+) {
+                type Weights = [f64; $n];
+                const UNIT: [f64; $n] = [1.0; $n];
+
+                fn blend(&self, weights: &[f64; $n], actor: &(impl Kinematic + Motor)) -> Output {
+                    combine([$(weighted(&self.$i, weights[$i], actor)),+].into_iter(), actor)
+                }
+
+                fn first(&self, threshold: f64, actor: &(impl Kinematic + Motor)) -> Steering {
+                    $(
+                        let steering = self.$i.steer(actor);
+                        if let Steering::Output(output) = &steering {
+                            if significant(output, threshold) {
+                                return steering;
+                            }
+                        }
+                    )+
+                    steering
+                }
+            }
+        };
+    }
+
+    pipeline!(1; A 0);
+    pipeline!(2; A 0, B 1);
+    pipeline!(3; A 0, B 1, C 2);
+    pipeline!(4; A 0, B 1, C 2, D 3);
+    pipeline!(5; A 0, B 1, C 2, D 3, E 4);
+    pipeline!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+
+    pub struct Blend<T: Pipeline> {
+        pub moves: T,
+        pub weights: T::Weights,
+    }
+
+    impl<T: Pipeline> Blend<T> {
+        pub fn new(moves: T) -> Blend<T> {
+            Blend {
+                moves,
+                weights: T::UNIT,
+            }
+        }
+
+        pub fn weighted(moves: T, weights: T::Weights) -> Blend<T> {
+            Blend { moves, weights }
+        }
+    }
+
+    impl<T: Pipeline> Move for Blend<T> {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            self.moves.blend(&self.weights, actor)
+        }
+    }
+
+    pub struct Priority<T: Pipeline> {
+        pub moves: T,
+        pub threshold: f64,
+    }
+
+    impl<T: Pipeline> Priority<T> {
+        pub fn new(moves: T) -> Priority<T> {
+            Priority {
+                moves,
+                threshold: 1e-3,
+            }
+        }
+    }
+
+    impl<T: Pipeline> Move for Priority<T> {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            self.steer(actor).output()
+        }
+        fn steer(&self, actor: &(impl Kinematic + Motor)) -> Steering {
+            self.moves.first(self.threshold, actor)
+        }
     }
 
     pub struct Seek {
         pub target: Vec2,
     }
 
+    impl Default for Seek {
+        fn default() -> Self {
+            Seek::new(vec2(0.0, 0.0))
+        }
+    }
+
+    impl Seek {
+        pub fn new(target: Vec2) -> Seek {
+            Seek { target }
+        }
+    }
+
     impl Move for Seek {
+        fn steer(&self, actor: &(impl Kinematic + Motor)) -> Steering {
+            let direction = self.target - actor.position();
+            if !direction.length().is_finite() {
+                return Steering::Blocked(Reason::NonFinite);
+            }
+            if direction.length() <= 0.0 {
+                return Steering::Blocked(Reason::Degenerate);
+            }
+            Steering::checked(self.execute(actor))
+        }
+
         fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
             let direction = self.target - actor.position();
+            let length = direction.length();
+            if length <= 0.0 || !length.is_finite() {
+                return Output::zero();
+            }
+            Output {
+                linear: direction * (actor.max_linear_acceleration() / length),
+                angular: 0.0,
+            }
+            .sanitized()
+        }
+    }
+
+    pub struct Arrive {
+        pub target: Vec2,
+        pub gain: f64,
+    }
+
+    impl Default for Arrive {
+        fn default() -> Self {
+            Arrive::new(vec2(0.0, 0.0))
+        }
+    }
+
+    impl Arrive {
+        pub fn new(target: Vec2) -> Arrive {
+            Arrive { target, gain: 1.0 }
+        }
+
+        pub fn gain(mut self, gain: f64) -> Arrive {
+            self.gain = gain;
+            self
+        }
+    }
+
+    impl Move for Arrive {
+        fn steer(&self, actor: &(impl Kinematic + Motor)) -> Steering {
+            let limits = actor.limits();
+            let arrived = actor.position().distance(self.target) <= limits.stop_radius
+                && actor.velocity().length() <= limits.stop_speed;
+            if arrived {
+                return Steering::Done;
+            }
+            Steering::checked(self.execute(actor))
+        }
+
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            let max_acceleration = actor.max_linear_acceleration();
+            let slow_radius = actor.limits().slow_radius;
+            let error = self.target - actor.position();
+            let distance = error.length();
+            let desired = if distance > 0.0 {
+                let mut speed =
+                    (self.gain * distance).min((2.0 * max_acceleration * distance).sqrt());
+                if distance < slow_radius {
+                    let entry = (2.0 * max_acceleration * slow_radius).sqrt();
+                    speed = speed.min(entry * distance / slow_radius);
+                }
+                error * (speed / distance)
+            } else {
+                error
+            };
+            let correction = (desired - actor.velocity()) / TICK_LENGTH;
+            let length = correction.length();
+            Output {
+                linear: if length > max_acceleration {
+                    correction * (max_acceleration / length)
+                } else {
+                    correction
+                },
+                angular: 0.0,
+            }
+            .sanitized()
+        }
+    }
+
+    pub struct Align {
+        pub target: f64,
+        pub feedforward: f64,
+        pub attitude: Attitude,
+    }
+
+    impl Default for Align {
+        fn default() -> Self {
+            Align::new(0.0)
+        }
+    }
+
+    impl Align {
+        pub fn new(target: f64) -> Align {
+            Align {
+                target,
+                feedforward: 0.0,
+                attitude: Attitude::default(),
+            }
+        }
+
+        pub fn feedforward(mut self, rate: f64) -> Align {
+            self.feedforward = rate;
+            self
+        }
+
+        pub fn attitude(mut self, attitude: Attitude) -> Align {
+            self.attitude = attitude;
+            self
+        }
+    }
+
+    impl Move for Align {
+        fn steer(&self, actor: &(impl Kinematic + Motor)) -> Steering {
+            let limits = actor.limits();
+            let aligned = angle_diff(actor.heading(), self.target).abs() <= limits.stop_angle
+                && (actor.angular_velocity() - self.feedforward).abs() <= limits.stop_rate;
+            if aligned {
+                return Steering::Done;
+            }
+            Steering::checked(self.execute(actor))
+        }
+
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            let max_acceleration = actor.max_angular_acceleration();
+            let max_rate = actor.max_angular_velocity();
+            let slow_angle = actor.limits().slow_angle;
+            let error = angle_diff(actor.heading(), self.target);
+            let mut rate = self.attitude.rate(error, max_acceleration, max_rate);
+            if error.abs() < slow_angle {
+                let limit = max_rate * error.abs() / slow_angle;
+                rate = rate.clamp(-limit, limit);
+            }
+            let rate = (rate + self.feedforward).clamp(-max_rate, max_rate);
+            Output {
+                linear: vec2(0.0, 0.0),
+                angular: self
+                    .attitude
+                    .torque(rate, actor.angular_velocity(), max_acceleration),
+            }
+            .sanitized()
+        }
+    }
+
+    pub struct Face<T: Kinematic> {
+        pub target: T,
+        pub attitude: Attitude,
+    }
+
+    impl<T: Kinematic> Face<T> {
+        pub fn new(target: T) -> Face<T> {
+            Face {
+                target,
+                attitude: Attitude::default(),
+            }
+        }
+
+        pub fn attitude(mut self, attitude: Attitude) -> Face<T> {
+            self.attitude = attitude;
+            self
+        }
+
+        pub fn line_of_sight_rate(&self, actor: &impl Kinematic) -> f64 {
+            self.line_of_sight(actor).1
+        }
+
+        pub fn line_of_sight(&self, actor: &impl Kinematic) -> (f64, f64) {
+            let offset = self.target.position() - actor.position();
+            let relative = self.target.velocity() - actor.velocity();
+            let range_squared = offset.dot(offset);
+            let rate = if range_squared < 1.0 {
+                0.0
+            } else {
+                cross(offset, relative) / range_squared
+            };
+            (offset.angle(), rate)
+        }
+    }
+
+    impl<T: Kinematic> Move for Face<T> {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            let (bearing, rate) = self.line_of_sight(actor);
+            Align {
+                target: bearing,
+                feedforward: rate,
+                attitude: self.attitude,
+            }
+            .execute(actor)
+        }
+    }
+
+    pub struct Station {
+        pub position: Vec2,
+        pub velocity: Vec2,
+        pub heading: f64,
+        pub lqr: Lqr,
+    }
+
+    impl Default for Station {
+        fn default() -> Self {
+            Station::new(vec2(0.0, 0.0))
+        }
+    }
+
+    impl Station {
+        pub fn new(position: Vec2) -> Station {
+            Station {
+                position,
+                velocity: vec2(0.0, 0.0),
+                heading: 0.0,
+                lqr: Lqr::default(),
+            }
+        }
+
+        pub fn velocity(mut self, velocity: Vec2) -> Station {
+            self.velocity = velocity;
+            self
+        }
+
+        pub fn heading(mut self, heading: f64) -> Station {
+            self.heading = heading;
+            self
+        }
+
+        pub fn lqr(mut self, lqr: Lqr) -> Station {
+            self.lqr = lqr;
+            self
+        }
+    }
+
+    impl Move for Station {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            let max_linear = actor.max_linear_acceleration();
+            let max_angular = actor.max_angular_acceleration();
+            let linear = self.lqr.linear(
+                self.position - actor.position(),
+                self.velocity - actor.velocity(),
+            );
+            let length = linear.length();
             Output {
-                linear: direction.normalize() * actor.max_linear_acceleration(),
+                linear: if length > max_linear {
+                    linear * (max_linear / length)
+                } else {
+                    linear
+                },
+                angular: self
+                    .lqr
+                    .angular(
+                        angle_diff(actor.heading(), self.heading),
+                        -actor.angular_velocity(),
+                    )
+                    .clamp(-max_angular, max_angular),
+            }
+            .sanitized()
+        }
+    }
+
+    pub struct OffsetPursuit<T: Kinematic> {
+        pub leader: T,
+        pub offset: Vec2,
+        pub gain: f64,
+    }
+
+    impl<T: Kinematic> OffsetPursuit<T> {
+        pub fn new(leader: T, offset: Vec2) -> OffsetPursuit<T> {
+            OffsetPursuit {
+                leader,
+                offset,
+                gain: 1.0,
+            }
+        }
+
+        pub fn gain(mut self, gain: f64) -> OffsetPursuit<T> {
+            self.gain = gain;
+            self
+        }
+
+        pub fn slot(&self) -> Vec2 {
+            self.leader.position() + self.offset.rotate(self.leader.heading())
+        }
+    }
+
+    impl<T: Kinematic> Move for OffsetPursuit<T> {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            let max_acceleration = actor.max_linear_acceleration();
+            let error = self.slot() - actor.position();
+            let distance = error.length();
+            let approach = if distance > 0.0 {
+                let speed = (self.gain * distance).min((2.0 * max_acceleration * distance).sqrt());
+                error * (speed / distance)
+            } else {
+                error
+            };
+            let correction = (self.leader.velocity() + approach - actor.velocity()) / TICK_LENGTH;
+            let length = correction.length();
+            Output {
+                linear: if length > max_acceleration {
+                    correction * (max_acceleration / length)
+                } else {
+                    correction
+                },
                 angular: 0.0,
             }
+            .sanitized()
+        }
+    }
+
+    pub struct KeepDistance {
+        pub target: Vec2,
+        pub distance: f64,
+    }
+
+    impl Default for KeepDistance {
+        fn default() -> Self {
+            KeepDistance::new(vec2(0.0, 0.0), 1_000.0)
+        }
+    }
+
+    impl KeepDistance {
+        pub fn new(target: Vec2, distance: f64) -> KeepDistance {
+            KeepDistance { target, distance }
+        }
+    }
+
+    impl Move for KeepDistance {
+        fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+            let offset = actor.position() - self.target;
+            let direction = if offset.length() > 0.0 {
+                offset.normalize()
+            } else {
+                vec2(1.0, 0.0).rotate(actor.heading())
+            };
+            let max_acceleration = actor.max_linear_acceleration();
+            let error = self.distance - offset.length();
+            let desired_speed = (2.0 * max_acceleration * error.abs()).sqrt() * error.signum();
+            let radial_speed = actor.velocity().dot(direction);
+            let correction = ((desired_speed - radial_speed) / TICK_LENGTH)
+                .clamp(-max_acceleration, max_acceleration);
+            Output {
+                linear: direction * correction,
+                angular: 0.0,
+            }
+            .sanitized()
+        }
+    }
+
+    macro_rules! strategies {
+        ($($variant:ident($strategy:ty)),* $(,)?) => {
+            pub enum Strategy<T: Kinematic> {
+                $($variant($strategy)),*
+            }
+
+            $(impl<T: Kinematic> From<$strategy> for Strategy<T> {
+                fn from(strategy: $strategy) -> Self {
+                    Strategy::$variant(strategy)
+                }
+            })*
+
+            impl<T: Kinematic> Move for Strategy<T> {
+                fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+                    match self {
+                        $(Strategy::$variant(strategy) => strategy.execute(actor)),*
+                    }
+                }
+                fn steer(&self, actor: &(impl Kinematic + Motor)) -> Steering {
+                    match self {
+                        $(Strategy::$variant(strategy) => strategy.steer(actor)),*
+                    }
+                }
+                fn name(&self) -> &'static str {
+                    match self {
+                        $(Strategy::$variant(strategy) => strategy.name()),*
+                    }
+                }
+            }
+        };
+    }
+
+    strategies!(
+        Seek(Seek),
+        Arrive(Arrive),
+        Align(Align),
+        Face(Face<T>),
+        Station(Station),
+        OffsetPursuit(OffsetPursuit<T>),
+        KeepDistance(KeepDistance),
+    );
+
+    impl<T: Kinematic> MovementBlend<Strategy<T>> {
+        pub fn strategy(mut self, strategy: impl Into<Strategy<T>>, weight: f64) -> Self {
+            self.moves.push((strategy.into(), weight));
+            self
         }
     }
 }
 
+#[cfg(feature = "oort")]
 use movement::*;
+#[cfg(feature = "oort")]
 use oort_api::prelude::*;
 
-pub struct Ship {}
+#[cfg(feature = "oort")]
+pub struct ShipState {
+    pub profile: profile::Profile,
+    pub blackboard: context::Blackboard,
+    pub ticks: u64,
+}
+
+#[cfg(feature = "oort")]
+impl ShipState {
+    pub fn new(profile: profile::Profile) -> ShipState {
+        ShipState {
+            profile,
+            blackboard: context::Blackboard::new(),
+            ticks: 0,
+        }
+    }
+}
+
+#[cfg(feature = "oort")]
+pub struct Ship {
+    brain: Box<dyn registry::Controller>,
+    state: ShipState,
+    oort: Oort,
+}
 
+#[cfg(feature = "oort")]
+impl Default for Ship {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "oort")]
 impl Ship {
     pub fn new() -> Ship {
-        Ship {}
+        Ship::configured(&registry::Registry::default(), profile::Profile::current())
+    }
+
+    pub fn configured(registry: &registry::Registry, profile: profile::Profile) -> Ship {
+        Ship {
+            brain: registry.current(&profile),
+            state: ShipState::new(profile),
+            oort: Oort::default(),
+        }
     }
+
+    pub fn state(&self) -> &ShipState {
+        &self.state
+    }
+
     pub fn tick(&mut self) {
-        let action = Seek { target: target() };
-        let result = action.execute(self);
-        accelerate(result.linear);
+        let blackboard = core::mem::take(&mut self.state.blackboard);
+        let mut context =
+            context::TickContext::with_draw(&self.oort, current_time(), draw::OortDraw)
+                .with_ship(id() as u16)
+                .with_blackboard(blackboard);
+        self.brain.tick(&mut context);
+        self.state.blackboard = context.blackboard;
+        self.state.ticks += 1;
     }
 }
 
-impl Kinematic for Ship {
+#[cfg(feature = "oort")]
+#[derive(Default)]
+pub struct Oort {
+    pub limits: SteeringLimits,
+}
+
+#[cfg(feature = "oort")]
+impl Kinematic for Oort {
     fn position(&self) -> Vec2 {
         oort_api::prelude::position()
     }
@@ -65,7 +1041,8 @@ impl Kinematic for Ship {
     }
 }
 
-impl Motor for Ship {
+#[cfg(feature = "oort")]
+impl Motor for Oort {
     fn max_linear_acceleration(&self) -> f64 {
         [
             max_forward_acceleration(),
@@ -79,4 +1056,10 @@ impl Motor for Ship {
     fn max_angular_acceleration(&self) -> f64 {
         oort_api::prelude::max_angular_acceleration()
     }
-}
\ No newline at end of file
+    fn max_angular_velocity(&self) -> f64 {
+        self.limits.max_angular_velocity
+    }
+    fn limits(&self) -> SteeringLimits {
+        self.limits
+    }
+}
@@ -0,0 +1,368 @@
+#[cfg(feature = "oort")]
+pub use oort_api::prelude::{angle_diff, vec2, Vec2, TICK_LENGTH};
+
+#[cfg(not(feature = "oort"))]
+pub use fallback::*;
+
+pub use scalar::Scalar;
+
+pub fn radians(degrees: f64) -> f64 {
+    degrees * (core::f64::consts::PI / 180.0)
+}
+
+pub fn degrees(radians: f64) -> f64 {
+    radians * (180.0 / core::f64::consts::PI)
+}
+
+pub fn cross(a: Vec2, b: Vec2) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+pub fn perpendicular(v: Vec2) -> Vec2 {
+    vec2(-v.y, v.x)
+}
+
+pub fn lerp(a: Vec2, b: Vec2, t: f64) -> Vec2 {
+    a + (b - a) * t
+}
+
+pub fn sq_length(v: Vec2) -> f64 {
+    v.dot(v)
+}
+
+pub fn within(a: Vec2, b: Vec2, radius: f64) -> bool {
+    sq_length(a - b) < radius * radius
+}
+
+#[cfg(feature = "fast-math")]
+pub use fast::{atan2, inv_sqrt, sin_cos};
+
+#[cfg(not(feature = "fast-math"))]
+pub fn sin_cos(angle: f64) -> (f64, f64) {
+    angle.sin_cos()
+}
+
+#[cfg(not(feature = "fast-math"))]
+pub fn atan2(y: f64, x: f64) -> f64 {
+    y.atan2(x)
+}
+
+#[cfg(not(feature = "fast-math"))]
+pub fn inv_sqrt(value: f64) -> f64 {
+    1.0 / value.sqrt()
+}
+
+pub mod fast {
+    use core::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+
+    pub fn sin_cos(angle: f64) -> (f64, f64) {
+        let quadrant = (angle / FRAC_PI_2 + 0.5) as i64 - (angle < -FRAC_PI_4) as i64;
+        let r = angle - quadrant as f64 * FRAC_PI_2;
+        let r2 = r * r;
+        let sin = r * (1.0 - r2 / 6.0 * (1.0 - r2 / 20.0 * (1.0 - r2 / 42.0)));
+        let cos = 1.0 - r2 / 2.0 * (1.0 - r2 / 12.0 * (1.0 - r2 / 30.0 * (1.0 - r2 / 56.0)));
+        match quadrant.rem_euclid(4) {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            2 => (-sin, -cos),
+            _ => (-cos, sin),
+        }
+    }
+
+    fn atan(z: f64) -> f64 {
+        let z2 = z * z;
+        z * (0.999_977_26
+            + z2 * (-0.332_623_47
+                + z2 * (0.193_543_46
+                    + z2 * (-0.116_432_87 + z2 * (0.052_653_32 - 0.011_721_20 * z2)))))
+    }
+
+    pub fn atan2(y: f64, x: f64) -> f64 {
+        if x == 0.0 && y == 0.0 {
+            return 0.0;
+        }
+        let angle = if x.abs() >= y.abs() {
+            atan(y / x)
+        } else {
+            FRAC_PI_2.copysign(y) - atan(x / y)
+        };
+        if x < 0.0 && x.abs() >= y.abs() {
+            if y >= 0.0 {
+                angle + PI
+            } else {
+                angle - PI
+            }
+        } else {
+            angle
+        }
+    }
+
+    pub fn inv_sqrt(value: f64) -> f64 {
+        let estimate = f64::from_bits(0x5FE6_EB50_C7B5_37A9 - (value.to_bits() >> 1));
+        let half = 0.5 * value;
+        let refine = |y: f64| y * (1.5 - half * y * y);
+        refine(refine(refine(estimate)))
+    }
+}
+
+mod scalar {
+    use core::fmt::Debug;
+    use core::ops::{Add, Div, Mul, Neg, Sub};
+
+    pub trait Scalar:
+        Copy
+        + Debug
+        + PartialOrd
+        + Add<Output = Self>
+        + Sub<Output = Self>
+        + Mul<Output = Self>
+        + Div<Output = Self>
+        + Neg<Output = Self>
+    {
+        const ZERO: Self;
+        const ONE: Self;
+        const INFINITY: Self;
+
+        fn from_f64(value: f64) -> Self;
+        fn to_f64(self) -> f64;
+        fn abs(self) -> Self;
+        fn signum(self) -> Self;
+        fn min(self, other: Self) -> Self;
+        fn max(self, other: Self) -> Self;
+        fn clamp(self, low: Self, high: Self) -> Self;
+        fn is_finite(self) -> bool;
+        fn sqrt(self) -> Self;
+        fn exp(self) -> Self;
+        fn hypot(self, other: Self) -> Self;
+        fn atan2(self, other: Self) -> Self;
+        fn sin_cos(self) -> (Self, Self);
+        fn round(self) -> Self;
+        fn ceil(self) -> Self;
+        fn rem_euclid(self, modulus: Self) -> Self;
+    }
+
+    macro_rules! scalar {
+        ($t:ident, $sqrt:ident, $exp:ident, $hypot:ident, $atan2:ident, $sincos:ident, $round:ident, $ceil:ident, $fmod:ident) => {
+            impl Scalar for $t {
+                const ZERO: $t = 0.0;
+                const ONE: $t = 1.0;
+                const INFINITY: $t = $t::INFINITY;
+
+                fn from_f64(value: f64) -> $t {
+                    value as $t
+                }
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+                fn abs(self) -> $t {
+                    $t::abs(self)
+                }
+                fn signum(self) -> $t {
+                    $t::signum(self)
+                }
+                fn min(self, other: $t) -> $t {
+                    $t::min(self, other)
+                }
+                fn max(self, other: $t) -> $t {
+                    $t::max(self, other)
+                }
+                fn clamp(self, low: $t, high: $t) -> $t {
+                    $t::clamp(self, low, high)
+                }
+                fn is_finite(self) -> bool {
+                    $t::is_finite(self)
+                }
+
+                #[cfg(feature = "std")]
+                fn sqrt(self) -> $t {
+                    $t::sqrt(self)
+                }
+                #[cfg(feature = "std")]
+                fn exp(self) -> $t {
+                    $t::exp(self)
+                }
+                #[cfg(feature = "std")]
+                fn hypot(self, other: $t) -> $t {
+                    $t::hypot(self, other)
+                }
+                #[cfg(feature = "std")]
+                fn atan2(self, other: $t) -> $t {
+                    $t::atan2(self, other)
+                }
+                #[cfg(feature = "std")]
+                fn sin_cos(self) -> ($t, $t) {
+                    $t::sin_cos(self)
+                }
+                #[cfg(feature = "std")]
+                fn round(self) -> $t {
+                    $t::round(self)
+                }
+                #[cfg(feature = "std")]
+                fn ceil(self) -> $t {
+                    $t::ceil(self)
+                }
+                #[cfg(feature = "std")]
+                fn rem_euclid(self, modulus: $t) -> $t {
+                    $t::rem_euclid(self, modulus)
+                }
+
+                #[cfg(not(feature = "std"))]
+                fn sqrt(self) -> $t {
+                    libm::$sqrt(self)
+                }
+                #[cfg(not(feature = "std"))]
+                fn exp(self) -> $t {
+                    libm::$exp(self)
+                }
+                #[cfg(not(feature = "std"))]
+                fn hypot(self, other: $t) -> $t {
+                    libm::$hypot(self, other)
+                }
+                #[cfg(not(feature = "std"))]
+                fn atan2(self, other: $t) -> $t {
+                    libm::$atan2(self, other)
+                }
+                #[cfg(not(feature = "std"))]
+                fn sin_cos(self) -> ($t, $t) {
+                    libm::$sincos(self)
+                }
+                #[cfg(not(feature = "std"))]
+                fn round(self) -> $t {
+                    libm::$round(self)
+                }
+                #[cfg(not(feature = "std"))]
+                fn ceil(self) -> $t {
+                    libm::$ceil(self)
+                }
+                #[cfg(not(feature = "std"))]
+                fn rem_euclid(self, modulus: $t) -> $t {
+                    let remainder = libm::$fmod(self, modulus);
+                    if remainder < 0.0 {
+                        remainder + modulus.abs()
+                    } else {
+                        remainder
+                    }
+                }
+            }
+        };
+    }
+
+    scalar!(f32, sqrtf, expf, hypotf, atan2f, sincosf, roundf, ceilf, fmodf);
+    scalar!(f64, sqrt, exp, hypot, atan2, sincos, round, ceil, fmod);
+}
+
+#[cfg(not(feature = "oort"))]
+mod fallback {
+    #[cfg(not(feature = "std"))]
+    #[allow(unused_imports)]
+    use super::Scalar;
+    use core::f64::consts::{PI, TAU};
+    use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+    pub const TICK_LENGTH: f64 = 1.0 / 60.0;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq)]
+    pub struct Vec2 {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    pub fn vec2(x: f64, y: f64) -> Vec2 {
+        Vec2 { x, y }
+    }
+
+    pub fn angle_diff(a: f64, b: f64) -> f64 {
+        let c = (b - a).rem_euclid(TAU);
+        if c > PI {
+            c - TAU
+        } else {
+            c
+        }
+    }
+
+    impl Vec2 {
+        pub fn length(self) -> f64 {
+            self.x.hypot(self.y)
+        }
+
+        pub fn normalize(self) -> Vec2 {
+            self / self.length()
+        }
+
+        pub fn rotate(self, angle: f64) -> Vec2 {
+            let (sin, cos) = angle.sin_cos();
+            vec2(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+        }
+
+        pub fn angle(self) -> f64 {
+            self.y.atan2(self.x)
+        }
+
+        pub fn dot(self, other: Vec2) -> f64 {
+            self.x * other.x + self.y * other.y
+        }
+
+        pub fn distance(self, other: Vec2) -> f64 {
+            (self - other).length()
+        }
+    }
+
+    impl Add for Vec2 {
+        type Output = Vec2;
+        fn add(self, other: Vec2) -> Vec2 {
+            vec2(self.x + other.x, self.y + other.y)
+        }
+    }
+
+    impl Sub for Vec2 {
+        type Output = Vec2;
+        fn sub(self, other: Vec2) -> Vec2 {
+            vec2(self.x - other.x, self.y - other.y)
+        }
+    }
+
+    impl Mul<f64> for Vec2 {
+        type Output = Vec2;
+        fn mul(self, scale: f64) -> Vec2 {
+            vec2(self.x * scale, self.y * scale)
+        }
+    }
+
+    impl Div<f64> for Vec2 {
+        type Output = Vec2;
+        fn div(self, scale: f64) -> Vec2 {
+            vec2(self.x / scale, self.y / scale)
+        }
+    }
+
+    impl Neg for Vec2 {
+        type Output = Vec2;
+        fn neg(self) -> Vec2 {
+            vec2(-self.x, -self.y)
+        }
+    }
+
+    impl AddAssign for Vec2 {
+        fn add_assign(&mut self, other: Vec2) {
+            *self = *self + other;
+        }
+    }
+
+    impl SubAssign for Vec2 {
+        fn sub_assign(&mut self, other: Vec2) {
+            *self = *self - other;
+        }
+    }
+
+    impl MulAssign<f64> for Vec2 {
+        fn mul_assign(&mut self, scale: f64) {
+            *self = *self * scale;
+        }
+    }
+
+    impl DivAssign<f64> for Vec2 {
+        fn div_assign(&mut self, scale: f64) {
+            *self = *self / scale;
+        }
+    }
+}
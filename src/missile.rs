@@ -0,0 +1,641 @@
+use crate::abilities::Abilities;
+use crate::math::{cross, inv_sqrt, perpendicular};
+use crate::movement::*;
+use crate::radio;
+use crate::random;
+use crate::targeting::closest_approach;
+use crate::time::Seconds;
+use oort_api::prelude::*;
+
+pub struct Performance {
+    pub acceleration: f64,
+    pub delta_v: f64,
+    pub lifetime: f64,
+}
+
+impl Performance {
+    pub fn burn_time(&self) -> f64 {
+        self.delta_v / self.acceleration
+    }
+    pub fn reach(&self, time: f64) -> f64 {
+        let burn = self.burn_time().min(time);
+        0.5 * self.acceleration * burn * burn
+            + self.delta_v.min(self.acceleration * burn) * (time - burn)
+    }
+}
+
+pub struct LaunchSolution {
+    pub heading: f64,
+    pub intercept_time: f64,
+    pub intercept_point: Vec2,
+}
+
+pub fn launch_solution(
+    launcher: &impl Kinematic,
+    target: &impl Kinematic,
+    missile: &Performance,
+) -> Option<LaunchSolution> {
+    let offset = target.position() - launcher.position();
+    let relative_velocity = target.velocity() - launcher.velocity();
+    let miss = |time: f64| missile.reach(time) - (offset + relative_velocity * time).length();
+    let time = first_root(miss, missile.lifetime)?;
+    let aim = offset + relative_velocity * time;
+    Some(LaunchSolution {
+        heading: aim.angle(),
+        intercept_time: time,
+        intercept_point: target.position() + target.velocity() * time,
+    })
+}
+
+pub fn launch_solution_near(
+    launcher: &impl Kinematic,
+    target: &impl Kinematic,
+    missile: &Performance,
+    guess: f64,
+) -> Option<LaunchSolution> {
+    let offset = target.position() - launcher.position();
+    let relative_velocity = target.velocity() - launcher.velocity();
+    let miss = |time: f64| missile.reach(time) - (offset + relative_velocity * time).length();
+    let window = missile.lifetime / 64.0;
+    let low = (guess - window).max(0.0);
+    let high = (guess + window).min(missile.lifetime);
+    let warm = guess.is_finite() && low < high && miss(low) < 0.0 && miss(high) >= 0.0;
+    if !warm {
+        return launch_solution(launcher, target, missile);
+    }
+    let time = bisect(miss, low, high);
+    let aim = offset + relative_velocity * time;
+    Some(LaunchSolution {
+        heading: aim.angle(),
+        intercept_time: time,
+        intercept_point: target.position() + target.velocity() * time,
+    })
+}
+
+#[derive(Default)]
+pub struct LaunchCache {
+    entries: Vec<(u32, f64)>,
+}
+
+impl LaunchCache {
+    pub fn new() -> LaunchCache {
+        LaunchCache::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn forget(&mut self, key: u32) {
+        self.entries.retain(|(entry, _)| *entry != key);
+    }
+
+    pub fn solve(
+        &mut self,
+        key: u32,
+        launcher: &impl Kinematic,
+        target: &impl Kinematic,
+        missile: &Performance,
+    ) -> Option<LaunchSolution> {
+        let index = self.entries.iter().position(|(entry, _)| *entry == key);
+        let solution = match index {
+            Some(index) => launch_solution_near(launcher, target, missile, self.entries[index].1),
+            None => launch_solution(launcher, target, missile),
+        };
+        match (index, &solution) {
+            (Some(index), Some(solution)) => self.entries[index].1 = solution.intercept_time,
+            (Some(index), None) => {
+                self.entries.swap_remove(index);
+            }
+            (None, Some(solution)) => self.entries.push((key, solution.intercept_time)),
+            (None, None) => {}
+        }
+        solution
+    }
+}
+
+pub fn can_reach(
+    launcher: &impl Kinematic,
+    target: &impl Kinematic,
+    missile: &Performance,
+) -> bool {
+    launch_solution(launcher, target, missile).is_some()
+}
+
+fn first_root(f: impl Fn(f64) -> f64, horizon: f64) -> Option<f64> {
+    const STEPS: usize = 64;
+    if horizon.is_nan() || horizon <= 0.0 {
+        return None;
+    }
+    let step = horizon / STEPS as f64;
+    let high = (1..=STEPS)
+        .map(|i| i as f64 * step)
+        .find(|&t| f(t) >= 0.0)?;
+    Some(bisect(f, high - step, high))
+}
+
+fn bisect(f: impl Fn(f64) -> f64, mut low: f64, mut high: f64) -> f64 {
+    const ITERATIONS: usize = 32;
+    for _ in 0..ITERATIONS {
+        let mid = 0.5 * (low + high);
+        if f(mid) >= 0.0 {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    high
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TargetState {
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+impl TargetState {
+    pub fn of(target: &impl Kinematic) -> TargetState {
+        TargetState {
+            position: target.position(),
+            velocity: target.velocity(),
+        }
+    }
+    pub fn extrapolate(&self, time: f64) -> TargetState {
+        TargetState {
+            position: self.position + self.velocity * time,
+            velocity: self.velocity,
+        }
+    }
+}
+
+impl Kinematic for TargetState {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.velocity.angle()
+    }
+    fn angular_velocity(&self) -> f64 {
+        0.0
+    }
+}
+
+pub const ALL_MISSILES: u16 = u16::MAX;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Designation {
+    pub track: u16,
+    pub target: TargetState,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Retarget {
+    pub missile: u16,
+    pub track: u16,
+    pub target: TargetState,
+}
+
+impl Retarget {
+    pub fn addresses(&self, missile: u16) -> bool {
+        self.missile == ALL_MISSILES || self.missile == missile
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RetargetAck {
+    pub accepted: bool,
+    pub intercept_time: f64,
+}
+
+pub fn retarget_solution(
+    missile: &impl Kinematic,
+    target: &TargetState,
+    acceleration: f64,
+    remaining_delta_v: f64,
+    horizon: f64,
+) -> Option<LaunchSolution> {
+    let performance = Performance {
+        acceleration,
+        delta_v: remaining_delta_v,
+        lifetime: horizon,
+    };
+    launch_solution(missile, target, &performance)
+}
+
+pub struct Uplink {
+    pub channel: usize,
+}
+
+impl Uplink {
+    pub fn transmit(&self, track: u32, target: &impl Kinematic) {
+        radio::transmit(
+            self.channel,
+            &Designation {
+                track: track as u16,
+                target: TargetState::of(target),
+            },
+        );
+    }
+
+    pub fn retarget(&self, missile: u16, track: u32, target: &impl Kinematic) {
+        radio::transmit(
+            self.channel,
+            &Retarget {
+                missile,
+                track: track as u16,
+                target: TargetState::of(target),
+            },
+        );
+    }
+
+    pub fn acknowledgements(&self) -> Vec<(u16, RetargetAck)> {
+        radio::receive_on(self.channel)
+            .filter_map(|message| {
+                let header = radio::header(message)?;
+                Some((header.sender, radio::decode::<RetargetAck>(message)?))
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Guidance {
+    Ballistic,
+    Command,
+    Autonomous,
+}
+
+pub struct Datalink {
+    pub channel: usize,
+    pub missile: u16,
+    pub mode: Guidance,
+    pub seeker_gate: f64,
+    uplinked: Option<(TargetState, f64)>,
+    pending: Option<Retarget>,
+    track: Option<u16>,
+}
+
+impl Datalink {
+    pub fn new(channel: usize) -> Datalink {
+        Datalink {
+            channel,
+            missile: id() as u16,
+            mode: Guidance::Ballistic,
+            seeker_gate: 1_000.0,
+            uplinked: None,
+            pending: None,
+            track: None,
+        }
+    }
+    pub fn track(&self) -> Option<u16> {
+        self.track
+    }
+    pub fn accept(&mut self, message: Message, now: f64) {
+        if let Some(designation) = radio::decode::<Designation>(message) {
+            if self.track.is_none_or(|track| track == designation.track) {
+                self.uplinked = Some((designation.target, now));
+            }
+        } else if let Some(order) = radio::decode::<Retarget>(message) {
+            if order.addresses(self.missile) && self.track != Some(order.track) {
+                self.pending = Some(order);
+            }
+        }
+    }
+    pub fn pending(&mut self) -> Option<Retarget> {
+        self.pending.take()
+    }
+    pub fn retarget(&mut self, order: &Retarget, now: f64) {
+        self.uplinked = Some((order.target, now));
+        self.mode = Guidance::Command;
+        self.track = Some(order.track);
+    }
+    pub fn consider(
+        &mut self,
+        actor: &(impl Kinematic + Motor),
+        remaining_delta_v: f64,
+        horizon: f64,
+        now: f64,
+    ) -> Option<RetargetAck> {
+        let order = self.pending()?;
+        let solution = retarget_solution(
+            actor,
+            &order.target,
+            actor.max_linear_acceleration(),
+            remaining_delta_v,
+            horizon,
+        );
+        if solution.is_some() {
+            self.retarget(&order, now);
+        }
+        Some(RetargetAck {
+            accepted: solution.is_some(),
+            intercept_time: solution.map_or(f64::INFINITY, |solution| solution.intercept_time),
+        })
+    }
+    pub fn update(&mut self, seeker: Option<&ScanResult>, now: f64) -> Option<TargetState> {
+        for message in radio::receive_on(self.channel) {
+            self.accept(message, now);
+        }
+        self.select(seeker, now)
+    }
+    pub fn select(&mut self, seeker: Option<&ScanResult>, now: f64) -> Option<TargetState> {
+        let expected = self
+            .uplinked
+            .map(|(state, received)| state.extrapolate(now - received));
+        let seeker = seeker.filter(|contact| {
+            self.track.is_none()
+                || self.mode == Guidance::Autonomous
+                || expected.is_some_and(|state| {
+                    state.position.distance(contact.position) <= self.seeker_gate
+                })
+        });
+        if let Some(contact) = seeker {
+            self.mode = Guidance::Autonomous;
+            return Some(TargetState {
+                position: contact.position,
+                velocity: contact.velocity,
+            });
+        }
+        let (state, received) = self.uplinked?;
+        if self.mode == Guidance::Ballistic {
+            self.mode = Guidance::Command;
+        }
+        Some(state.extrapolate(now - received))
+    }
+}
+
+pub struct ProportionalNavigation<T: Kinematic> {
+    pub target: T,
+    pub gain: f64,
+}
+
+impl<T: Kinematic> Move for ProportionalNavigation<T> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let line_of_sight = self.target.position() - actor.position();
+        let relative_velocity = self.target.velocity() - actor.velocity();
+        let range_squared = line_of_sight.dot(line_of_sight);
+        let max_acceleration = actor.max_linear_acceleration();
+        if range_squared == 0.0 {
+            return Output {
+                linear: vec2(0.0, 0.0),
+                angular: 0.0,
+            };
+        }
+        let direction = line_of_sight * inv_sqrt(range_squared);
+        let normal = perpendicular(direction);
+        let closing_speed = -relative_velocity.dot(direction);
+        let rotation_rate = cross(line_of_sight, relative_velocity) / range_squared;
+        let lateral =
+            (self.gain * closing_speed * rotation_rate).clamp(-max_acceleration, max_acceleration);
+        let axial = (max_acceleration * max_acceleration - lateral * lateral).sqrt();
+        Output {
+            linear: normal * lateral + direction * axial,
+            angular: 0.0,
+        }
+    }
+}
+
+pub fn time_to_go(actor: &impl Kinematic, target: &impl Kinematic) -> Option<Seconds> {
+    let line_of_sight = target.position() - actor.position();
+    let closing_speed = -(target.velocity() - actor.velocity()).dot(line_of_sight.normalize());
+    (closing_speed > 0.0).then(|| Seconds(line_of_sight.length() / closing_speed))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Weave {
+    pub seed: u64,
+    pub window: f64,
+    pub period: f64,
+    pub amplitude: f64,
+    pub budget: f64,
+}
+
+impl Weave {
+    pub fn lateral(
+        &self,
+        time: f64,
+        time_to_go: f64,
+        remaining_delta_v: f64,
+        max_acceleration: f64,
+    ) -> f64 {
+        if time_to_go > self.window || time_to_go <= 0.0 || self.period <= 0.0 {
+            return 0.0;
+        }
+        let phase = time / self.period;
+        let segment = phase.floor();
+        let blend = phase - segment;
+        let blend = blend * blend * (3.0 - 2.0 * blend);
+        let from = random::noise(self.seed, segment as u64);
+        let to = random::noise(self.seed, segment as u64 + 1);
+        let limit = (self.amplitude * max_acceleration)
+            .min(self.budget * remaining_delta_v.max(0.0) / time_to_go);
+        limit * (from + (to - from) * blend)
+    }
+}
+
+pub struct TerminalWeave<T: Kinematic> {
+    pub guidance: ProportionalNavigation<T>,
+    pub weave: Weave,
+    pub time: f64,
+    pub remaining_delta_v: f64,
+}
+
+impl<T: Kinematic> Move for TerminalWeave<T> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let command = self.guidance.execute(actor);
+        let Some(time_to_go) = time_to_go(actor, &self.guidance.target) else {
+            return command;
+        };
+        let max_acceleration = actor.max_linear_acceleration();
+        let lateral = self.weave.lateral(
+            self.time,
+            time_to_go.get(),
+            self.remaining_delta_v,
+            max_acceleration,
+        );
+        let direction = (self.guidance.target.position() - actor.position()).normalize();
+        let linear = command.linear + perpendicular(direction) * lateral;
+        let length = linear.length();
+        Output {
+            linear: if length > max_acceleration {
+                linear * (max_acceleration / length)
+            } else {
+                linear
+            },
+            angular: command.angular,
+        }
+    }
+}
+
+pub struct ZeroEffortMiss<T: Kinematic> {
+    pub target: T,
+    pub gain: f64,
+}
+
+impl<T: Kinematic> Move for ZeroEffortMiss<T> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let max_acceleration = actor.max_linear_acceleration();
+        let linear = match time_to_go(actor, &self.target) {
+            Some(time_to_go) if time_to_go > Seconds::ZERO => {
+                let line_of_sight = self.target.position() - actor.position();
+                let relative_velocity = self.target.velocity() - actor.velocity();
+                let miss = line_of_sight + relative_velocity * time_to_go;
+                let direction = line_of_sight.normalize();
+                let lateral = miss - direction * miss.dot(direction);
+                let command = lateral * (self.gain / (time_to_go.get() * time_to_go.get()));
+                let length = command.length();
+                if length > max_acceleration {
+                    command * (max_acceleration / length)
+                } else {
+                    command
+                }
+            }
+            _ => (self.target.position() - actor.position()).normalize() * max_acceleration,
+        };
+        Output {
+            linear,
+            angular: 0.0,
+        }
+    }
+}
+
+pub struct Torpedo {
+    pub datalink: Datalink,
+    pub abilities: Abilities,
+    pub gain: f64,
+    pub acquisition_range: f64,
+    pub seeker_width: f64,
+    pub fuze_radius: f64,
+    pub horizon: f64,
+}
+
+impl Torpedo {
+    pub fn new(channel: usize) -> Torpedo {
+        Torpedo {
+            datalink: Datalink::new(channel),
+            abilities: Abilities::for_class(Class::Torpedo),
+            gain: 3.0,
+            acquisition_range: 2_000.0,
+            seeker_width: 0.2,
+            fuze_radius: 25.0,
+            horizon: 60.0,
+        }
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let contact = scan();
+        let now = current_time();
+        if let Some(threat) = contact.as_ref().filter(|c| c.class == Class::Missile) {
+            let threat = TargetState {
+                position: threat.position,
+                velocity: threat.velocity,
+            };
+            self.abilities.decoy_against(now, actor, &threat);
+        }
+        let seeker =
+            contact.filter(|contact| matches!(contact.class, Class::Frigate | Class::Cruiser));
+        let mut target = self.datalink.update(seeker.as_ref(), now);
+        if let Some(ack) = self.datalink.consider(actor, fuel(), self.horizon, now) {
+            radio::transmit(self.datalink.channel, &ack);
+            if ack.accepted {
+                target = self.datalink.select(None, now);
+            }
+        }
+        let Some(target) = target else {
+            return;
+        };
+        let offset = target.position - actor.position();
+        set_radar_heading(offset.angle());
+        set_radar_width(self.seeker_width);
+        set_radar_max_distance(self.acquisition_range);
+        let approach = closest_approach(actor, &target);
+        if offset.length() < self.fuze_radius
+            || (approach.time <= Seconds::TICK && approach.distance < self.fuze_radius)
+        {
+            explode();
+            return;
+        }
+        let command = ZeroEffortMiss {
+            target,
+            gain: self.gain,
+        }
+        .execute(actor);
+        accelerate(command.linear);
+    }
+}
+
+pub struct Missile {
+    pub datalink: Datalink,
+    pub gain: f64,
+    pub weave: Weave,
+    pub acquisition_range: f64,
+    pub seeker_width: f64,
+    pub fuze_radius: f64,
+    pub horizon: f64,
+}
+
+impl Missile {
+    pub fn new(channel: usize) -> Missile {
+        Missile {
+            datalink: Datalink::new(channel),
+            gain: 4.0,
+            weave: Weave {
+                seed: seed() as u64,
+                window: 2.0,
+                period: 0.4,
+                amplitude: 0.5,
+                budget: 0.3,
+            },
+            acquisition_range: 5_000.0,
+            seeker_width: 0.3,
+            fuze_radius: 20.0,
+            horizon: 20.0,
+        }
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let seeker =
+            scan().filter(|contact| !matches!(contact.class, Class::Missile | Class::Torpedo));
+        let now = current_time();
+        let mut target = self.datalink.update(seeker.as_ref(), now);
+        if let Some(ack) = self.datalink.consider(actor, fuel(), self.horizon, now) {
+            radio::transmit(self.datalink.channel, &ack);
+            if ack.accepted {
+                target = self.datalink.select(None, now);
+            }
+        }
+        let Some(target) = target else {
+            accelerate(vec2(1.0, 0.0).rotate(actor.heading()) * actor.max_linear_acceleration());
+            return;
+        };
+        let offset = target.position - actor.position();
+        set_radar_heading(offset.angle());
+        set_radar_width(self.seeker_width);
+        set_radar_max_distance(self.acquisition_range);
+        let approach = closest_approach(actor, &target);
+        if offset.length() < self.fuze_radius
+            || (approach.time <= Seconds::TICK && approach.distance < self.fuze_radius)
+        {
+            explode();
+            return;
+        }
+        let command = TerminalWeave {
+            guidance: ProportionalNavigation {
+                target,
+                gain: self.gain,
+            },
+            weave: self.weave,
+            time: current_time(),
+            remaining_delta_v: fuel(),
+        }
+        .execute(actor);
+        accelerate(command.linear);
+    }
+}
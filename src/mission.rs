@@ -0,0 +1,155 @@
+use crate::fleet::Roster;
+use crate::missile::TargetState;
+use crate::movement::*;
+use crate::perception::{TrackId, Tracker};
+use crate::planning::Arena;
+use oort_api::prelude::*;
+
+#[derive(Clone, Debug)]
+pub enum Task {
+    GoTo(Vec2),
+    Patrol(Vec<Vec2>),
+    Engage(TrackId),
+    Escort(u16),
+    Rtb,
+}
+
+#[derive(Clone, Debug)]
+pub struct Queued {
+    pub task: Task,
+    pub priority: i32,
+}
+
+pub struct Mission {
+    pub home: Vec2,
+    pub arrival_radius: f64,
+    pub arrival_speed: f64,
+    pub escort_offset: Vec2,
+    pub gain: f64,
+    pub arena: Option<Arena>,
+    tasks: Vec<Queued>,
+    leg: usize,
+}
+
+impl Mission {
+    pub fn new(home: Vec2) -> Mission {
+        Mission {
+            home,
+            arrival_radius: 100.0,
+            arrival_speed: 10.0,
+            escort_offset: vec2(-500.0, 0.0),
+            gain: 1.0,
+            arena: None,
+            tasks: Vec::new(),
+            leg: 0,
+        }
+    }
+
+    pub fn push(&mut self, task: Task, priority: i32) {
+        let index = self
+            .tasks
+            .iter()
+            .position(|queued| queued.priority < priority)
+            .unwrap_or(self.tasks.len());
+        if index == 0 {
+            self.leg = 0;
+        }
+        self.tasks.insert(index, Queued { task, priority });
+    }
+
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+        self.leg = 0;
+    }
+
+    pub fn tasks(&self) -> &[Queued] {
+        &self.tasks
+    }
+
+    pub fn current(&self) -> Option<&Task> {
+        self.tasks.first().map(|queued| &queued.task)
+    }
+
+    fn complete(&mut self) {
+        self.tasks.remove(0);
+        self.leg = 0;
+    }
+
+    fn arrived(&self, actor: &impl Kinematic, point: Vec2) -> bool {
+        let point = self.arena.map_or(point, |arena| arena.clamp(point));
+        actor.position().distance(point) < self.arrival_radius
+            && actor.velocity().length() < self.arrival_speed
+    }
+
+    fn go_to(&self, actor: &(impl Kinematic + Motor), point: Vec2) -> Output {
+        let point = self.arena.map_or(point, |arena| arena.clamp(point));
+        OffsetPursuit {
+            leader: TargetState {
+                position: point,
+                velocity: vec2(0.0, 0.0),
+            },
+            offset: vec2(0.0, 0.0),
+            gain: self.gain,
+        }
+        .execute(actor)
+    }
+
+    pub fn update(
+        &mut self,
+        actor: &(impl Kinematic + Motor),
+        tracker: &Tracker,
+        roster: &Roster,
+        now: f64,
+    ) -> Option<Output> {
+        loop {
+            let mut leg = self.leg;
+            let output = match self.current()? {
+                Task::GoTo(point) => {
+                    (!self.arrived(actor, *point)).then(|| self.go_to(actor, *point))
+                }
+                Task::Rtb => {
+                    (!self.arrived(actor, self.home)).then(|| self.go_to(actor, self.home))
+                }
+                Task::Patrol(points) => {
+                    if points.is_empty() {
+                        None
+                    } else {
+                        leg %= points.len();
+                        if actor.position().distance(points[leg]) < self.arrival_radius {
+                            leg = (leg + 1) % points.len();
+                        }
+                        Some(
+                            Seek {
+                                target: self
+                                    .arena
+                                    .map_or(points[leg], |arena| arena.clamp(points[leg])),
+                            }
+                            .execute(actor),
+                        )
+                    }
+                }
+                Task::Engage(id) => tracker.get(*id).map(|track| {
+                    Seek {
+                        target: track.predict(now).position,
+                    }
+                    .execute(actor)
+                }),
+                Task::Escort(ship) => roster.get(*ship).map(|friend| {
+                    OffsetPursuit {
+                        leader: *friend,
+                        offset: self.escort_offset,
+                        gain: self.gain,
+                    }
+                    .execute(actor)
+                }),
+            };
+            match output {
+                Some(output) => {
+                    self.leg = leg;
+                    return Some(output);
+                }
+                None => self.complete(),
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::context::Blackboard;
+use crate::math::*;
+use crate::movement::Kinematic;
+use alloc::vec::Vec;
+use core::f64::consts::FRAC_PI_2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    Unknown,
+    Charger,
+    Orbiter,
+    Kiter,
+    Spammer,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Counter {
+    Standoff,
+    Cutoff,
+    Pursue,
+    Defend,
+}
+
+impl Pattern {
+    pub fn code(self) -> u32 {
+        match self {
+            Pattern::Unknown => 0,
+            Pattern::Charger => 1,
+            Pattern::Orbiter => 2,
+            Pattern::Kiter => 3,
+            Pattern::Spammer => 4,
+        }
+    }
+
+    pub fn from_code(code: u32) -> Option<Pattern> {
+        Some(match code {
+            0 => Pattern::Unknown,
+            1 => Pattern::Charger,
+            2 => Pattern::Orbiter,
+            3 => Pattern::Kiter,
+            4 => Pattern::Spammer,
+            _ => return None,
+        })
+    }
+
+    pub fn counter(self) -> Option<Counter> {
+        match self {
+            Pattern::Unknown => None,
+            Pattern::Charger => Some(Counter::Standoff),
+            Pattern::Orbiter => Some(Counter::Cutoff),
+            Pattern::Kiter => Some(Counter::Pursue),
+            Pattern::Spammer => Some(Counter::Defend),
+        }
+    }
+
+    pub fn publish(self, blackboard: &mut Blackboard) {
+        blackboard.set("opponent.pattern", self.code());
+    }
+
+    pub fn read(blackboard: &Blackboard) -> Pattern {
+        blackboard
+            .id("opponent.pattern")
+            .and_then(Pattern::from_code)
+            .unwrap_or(Pattern::Unknown)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Sample {
+    time: f64,
+    range_rate: f64,
+    tangential: f64,
+    aspect: f64,
+}
+
+pub struct Classifier {
+    pub window: f64,
+    pub min_samples: usize,
+    pub charge_speed: f64,
+    pub charge_aspect: f64,
+    pub orbit_speed: f64,
+    pub orbit_ratio: f64,
+    pub kite_aspect: f64,
+    pub kite_closure: f64,
+    pub spam_rate: f64,
+    samples: Vec<Sample>,
+    launches: Vec<f64>,
+}
+
+impl Default for Classifier {
+    fn default() -> Self {
+        Classifier {
+            window: 10.0,
+            min_samples: 30,
+            charge_speed: 50.0,
+            charge_aspect: 0.5,
+            orbit_speed: 30.0,
+            orbit_ratio: 0.3,
+            kite_aspect: 2.0,
+            kite_closure: 10.0,
+            spam_rate: 0.2,
+            samples: Vec::new(),
+            launches: Vec::new(),
+        }
+    }
+}
+
+impl Classifier {
+    pub fn new() -> Classifier {
+        Classifier::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    fn expire(&mut self, now: f64) {
+        let horizon = now - self.window;
+        self.samples.retain(|sample| sample.time >= horizon);
+        self.launches.retain(|time| *time >= horizon);
+    }
+
+    pub fn observe(&mut self, own: &impl Kinematic, enemy: &impl Kinematic, now: f64) {
+        let offset = enemy.position() - own.position();
+        let range = offset.length();
+        if range <= 0.0 || !range.is_finite() || !now.is_finite() {
+            return;
+        }
+        let line_of_sight = offset / range;
+        let relative = enemy.velocity() - own.velocity();
+        let speed = enemy.velocity().length();
+        let aspect = if speed > 1.0 {
+            let inbound = -line_of_sight;
+            atan2(
+                cross(enemy.velocity(), inbound).abs(),
+                enemy.velocity().dot(inbound),
+            )
+        } else {
+            FRAC_PI_2
+        };
+        self.samples.push(Sample {
+            time: now,
+            range_rate: relative.dot(line_of_sight),
+            tangential: cross(line_of_sight, relative),
+            aspect,
+        });
+        self.expire(now);
+    }
+
+    pub fn launch(&mut self, now: f64) {
+        self.launches.push(now);
+        self.expire(now);
+    }
+
+    pub fn pattern(&self) -> Pattern {
+        if self.launches.len() as f64 >= self.spam_rate * self.window {
+            return Pattern::Spammer;
+        }
+        if self.samples.len() < self.min_samples.max(1) {
+            return Pattern::Unknown;
+        }
+        let n = self.samples.len() as f64;
+        let mean = |f: fn(&Sample) -> f64| self.samples.iter().map(f).sum::<f64>() / n;
+        let range_rate = mean(|sample| sample.range_rate);
+        let tangential = mean(|sample| sample.tangential).abs();
+        let aspect = mean(|sample| sample.aspect);
+        if -range_rate >= self.charge_speed && aspect <= self.charge_aspect {
+            Pattern::Charger
+        } else if tangential >= self.orbit_speed
+            && range_rate.abs() <= self.orbit_ratio * tangential
+        {
+            Pattern::Orbiter
+        } else if aspect >= self.kite_aspect && range_rate >= -self.kite_closure {
+            Pattern::Kiter
+        } else {
+            Pattern::Unknown
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Opponents {
+    models: Vec<(u32, Classifier)>,
+}
+
+impl Opponents {
+    pub fn new() -> Opponents {
+        Opponents::default()
+    }
+
+    fn model(&mut self, id: u32) -> &mut Classifier {
+        let index = match self.models.iter().position(|(key, _)| *key == id) {
+            Some(index) => index,
+            None => {
+                self.models.push((id, Classifier::new()));
+                self.models.len() - 1
+            }
+        };
+        &mut self.models[index].1
+    }
+
+    pub fn observe(&mut self, id: u32, own: &impl Kinematic, enemy: &impl Kinematic, now: f64) {
+        self.model(id).observe(own, enemy, now);
+    }
+
+    pub fn launch(&mut self, id: u32, now: f64) {
+        self.model(id).launch(now);
+    }
+
+    pub fn pattern(&self, id: u32) -> Pattern {
+        self.models
+            .iter()
+            .find(|(key, _)| *key == id)
+            .map_or(Pattern::Unknown, |(_, model)| model.pattern())
+    }
+
+    pub fn retain(&mut self, mut live: impl FnMut(u32) -> bool) {
+        self.models.retain(|(id, _)| live(*id));
+    }
+}
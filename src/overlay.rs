@@ -0,0 +1,377 @@
+use crate::assignment::Claim;
+use crate::danger::DangerMap;
+use crate::draw::{DebugDraw, Layer, Layers, OortDraw};
+use crate::fleet::{Election, Role, Roster};
+use crate::movement::{Kinematic, Output};
+use crate::perception::{Beam, Track, TrackId};
+use oort_api::prelude::*;
+use std::collections::VecDeque;
+use std::f64::consts::{FRAC_PI_2, TAU};
+
+pub struct Overlay<D: DebugDraw = OortDraw> {
+    pub draw: D,
+    pub layers: Layers,
+    pub friendly: u32,
+    pub leader: u32,
+    pub shared: u32,
+    pub local: u32,
+    pub assignment: u32,
+    pub steering: u32,
+    pub radar: u32,
+    pub radar_heat: u32,
+    pub radar_range: f64,
+    pub path: u32,
+    pub slot: u32,
+    pub danger: u32,
+    pub palette: [u32; 6],
+    pub size: f64,
+}
+
+impl Default for Overlay {
+    fn default() -> Self {
+        Overlay::new(OortDraw)
+    }
+}
+
+impl<D: DebugDraw> Overlay<D> {
+    pub fn new(draw: D) -> Overlay<D> {
+        Overlay {
+            draw,
+            layers: Layers::default(),
+            friendly: 0x00ff00,
+            leader: 0x00ffff,
+            shared: 0xff8800,
+            local: 0xff0000,
+            assignment: 0xffff00,
+            steering: 0xff00ff,
+            radar: 0x404040,
+            radar_heat: 0x00aaff,
+            radar_range: 1e5,
+            path: 0x8888ff,
+            slot: 0x00ff88,
+            danger: 0xff2200,
+            palette: [0xff4444, 0x44ff44, 0x4488ff, 0xffaa00, 0x00ffff, 0xaa44ff],
+            size: 50.0,
+        }
+    }
+
+    pub fn role_label(role: Role) -> &'static str {
+        match role {
+            Role::Leader => "LDR",
+            Role::Wingman => "WNG",
+            Role::Striker => "STK",
+            Role::Screen => "SCR",
+            Role::Scout => "SCT",
+            Role::Rearguard => "RGD",
+        }
+    }
+
+    pub fn friendlies(
+        &self,
+        me: u16,
+        actor: &impl Kinematic,
+        election: &Election,
+        roster: &Roster,
+    ) {
+        let mut ships = vec![(me, actor.position())];
+        ships.extend(
+            roster
+                .friends()
+                .iter()
+                .map(|friend| (friend.ship, friend.position)),
+        );
+        let draw = self.layers.on(Layer::Friendlies, &self.draw);
+        for (ship, position) in ships {
+            let role = election.role_of(ship);
+            let color = if role == Role::Leader {
+                self.leader
+            } else {
+                self.friendly
+            };
+            draw.square(position, self.size, color);
+            draw.text(
+                position + vec2(self.size, self.size),
+                color,
+                &format!("{} {}", ship, Self::role_label(role)),
+            );
+        }
+    }
+
+    pub fn tracks(&self, tracks: &[Track], now: f64) {
+        let draw = self.layers.on(Layer::Tracks, &self.draw);
+        for track in tracks {
+            let color = if track.shared_by.is_some() {
+                self.shared
+            } else {
+                self.local
+            };
+            draw.diamond(track.position, self.size, color);
+            draw.line(track.position, track.position + track.velocity, color);
+            let source = match track.shared_by {
+                Some(sender) => format!("via {}", sender),
+                None => "own".to_string(),
+            };
+            let label = format!(
+                "#{} {:?} {:.0}m/s {} {:.1}s",
+                track.id,
+                track.class,
+                track.velocity.length(),
+                source,
+                track.staleness(now)
+            );
+            draw.text(track.position + vec2(self.size, -self.size), color, &label);
+        }
+    }
+
+    pub fn trails(&self, trails: &Trails) {
+        let draw = self.layers.on(Layer::Tracks, &self.draw);
+        for (_, trail) in &trails.history {
+            let count = trail.len();
+            for (i, point) in trail.iter().enumerate() {
+                let fade = (i + 1) as f64 / count as f64;
+                draw.square(*point, self.size * 0.2 * fade, self.local);
+            }
+        }
+    }
+
+    pub fn assignments(&self, me: u16, actor: &impl Kinematic, roster: &Roster, claims: &[Claim]) {
+        let draw = self.layers.on(Layer::Assignments, &self.draw);
+        for claim in claims {
+            let shooter = if claim.ship == me {
+                Some(actor.position())
+            } else {
+                roster.get(claim.ship).map(|friend| friend.position)
+            };
+            if let Some(shooter) = shooter {
+                draw.line(shooter, claim.position, self.assignment);
+            }
+        }
+    }
+
+    pub fn steering(&self, actor: &impl Kinematic, output: &Output) {
+        let draw = self.layers.on(Layer::Steering, &self.draw);
+        let position = actor.position();
+        draw.line(position, position + output.linear, self.steering);
+        draw.line(
+            position,
+            position + vec2(self.size * output.angular, 0.0).rotate(actor.heading() + FRAC_PI_2),
+            self.steering,
+        );
+    }
+
+    pub fn components(&self, actor: &impl Kinematic, components: &[(&str, Output)], sum: &Output) {
+        let draw = self.layers.on(Layer::Steering, &self.draw);
+        let position = actor.position();
+        for (i, (name, output)) in components.iter().enumerate() {
+            let color = self.palette[i % self.palette.len()];
+            let tip = position + output.linear;
+            draw.line(position, tip, color);
+            draw.text(tip, color, name);
+        }
+        draw.line(position, position + sum.linear, self.steering);
+        draw.text(position + sum.linear, self.steering, "sum");
+    }
+
+    pub fn beam(&self, origin: Vec2, beam: &Beam) {
+        let draw = self.layers.on(Layer::Radar, &self.draw);
+        let far = beam.max_distance.min(self.radar_range);
+        for edge in [-0.5, 0.5] {
+            let direction = vec2(1.0, 0.0).rotate(beam.heading + edge * beam.width);
+            draw.line(
+                origin + direction * beam.min_distance,
+                origin + direction * far,
+                self.radar,
+            );
+        }
+        for range in [beam.min_distance, far] {
+            if range <= 0.0 {
+                continue;
+            }
+            let steps = 8;
+            let mut previous = None;
+            for step in 0..=steps {
+                let angle = beam.heading + beam.width * (step as f64 / steps as f64 - 0.5);
+                let point = origin + vec2(range, 0.0).rotate(angle);
+                if let Some(previous) = previous {
+                    draw.line(previous, point, self.radar);
+                }
+                previous = Some(point);
+            }
+        }
+    }
+
+    pub fn coverage(&self, origin: Vec2, coverage: &Coverage, now: f64) {
+        let draw = self.layers.on(Layer::Radar, &self.draw);
+        let bins = coverage.scanned.len();
+        for (i, scanned) in coverage.scanned.iter().enumerate() {
+            let heat = coverage.heat(*scanned, now);
+            if heat <= 0.0 {
+                continue;
+            }
+            let angle = (i as f64 + 0.5) * TAU / bins as f64;
+            let direction = vec2(1.0, 0.0).rotate(angle);
+            draw.line(
+                origin + direction * self.size * 2.0,
+                origin + direction * (self.size * 2.0 + self.size * 4.0 * heat),
+                Self::fade(self.radar_heat, heat),
+            );
+        }
+    }
+
+    fn fade(color: u32, heat: f64) -> u32 {
+        let scale = |shift: u32| (((color >> shift) & 0xff) as f64 * heat.clamp(0.0, 1.0)) as u32;
+        scale(16) << 16 | scale(8) << 8 | scale(0)
+    }
+
+    pub fn danger(&self, map: &DangerMap) {
+        let draw = self.layers.on(Layer::Danger, &self.draw);
+        let peak = map.max();
+        if peak <= 0.0 {
+            return;
+        }
+        for y in 0..map.height {
+            for x in 0..map.width {
+                let heat = map.cell_risk(x, y) / peak;
+                if heat > 0.05 {
+                    draw.square(
+                        map.center(x, y),
+                        map.cell * 0.9 * heat,
+                        Self::fade(self.danger, heat),
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn path(&self, path: &[Vec2]) {
+        let draw = self.layers.on(Layer::Path, &self.draw);
+        for segment in path.windows(2) {
+            draw.line(segment[0], segment[1], self.path);
+        }
+        for point in path {
+            draw.square(*point, self.size * 0.25, self.path);
+        }
+    }
+
+    pub fn slots(&self, slots: &[Vec2]) {
+        let draw = self.layers.on(Layer::Formation, &self.draw);
+        for (rank, slot) in slots.iter().enumerate() {
+            draw.polygon(*slot, self.size * 0.5, 6, 0.0, self.slot);
+            draw.text(
+                *slot + vec2(self.size * 0.5, 0.0),
+                self.slot,
+                &rank.to_string(),
+            );
+        }
+    }
+
+    pub fn draw(
+        &self,
+        me: u16,
+        actor: &impl Kinematic,
+        election: &Election,
+        roster: &Roster,
+        tracks: &[Track],
+        claims: &[Claim],
+    ) {
+        self.friendlies(me, actor, election, roster);
+        self.tracks(tracks, current_time());
+        self.assignments(me, actor, roster, claims);
+    }
+}
+
+pub struct Trails {
+    pub length: usize,
+    pub interval: f64,
+    history: Vec<(TrackId, VecDeque<Vec2>)>,
+    last: f64,
+}
+
+impl Default for Trails {
+    fn default() -> Self {
+        Trails::new(10, 0.25)
+    }
+}
+
+impl Trails {
+    pub fn new(length: usize, interval: f64) -> Trails {
+        Trails {
+            length,
+            interval,
+            history: Vec::new(),
+            last: f64::NEG_INFINITY,
+        }
+    }
+
+    pub fn get(&self, id: TrackId) -> Option<&VecDeque<Vec2>> {
+        self.history
+            .iter()
+            .find(|(track, _)| *track == id)
+            .map(|(_, trail)| trail)
+    }
+
+    pub fn record(&mut self, tracks: &[Track], now: f64) {
+        self.history
+            .retain(|(id, _)| tracks.iter().any(|track| track.id == *id));
+        if now - self.last < self.interval {
+            return;
+        }
+        self.last = now;
+        for track in tracks {
+            let index = match self.history.iter().position(|(id, _)| *id == track.id) {
+                Some(index) => index,
+                None => {
+                    self.history.push((track.id, VecDeque::new()));
+                    self.history.len() - 1
+                }
+            };
+            let trail = &mut self.history[index].1;
+            trail.push_back(track.position);
+            while trail.len() > self.length {
+                trail.pop_front();
+            }
+        }
+    }
+}
+
+pub struct Coverage {
+    pub fade: f64,
+    scanned: Vec<f64>,
+}
+
+impl Default for Coverage {
+    fn default() -> Self {
+        Coverage::new(36, 2.0)
+    }
+}
+
+impl Coverage {
+    pub fn new(bins: usize, fade: f64) -> Coverage {
+        Coverage {
+            fade,
+            scanned: vec![f64::NEG_INFINITY; bins.max(1)],
+        }
+    }
+
+    pub fn heat(&self, scanned: f64, now: f64) -> f64 {
+        (1.0 - (now - scanned) / self.fade).clamp(0.0, 1.0)
+    }
+
+    pub fn gaps(&self, now: f64) -> usize {
+        self.scanned
+            .iter()
+            .filter(|scanned| self.heat(**scanned, now) <= 0.0)
+            .count()
+    }
+
+    pub fn record(&mut self, beam: &Beam, now: f64) {
+        let bins = self.scanned.len();
+        let width = TAU / bins as f64;
+        for (i, scanned) in self.scanned.iter_mut().enumerate() {
+            let center = (i as f64 + 0.5) * width;
+            if angle_diff(beam.heading, center).abs() <= (beam.width + width) * 0.5 {
+                *scanned = now;
+            }
+        }
+    }
+}
@@ -0,0 +1,585 @@
+use crate::contact::{Class, ContactReport, ScanResult};
+use crate::filter::{NoiseModel, VecKalman};
+use crate::math::*;
+use crate::movement::Kinematic;
+use crate::storage::{FixedVec, Scratch};
+use core::f64::consts::TAU;
+
+pub type TrackId = u32;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Track {
+    pub id: TrackId,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serial::class"))]
+    pub class: Class,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serial::vec2"))]
+    pub position: Vec2,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serial::vec2"))]
+    pub velocity: Vec2,
+    pub updated: f64,
+    pub age: f64,
+    pub hits: u32,
+    pub shared_by: Option<u16>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub filter: VecKalman,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub suspect: Option<Suspect>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Suspect {
+    pub born: f64,
+    pub velocity: Vec2,
+}
+
+impl Track {
+    pub fn is_munition(&self) -> bool {
+        matches!(self.class, Class::Missile | Class::Torpedo)
+    }
+    pub fn is_hull(&self) -> bool {
+        !self.is_munition() && self.class != Class::Asteroid
+    }
+    pub fn is_decoy(&self) -> bool {
+        self.suspect.is_some()
+    }
+    pub fn is_target(&self) -> bool {
+        self.is_hull() && !self.is_decoy()
+    }
+    pub fn staleness(&self, now: f64) -> f64 {
+        self.age + (now - self.updated)
+    }
+    pub fn uncertainty(&self, now: f64) -> f64 {
+        let p = self.filter.covariance();
+        let dt = self.staleness(now);
+        (p[0][0] + 2.0 * dt * p[0][1] + dt * dt * p[1][1])
+            .max(0.0)
+            .sqrt()
+    }
+}
+
+impl Kinematic for Track {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.velocity.angle()
+    }
+    fn angular_velocity(&self) -> f64 {
+        0.0
+    }
+}
+
+impl Track {
+    pub fn predict(&self, now: f64) -> Track {
+        Track {
+            position: self.position + self.velocity * (now - self.updated),
+            updated: now,
+            age: self.staleness(now),
+            ..self.clone()
+        }
+    }
+}
+
+pub const TRACK_CAPACITY: usize = 64;
+
+pub struct Tracker<const N: usize = TRACK_CAPACITY> {
+    pub gate: f64,
+    pub timeout: f64,
+    pub decoy_radius: f64,
+    pub decoy_acceleration: f64,
+    pub decoy_window: f64,
+    pub decoy_lifetime: f64,
+    pub filter: VecKalman,
+    pub noise: NoiseModel,
+    tracks: FixedVec<Track, N>,
+    evicted: FixedVec<TrackId, N>,
+    next_id: TrackId,
+}
+
+impl<const N: usize> Default for Tracker<N> {
+    fn default() -> Self {
+        Self::bounded()
+    }
+}
+
+impl Tracker {
+    pub fn new() -> Tracker {
+        Tracker::bounded()
+    }
+}
+
+impl<const N: usize> Tracker<N> {
+    pub fn bounded() -> Tracker<N> {
+        let empty = Track {
+            id: 0,
+            class: Class::Unknown,
+            position: vec2(0.0, 0.0),
+            velocity: vec2(0.0, 0.0),
+            updated: f64::NEG_INFINITY,
+            age: 0.0,
+            hits: 0,
+            shared_by: None,
+            filter: Default::default(),
+            suspect: None,
+        };
+        Tracker {
+            gate: 200.0,
+            timeout: 3.0,
+            decoy_radius: 150.0,
+            decoy_acceleration: 20.0,
+            decoy_window: 1.0,
+            decoy_lifetime: 10.0,
+            filter: VecKalman::default(),
+            noise: NoiseModel::default(),
+            tracks: FixedVec::new(empty),
+            evicted: FixedVec::new(0),
+            next_id: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    fn associate(&self, class: Class, position: Vec2, now: f64) -> Option<usize> {
+        self.tracks
+            .iter()
+            .enumerate()
+            .filter(|(_, track)| track.class == class)
+            .map(|(i, track)| (i, sq_length(track.predict(now).position - position)))
+            .filter(|(_, distance)| *distance < self.gate * self.gate)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    fn create(
+        &mut self,
+        class: Class,
+        filter: VecKalman,
+        now: f64,
+        age: f64,
+        shared_by: Option<u16>,
+    ) -> TrackId {
+        let (position, velocity) = (filter.position, filter.velocity);
+        let id = self.next_id;
+        self.next_id += 1;
+        let suspect = self.suspicious(class, position, now).then_some(Suspect {
+            born: now,
+            velocity,
+        });
+        if self.tracks.is_full() {
+            let stalest = self
+                .tracks
+                .iter()
+                .enumerate()
+                .min_by(|a, b| a.1.updated.total_cmp(&b.1.updated))
+                .map(|(i, track)| (i, track.id));
+            if let Some((i, stale)) = stalest {
+                self.tracks.swap_remove(i);
+                let _ = self.evicted.push(stale);
+            }
+        }
+        let _ = self.tracks.push(Track {
+            id,
+            class,
+            position,
+            velocity,
+            updated: now,
+            age,
+            hits: 1,
+            shared_by,
+            filter,
+            suspect,
+        });
+        id
+    }
+
+    fn suspicious(&self, class: Class, position: Vec2, now: f64) -> bool {
+        !matches!(class, Class::Asteroid | Class::Missile | Class::Torpedo)
+            && self.tracks.iter().any(|track| {
+                track.class != Class::Asteroid
+                    && !track.is_decoy()
+                    && sq_length(track.predict(now).position - position)
+                        < self.decoy_radius * self.decoy_radius
+            })
+    }
+
+    fn vindicated(&self, track: &Track, now: f64) -> bool {
+        let Some(suspect) = track.suspect else {
+            return false;
+        };
+        let elapsed = now - suspect.born;
+        let accelerated = elapsed >= self.decoy_window
+            && (track.velocity - suspect.velocity).length() > self.decoy_acceleration * elapsed;
+        accelerated || elapsed > self.decoy_lifetime
+    }
+
+    pub fn valid(position: Vec2, velocity: Vec2, now: f64) -> bool {
+        [position.x, position.y, velocity.x, velocity.y, now]
+            .iter()
+            .all(|value| value.is_finite())
+    }
+
+    pub fn update(&mut self, contact: &ScanResult, now: f64) -> Option<TrackId> {
+        self.observe(contact, 0.0, now)
+    }
+
+    pub fn update_from(&mut self, origin: Vec2, contact: &ScanResult, now: f64) -> Option<TrackId> {
+        self.observe(contact, origin.distance(contact.position), now)
+    }
+
+    fn observe(&mut self, contact: &ScanResult, range: f64, now: f64) -> Option<TrackId> {
+        if !Self::valid(contact.position, contact.velocity, now) {
+            return None;
+        }
+        let Some(i) = self.associate(contact.class, contact.position, now) else {
+            let filter = self.seed(contact.position, contact.velocity, range);
+            return Some(self.create(contact.class, filter, now, 0.0, None));
+        };
+        Some(self.refresh(i, contact, range, now))
+    }
+
+    fn seed(&self, position: Vec2, velocity: Vec2, range: f64) -> VecKalman {
+        let mut filter = self.filter;
+        self.noise.apply(&mut filter, range);
+        filter.reset(position, velocity);
+        filter
+    }
+
+    fn refresh(&mut self, i: usize, contact: &ScanResult, range: f64, now: f64) -> TrackId {
+        let track = &mut self.tracks[i];
+        self.noise.apply(&mut track.filter, range);
+        track.filter.predict(now - track.updated);
+        let verdict = track.filter.update(contact.position, contact.velocity);
+        track.position = track.filter.position;
+        track.velocity = track.filter.velocity;
+        track.age = if verdict.applied() {
+            track.hits += 1;
+            track.shared_by = None;
+            0.0
+        } else {
+            track.staleness(now)
+        };
+        track.updated = now;
+        if self.vindicated(&self.tracks[i], now) {
+            self.tracks[i].suspect = None;
+        }
+        self.tracks[i].id
+    }
+
+    pub fn update_all(
+        &mut self,
+        origin: Vec2,
+        contacts: &[ScanResult],
+        now: f64,
+        scratch: &mut Scratch,
+    ) -> usize {
+        let rows = contacts.len();
+        let columns = self.tracks.len();
+        let gate = self.gate * self.gate;
+        let costs = scratch.scalars.alloc(rows * columns, f64::INFINITY);
+        for (row, contact) in contacts.iter().enumerate() {
+            if !Self::valid(contact.position, contact.velocity, now) {
+                continue;
+            }
+            for (column, track) in self.tracks.iter().enumerate() {
+                let distance = sq_length(track.predict(now).position - contact.position);
+                if track.class == contact.class && distance < gate {
+                    costs[row * columns + column] = distance;
+                }
+            }
+        }
+        let matched = scratch.flags.alloc(rows + columns, false);
+        while let Some(best) = (0..rows * columns)
+            .filter(|&cell| costs[cell].is_finite())
+            .filter(|&cell| !matched[cell / columns] && !matched[rows + cell % columns])
+            .min_by(|&a, &b| costs[a].total_cmp(&costs[b]))
+        {
+            let (row, column) = (best / columns, best % columns);
+            matched[row] = true;
+            matched[rows + column] = true;
+            let contact = &contacts[row];
+            self.refresh(column, contact, origin.distance(contact.position), now);
+        }
+        let mut created = 0;
+        for (row, contact) in contacts.iter().enumerate() {
+            if !matched[row] && Self::valid(contact.position, contact.velocity, now) {
+                let range = origin.distance(contact.position);
+                let filter = self.seed(contact.position, contact.velocity, range);
+                self.create(contact.class, filter, now, 0.0, None);
+                created += 1;
+            }
+        }
+        created
+    }
+
+    pub fn fuse(
+        &mut self,
+        report: &ContactReport,
+        sender: u16,
+        latency: f64,
+        now: f64,
+    ) -> Option<TrackId> {
+        if !Self::valid(report.position, report.velocity, now) || !latency.is_finite() {
+            return None;
+        }
+        let latency = latency.max(0.0);
+        let measured = now - latency;
+        let Some(i) = self.associate(report.class, report.position, measured) else {
+            let position = report.position + report.velocity * latency;
+            let filter = self.seed(position, report.velocity, 0.0);
+            return Some(self.create(report.class, filter, now, latency, Some(sender)));
+        };
+        let track = &mut self.tracks[i];
+        if track.updated >= measured {
+            return Some(track.id);
+        }
+        self.noise.apply(&mut track.filter, 0.0);
+        track.filter.predict(measured - track.updated);
+        let verdict = track.filter.update(report.position, report.velocity);
+        track.position = track.filter.position;
+        track.velocity = track.filter.velocity;
+        track.age = if verdict.applied() {
+            track.hits += 1;
+            track.shared_by = Some(sender);
+            0.0
+        } else {
+            track.staleness(measured)
+        };
+        track.updated = measured;
+        Some(track.id)
+    }
+
+    pub fn prune(&mut self, now: f64) -> FixedVec<TrackId, N> {
+        let timeout = self.timeout;
+        let mut dropped = core::mem::replace(&mut self.evicted, FixedVec::new(0));
+        self.tracks.retain(|track| {
+            let keep = now - track.updated <= timeout;
+            if !keep {
+                let _ = dropped.push(track.id);
+            }
+            keep
+        });
+        dropped
+    }
+
+    pub fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    pub fn get(&self, id: TrackId) -> Option<&Track> {
+        self.tracks.iter().find(|track| track.id == id)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Beam {
+    pub heading: f64,
+    pub width: f64,
+    pub min_distance: f64,
+    pub max_distance: f64,
+}
+
+#[cfg(feature = "oort")]
+impl Beam {
+    pub fn apply(&self) {
+        use oort_api::prelude::*;
+        set_radar_heading(self.heading);
+        set_radar_width(self.width);
+        set_radar_min_distance(self.min_distance);
+        set_radar_max_distance(self.max_distance);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Sector {
+    pub start: f64,
+    pub width: f64,
+}
+
+impl Sector {
+    pub fn full() -> Sector {
+        Sector {
+            start: 0.0,
+            width: TAU,
+        }
+    }
+
+    pub fn center(&self) -> f64 {
+        self.start + self.width / 2.0
+    }
+
+    pub fn contains(&self, bearing: f64) -> bool {
+        (bearing - self.start).rem_euclid(TAU) <= self.width
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Strobe {
+    pub origin: Vec2,
+    pub bearing: f64,
+    pub time: f64,
+}
+
+pub fn triangulate(strobes: &[Strobe], min_spread: f64) -> Option<Vec2> {
+    let (mut a, mut b, mut c) = (0.0, 0.0, 0.0);
+    let mut rhs = vec2(0.0, 0.0);
+    for strobe in strobes {
+        let (sin, cos) = strobe.bearing.sin_cos();
+        let (xx, xy, yy) = (1.0 - cos * cos, -cos * sin, 1.0 - sin * sin);
+        a += xx;
+        b += xy;
+        c += yy;
+        rhs += vec2(
+            xx * strobe.origin.x + xy * strobe.origin.y,
+            xy * strobe.origin.x + yy * strobe.origin.y,
+        );
+    }
+    let determinant = a * c - b * b;
+    let spread = min_spread.sin_cos().0;
+    if strobes.len() < 2 || determinant <= spread * spread {
+        return None;
+    }
+    Some(vec2(
+        (c * rhs.x - b * rhs.y) / determinant,
+        (a * rhs.y - b * rhs.x) / determinant,
+    ))
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Motion {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub time: f64,
+}
+
+impl Kinematic for Motion {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.velocity.angle()
+    }
+    fn angular_velocity(&self) -> f64 {
+        0.0
+    }
+}
+
+pub fn motion_analysis(strobes: &[Strobe], min_conditioning: f64) -> Option<Motion> {
+    let time = strobes.iter().map(|strobe| strobe.time).reduce(f64::max)?;
+    let mut normal = [[0.0; 5]; 4];
+    for strobe in strobes {
+        let (sin, cos) = strobe.bearing.sin_cos();
+        let tau = strobe.time - time;
+        let row = [sin, -cos, sin * tau, -cos * tau];
+        let rhs = sin * strobe.origin.x - cos * strobe.origin.y;
+        for i in 0..4 {
+            for j in 0..4 {
+                normal[i][j] += row[i] * row[j];
+            }
+            normal[i][4] += row[i] * rhs;
+        }
+    }
+    let [x, y, vx, vy] = solve4(normal, min_conditioning)?;
+    let motion = Motion {
+        position: vec2(x, y),
+        velocity: vec2(vx, vy),
+        time,
+    };
+    let last = strobes.iter().find(|strobe| strobe.time == time)?;
+    let (sin, cos) = last.bearing.sin_cos();
+    let ahead = (motion.position - last.origin).dot(vec2(cos, sin));
+    (ahead > 0.0).then_some(motion)
+}
+
+fn solve4(mut m: [[f64; 5]; 4], min_conditioning: f64) -> Option<[f64; 4]> {
+    let scale = (0..4).map(|i| m[i][i].abs()).fold(0.0, f64::max);
+    for column in 0..4 {
+        let pivot =
+            (column..4).max_by(|&a, &b| m[a][column].abs().total_cmp(&m[b][column].abs()))?;
+        m.swap(column, pivot);
+        let magnitude = m[column][column].abs();
+        if magnitude.is_nan() || magnitude <= scale * min_conditioning {
+            return None;
+        }
+        for row in column + 1..4 {
+            let factor = m[row][column] / m[column][column];
+            let source = m[column];
+            for (value, source) in m[row].iter_mut().zip(source).skip(column) {
+                *value -= factor * source;
+            }
+        }
+    }
+    let mut x = [0.0; 4];
+    for row in (0..4).rev() {
+        let known: f64 = (row + 1..4).map(|k| m[row][k] * x[k]).sum();
+        x[row] = (m[row][4] - known) / m[row][row];
+    }
+    x.iter().all(|value| value.is_finite()).then_some(x)
+}
+
+pub struct RadarScheduler {
+    pub sector: Sector,
+    pub search_width: f64,
+    pub track_width: f64,
+    pub range: f64,
+    pub range_gate: f64,
+    pub revisit: f64,
+    search_heading: f64,
+}
+
+impl Default for RadarScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RadarScheduler {
+    pub fn new() -> RadarScheduler {
+        RadarScheduler {
+            sector: Sector::full(),
+            search_width: TAU / 16.0,
+            track_width: TAU / 120.0,
+            range: 1e5,
+            range_gate: 500.0,
+            revisit: 0.5,
+            search_heading: 0.0,
+        }
+    }
+
+    pub fn next(&mut self, origin: Vec2, tracks: &[Track], now: f64) -> Beam {
+        let stalest = tracks
+            .iter()
+            .filter(|track| now - track.updated >= self.revisit)
+            .max_by(|a, b| (now - a.updated).total_cmp(&(now - b.updated)));
+        if let Some(track) = stalest {
+            let offset = track.predict(now).position - origin;
+            let distance = offset.length();
+            return Beam {
+                heading: offset.angle(),
+                width: self.track_width,
+                min_distance: (distance - self.range_gate).max(0.0),
+                max_distance: distance + self.range_gate,
+            };
+        }
+        let mut offset =
+            (self.search_heading + self.search_width - self.sector.start).rem_euclid(TAU);
+        if offset > self.sector.width {
+            offset = self.search_width.min(self.sector.width) / 2.0;
+        }
+        self.search_heading = self.sector.start + offset;
+        Beam {
+            heading: self.search_heading,
+            width: self.search_width,
+            min_distance: 0.0,
+            max_distance: self.range,
+        }
+    }
+}
@@ -0,0 +1,1072 @@
+#[cfg(feature = "oort")]
+use crate::abilities::{Abilities, Spec};
+#[cfg(feature = "oort")]
+use crate::avoidance::{Avoid, Neighbor, VelocityObstacle};
+use crate::contact::Class;
+use crate::control::{Attitude, Thrusters};
+use crate::math::*;
+use crate::movement::*;
+use crate::perception::Track;
+use crate::sim::Body;
+use crate::storage::Scratch;
+use alloc::collections::BinaryHeap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::f64::consts::{PI, SQRT_2, TAU};
+#[cfg(feature = "oort")]
+use oort_api::prelude::{world_size, Ability};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Obstacle {
+    pub position: Vec2,
+    pub radius: f64,
+}
+
+impl Obstacle {
+    pub fn from_tracks(tracks: &[Track], radius: f64) -> Vec<Obstacle> {
+        tracks
+            .iter()
+            .filter(|track| track.class == Class::Asteroid)
+            .map(|track| Obstacle {
+                position: track.position,
+                radius,
+            })
+            .collect()
+    }
+}
+
+pub struct Grid {
+    pub origin: Vec2,
+    pub cell: f64,
+    pub width: usize,
+    pub height: usize,
+    blocked: Vec<bool>,
+}
+
+impl Grid {
+    pub fn new(center: Vec2, extent: f64, cell: f64) -> Grid {
+        let size = ((2.0 * extent / cell).ceil() as usize).max(1);
+        Grid {
+            origin: center - vec2(extent, extent),
+            cell,
+            width: size,
+            height: size,
+            blocked: vec![false; size * size],
+        }
+    }
+
+    pub fn build(
+        center: Vec2,
+        extent: f64,
+        cell: f64,
+        obstacles: &[Obstacle],
+        clearance: f64,
+    ) -> Grid {
+        let mut grid = Grid::new(center, extent, cell);
+        for obstacle in obstacles {
+            grid.block(obstacle.position, obstacle.radius + clearance);
+        }
+        grid
+    }
+
+    pub fn block(&mut self, position: Vec2, radius: f64) {
+        let reach = (radius / self.cell).ceil() as i64 + 1;
+        let Some((cx, cy)) = self.cell_of(position) else {
+            return;
+        };
+        for dy in -reach..=reach {
+            for dx in -reach..=reach {
+                let (x, y) = (cx as i64 + dx, cy as i64 + dy);
+                if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+                    continue;
+                }
+                let (x, y) = (x as usize, y as usize);
+                if self.center(x, y).distance(position) <= radius + self.cell * 0.5 {
+                    self.blocked[y * self.width + x] = true;
+                }
+            }
+        }
+    }
+
+    pub fn cell_of(&self, point: Vec2) -> Option<(usize, usize)> {
+        let local = (point - self.origin) / self.cell;
+        if local.x < 0.0 || local.y < 0.0 {
+            return None;
+        }
+        let (x, y) = (local.x as usize, local.y as usize);
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    pub fn center(&self, x: usize, y: usize) -> Vec2 {
+        self.origin + vec2(x as f64 + 0.5, y as f64 + 0.5) * self.cell
+    }
+
+    pub fn blocked(&self, x: usize, y: usize) -> bool {
+        self.blocked[y * self.width + x]
+    }
+
+    fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize, f64)> + '_ {
+        const STEPS: [(i64, i64); 8] = [
+            (1, 0),
+            (-1, 0),
+            (0, 1),
+            (0, -1),
+            (1, 1),
+            (1, -1),
+            (-1, 1),
+            (-1, -1),
+        ];
+        STEPS.into_iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            if nx < 0 || ny < 0 || nx >= self.width as i64 || ny >= self.height as i64 {
+                return None;
+            }
+            let (nx, ny) = (nx as usize, ny as usize);
+            if self.blocked(nx, ny) {
+                return None;
+            }
+            if dx != 0 && dy != 0 && (self.blocked(nx, y) || self.blocked(x, ny)) {
+                return None;
+            }
+            let cost = if dx != 0 && dy != 0 { SQRT_2 } else { 1.0 };
+            Some((nx, ny, cost))
+        })
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Open {
+    estimate: f64,
+    cell: usize,
+}
+
+impl PartialEq for Open {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl Eq for Open {}
+
+impl PartialOrd for Open {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Open {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.total_cmp(&self.estimate)
+    }
+}
+
+fn octile(a: (usize, usize), b: (usize, usize)) -> f64 {
+    let dx = a.0.abs_diff(b.0) as f64;
+    let dy = a.1.abs_diff(b.1) as f64;
+    dx.max(dy) + (SQRT_2 - 1.0) * dx.min(dy)
+}
+
+pub struct AStar {
+    pub max_expansions: usize,
+}
+
+impl Default for AStar {
+    fn default() -> Self {
+        AStar {
+            max_expansions: 4_000,
+        }
+    }
+}
+
+impl AStar {
+    pub fn plan(&self, grid: &Grid, start: Vec2, goal: Vec2) -> Option<Vec<Vec2>> {
+        let start_cell = grid.cell_of(start)?;
+        let goal_cell = grid.cell_of(goal)?;
+        if grid.blocked(goal_cell.0, goal_cell.1) {
+            return None;
+        }
+        let index = |(x, y): (usize, usize)| y * grid.width + x;
+        let mut cost = vec![f64::INFINITY; grid.width * grid.height];
+        let mut parent = vec![usize::MAX; grid.width * grid.height];
+        let mut open = BinaryHeap::new();
+        cost[index(start_cell)] = 0.0;
+        open.push(Open {
+            estimate: octile(start_cell, goal_cell),
+            cell: index(start_cell),
+        });
+        let mut expansions = 0;
+        while let Some(Open { cell, .. }) = open.pop() {
+            let here = (cell % grid.width, cell / grid.width);
+            if here == goal_cell {
+                return Some(self.path(grid, &parent, cell, start, goal));
+            }
+            expansions += 1;
+            if expansions > self.max_expansions {
+                return None;
+            }
+            for (x, y, step) in grid.neighbors(here.0, here.1) {
+                let next = index((x, y));
+                let candidate = cost[cell] + step;
+                if candidate < cost[next] {
+                    cost[next] = candidate;
+                    parent[next] = cell;
+                    open.push(Open {
+                        estimate: candidate + octile((x, y), goal_cell),
+                        cell: next,
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    fn path(
+        &self,
+        grid: &Grid,
+        parent: &[usize],
+        goal_cell: usize,
+        start: Vec2,
+        goal: Vec2,
+    ) -> Vec<Vec2> {
+        let mut cells = vec![goal_cell];
+        let mut cell = goal_cell;
+        while parent[cell] != usize::MAX {
+            cell = parent[cell];
+            cells.push(cell);
+        }
+        cells.reverse();
+        let mut points: Vec<Vec2> = cells
+            .iter()
+            .map(|cell| grid.center(cell % grid.width, cell / grid.width))
+            .collect();
+        if let Some(first) = points.first_mut() {
+            *first = start;
+        }
+        if let Some(last) = points.last_mut() {
+            *last = goal;
+        }
+        simplify(&points)
+    }
+}
+
+pub fn simplify(points: &[Vec2]) -> Vec<Vec2> {
+    let mut path: Vec<Vec2> = Vec::with_capacity(points.len());
+    for (i, point) in points.iter().enumerate() {
+        if let (Some(&previous), Some(&next)) = (path.last(), points.get(i + 1)) {
+            let a = *point - previous;
+            let b = next - *point;
+            if cross(a, b).abs() < 1e-6 * a.length() * b.length() && a.dot(b) > 0.0 {
+                continue;
+            }
+        }
+        path.push(*point);
+    }
+    path
+}
+
+pub struct FollowPath<'a> {
+    pub path: &'a [Vec2],
+    pub radius: f64,
+}
+
+impl FollowPath<'_> {
+    pub fn current(&self, position: Vec2) -> Option<usize> {
+        if self.path.len() < 2 {
+            return (!self.path.is_empty()).then_some(0);
+        }
+        let leg = |i: usize| segment_distance(self.path[i], self.path[i + 1], position);
+        let closest = (0..self.path.len() - 1)
+            .rev()
+            .min_by(|a, b| leg(*a).total_cmp(&leg(*b)))?;
+        let next = closest + 1;
+        if self.path[next].distance(position) < self.radius && next + 1 < self.path.len() {
+            Some(next + 1)
+        } else {
+            Some(next)
+        }
+    }
+}
+
+impl Move for FollowPath<'_> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let Some(index) = self.current(actor.position()) else {
+            return Output {
+                linear: vec2(0.0, 0.0),
+                angular: 0.0,
+            };
+        };
+        Seek {
+            target: self.path[index],
+        }
+        .execute(actor)
+    }
+}
+
+pub fn segment_distance(a: Vec2, b: Vec2, point: Vec2) -> f64 {
+    let segment = b - a;
+    let length_squared = segment.dot(segment);
+    if length_squared <= 0.0 {
+        return point.distance(a);
+    }
+    let t = ((point - a).dot(segment) / length_squared).clamp(0.0, 1.0);
+    point.distance(a + segment * t)
+}
+
+pub fn segment_clear(a: Vec2, b: Vec2, obstacles: &[Obstacle], clearance: f64) -> bool {
+    obstacles
+        .iter()
+        .all(|obstacle| segment_distance(a, b, obstacle.position) >= obstacle.radius + clearance)
+}
+
+pub struct VisibilityGraph {
+    pub clearance: f64,
+    pub sides: usize,
+}
+
+impl Default for VisibilityGraph {
+    fn default() -> Self {
+        VisibilityGraph {
+            clearance: 100.0,
+            sides: 8,
+        }
+    }
+}
+
+impl VisibilityGraph {
+    fn corners<'a>(&'a self, obstacles: &'a [Obstacle]) -> impl Iterator<Item = Vec2> + 'a {
+        let sides = self.sides.max(3);
+        let step = TAU / sides as f64;
+        obstacles
+            .iter()
+            .flat_map(move |obstacle| {
+                let radius = (obstacle.radius + self.clearance) / (step / 2.0).sin_cos().1 * 1.01;
+                (0..sides).map(move |side| {
+                    obstacle.position + vec2(radius, 0.0).rotate(step * side as f64)
+                })
+            })
+            .filter(move |node| {
+                !obstacles
+                    .iter()
+                    .any(|other| other.position.distance(*node) < other.radius + self.clearance)
+            })
+    }
+
+    pub fn nodes(&self, start: Vec2, goal: Vec2, obstacles: &[Obstacle]) -> Vec<Vec2> {
+        [start, goal]
+            .into_iter()
+            .chain(self.corners(obstacles))
+            .collect()
+    }
+
+    pub fn plan(&self, start: Vec2, goal: Vec2, obstacles: &[Obstacle]) -> Option<Vec<Vec2>> {
+        self.plan_in(&mut Scratch::default(), start, goal, obstacles)
+    }
+
+    pub fn plan_in(
+        &self,
+        scratch: &mut Scratch,
+        start: Vec2,
+        goal: Vec2,
+        obstacles: &[Obstacle],
+    ) -> Option<Vec<Vec2>> {
+        if segment_clear(start, goal, obstacles, self.clearance) {
+            return Some(vec![start, goal]);
+        }
+        let nodes = scratch
+            .vectors
+            .extend([start, goal].into_iter().chain(self.corners(obstacles)));
+        let cost = scratch.scalars.alloc(nodes.len(), f64::INFINITY);
+        let parent = scratch.indices.alloc(nodes.len(), usize::MAX);
+        let closed = scratch.flags.alloc(nodes.len(), false);
+        let mut open = BinaryHeap::new();
+        cost[0] = 0.0;
+        open.push(Open {
+            estimate: start.distance(goal),
+            cell: 0,
+        });
+        while let Some(Open { cell, .. }) = open.pop() {
+            if closed[cell] {
+                continue;
+            }
+            closed[cell] = true;
+            if cell == 1 {
+                let mut path = vec![goal];
+                let mut node = cell;
+                while parent[node] != usize::MAX {
+                    node = parent[node];
+                    path.push(nodes[node]);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in 0..nodes.len() {
+                if closed[next]
+                    || !segment_clear(nodes[cell], nodes[next], obstacles, self.clearance)
+                {
+                    continue;
+                }
+                let candidate = cost[cell] + nodes[cell].distance(nodes[next]);
+                if candidate < cost[next] {
+                    cost[next] = candidate;
+                    parent[next] = cell;
+                    open.push(Open {
+                        estimate: candidate + nodes[next].distance(goal),
+                        cell: next,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "oort")]
+pub struct Belt {
+    pub planner: VisibilityGraph,
+    pub avoidance: VelocityObstacle,
+    pub rock_radius: f64,
+    pub hazard_range: f64,
+    pub arrival_radius: f64,
+    goal: Option<Vec2>,
+    rocks: usize,
+    path: Vec<Vec2>,
+    scratch: Scratch,
+}
+
+#[cfg(feature = "oort")]
+impl Default for Belt {
+    fn default() -> Self {
+        Belt {
+            planner: VisibilityGraph::default(),
+            avoidance: VelocityObstacle::default(),
+            rock_radius: 150.0,
+            hazard_range: 3_000.0,
+            arrival_radius: 200.0,
+            goal: None,
+            rocks: 0,
+            path: Vec::new(),
+            scratch: Scratch::new(),
+        }
+    }
+}
+
+#[cfg(feature = "oort")]
+impl Belt {
+    pub fn path(&self) -> &[Vec2] {
+        &self.path
+    }
+
+    fn stale(&self, position: Vec2, goal: Vec2, obstacles: &[Obstacle]) -> bool {
+        let moved = self
+            .goal
+            .is_none_or(|previous| previous.distance(goal) > self.arrival_radius);
+        let blocked = self
+            .path
+            .windows(2)
+            .any(|leg| !segment_clear(leg[0], leg[1], obstacles, 0.0));
+        let lost = self
+            .path
+            .first()
+            .is_none_or(|start| start.distance(position) > self.hazard_range);
+        moved || blocked || lost || obstacles.len() > self.rocks
+    }
+
+    pub fn replan(&mut self, position: Vec2, goal: Vec2, obstacles: &[Obstacle]) {
+        self.goal = Some(goal);
+        self.rocks = obstacles.len();
+        self.scratch.reset();
+        self.path = self
+            .planner
+            .plan_in(&mut self.scratch, position, goal, obstacles)
+            .unwrap_or_else(|| vec![position, goal]);
+    }
+
+    pub fn update(
+        &mut self,
+        actor: &(impl Kinematic + Motor),
+        tracks: &[Track],
+        goal: Vec2,
+        now: f64,
+    ) -> Output {
+        let position = actor.position();
+        let obstacles = Obstacle::from_tracks(tracks, self.rock_radius);
+        if self.stale(position, goal, &obstacles) {
+            self.replan(position, goal, &obstacles);
+        }
+        let hazards: Vec<Neighbor> = tracks
+            .iter()
+            .filter(|track| track.class == Class::Asteroid)
+            .filter(|track| track.position.distance(position) < self.hazard_range)
+            .map(|track| Neighbor::hazard(track, self.rock_radius, now))
+            .collect();
+        Avoid {
+            inner: FollowPath {
+                path: &self.path,
+                radius: self.arrival_radius,
+            },
+            obstacle: &self.avoidance,
+            hazards: &hazards,
+        }
+        .execute(actor)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Predicted {
+    pub tick: u32,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub heading: f64,
+    pub angular_velocity: f64,
+}
+
+impl Predicted {
+    pub fn of(actor: &impl Kinematic) -> Predicted {
+        Predicted {
+            tick: 0,
+            position: actor.position(),
+            velocity: actor.velocity(),
+            heading: actor.heading(),
+            angular_velocity: actor.angular_velocity(),
+        }
+    }
+
+    pub fn step(
+        &self,
+        thrusters: &Thrusters,
+        max_angular_acceleration: f64,
+        control: &Output,
+    ) -> Predicted {
+        let body = control.linear.rotate(-self.heading);
+        let linear = control.linear * thrusters.limit(body);
+        let angular = control
+            .angular
+            .clamp(-max_angular_acceleration, max_angular_acceleration);
+        let velocity = self.velocity + linear * TICK_LENGTH;
+        let angular_velocity = self.angular_velocity + angular * TICK_LENGTH;
+        Predicted {
+            tick: self.tick + 1,
+            position: self.position + velocity * TICK_LENGTH,
+            velocity,
+            heading: (self.heading + angular_velocity * TICK_LENGTH).rem_euclid(TAU),
+            angular_velocity,
+        }
+    }
+}
+
+impl Kinematic for Predicted {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.heading
+    }
+    fn angular_velocity(&self) -> f64 {
+        self.angular_velocity
+    }
+}
+
+pub fn predict<'a>(
+    start: &impl Kinematic,
+    thrusters: &Thrusters,
+    max_angular_acceleration: f64,
+    controls: impl IntoIterator<Item = &'a Output>,
+) -> Vec<Predicted> {
+    let mut state = Predicted::of(start);
+    let mut states = vec![state];
+    for control in controls {
+        state = state.step(thrusters, max_angular_acceleration, control);
+        states.push(state);
+    }
+    states
+}
+
+pub fn stops_within(states: &[Predicted], center: Vec2, radius: f64, speed: f64) -> bool {
+    states
+        .iter()
+        .all(|state| state.position.distance(center) <= radius)
+        && states
+            .last()
+            .is_some_and(|state| state.velocity.length() <= speed)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Repulsor {
+    pub position: Vec2,
+    pub radius: f64,
+    pub strength: f64,
+}
+
+pub struct Field {
+    pub goals: Vec<(Vec2, f64)>,
+    pub repulsors: Vec<Repulsor>,
+    pub arena: Option<(f64, f64, f64)>,
+}
+
+impl Default for Field {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Field {
+    pub fn new() -> Field {
+        Field {
+            goals: Vec::new(),
+            repulsors: Vec::new(),
+            arena: None,
+        }
+    }
+
+    pub fn attract(mut self, goal: Vec2, strength: f64) -> Field {
+        self.goals.push((goal, strength));
+        self
+    }
+
+    pub fn repel(mut self, position: Vec2, radius: f64, strength: f64) -> Field {
+        self.repulsors.push(Repulsor {
+            position,
+            radius,
+            strength,
+        });
+        self
+    }
+
+    pub fn enemies(
+        mut self,
+        tracks: &[Track],
+        radius: f64,
+        threat: impl Fn(&Track) -> f64,
+    ) -> Field {
+        self.repulsors.extend(
+            tracks
+                .iter()
+                .filter(|track| track.is_hull() || track.is_munition())
+                .map(|track| Repulsor {
+                    position: track.position,
+                    radius,
+                    strength: threat(track),
+                }),
+        );
+        self
+    }
+
+    pub fn obstacles(mut self, obstacles: &[Obstacle], margin: f64, strength: f64) -> Field {
+        self.repulsors
+            .extend(obstacles.iter().map(|obstacle| Repulsor {
+                position: obstacle.position,
+                radius: obstacle.radius + margin,
+                strength,
+            }));
+        self
+    }
+
+    pub fn edges(mut self, half_size: f64, margin: f64, strength: f64) -> Field {
+        self.arena = Some((half_size, margin, strength));
+        self
+    }
+
+    pub fn gradient(&self, point: Vec2) -> Vec2 {
+        let mut force = vec2(0.0, 0.0);
+        for (goal, strength) in &self.goals {
+            let offset = *goal - point;
+            let distance = offset.length();
+            if distance > 1.0 {
+                force += offset * (strength / distance);
+            }
+        }
+        for repulsor in &self.repulsors {
+            let offset = point - repulsor.position;
+            let distance = offset.length().max(1.0);
+            if distance < repulsor.radius {
+                let falloff = 1.0 - distance / repulsor.radius;
+                force += offset * (repulsor.strength * falloff * falloff / distance);
+            }
+        }
+        if let Some((half_size, margin, strength)) = self.arena {
+            let push = |coordinate: f64| {
+                let depth = coordinate.abs() - (half_size - margin);
+                if depth > 0.0 {
+                    -coordinate.signum() * strength * (depth / margin).min(1.0)
+                } else {
+                    0.0
+                }
+            };
+            force += vec2(push(point.x), push(point.y));
+        }
+        force
+    }
+}
+
+pub struct FollowField<'a> {
+    pub field: &'a Field,
+    pub speed: f64,
+}
+
+impl Move for FollowField<'_> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let max_acceleration = actor.max_linear_acceleration();
+        let gradient = self.field.gradient(actor.position());
+        let length = gradient.length();
+        let desired = if length > 0.0 {
+            gradient * (self.speed / length)
+        } else {
+            vec2(0.0, 0.0)
+        };
+        let correction = (desired - actor.velocity()) / TICK_LENGTH;
+        let magnitude = correction.length();
+        Output {
+            linear: if magnitude > max_acceleration {
+                correction * (max_acceleration / magnitude)
+            } else {
+                correction
+            },
+            angular: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Rendezvous {
+    pub time: f64,
+    pub flip_time: f64,
+    pub burn: Vec2,
+    pub brake: Vec2,
+}
+
+impl Rendezvous {
+    pub fn burns(relative_position: Vec2, relative_velocity: Vec2, time: f64) -> (Vec2, Vec2) {
+        let squared = time * time;
+        let burn = -(relative_position * 4.0 + relative_velocity * (3.0 * time)) / squared;
+        let brake = (relative_position * 4.0 + relative_velocity * time) / squared;
+        (burn, brake)
+    }
+
+    pub fn solve(
+        actor: &impl Kinematic,
+        target: &impl Kinematic,
+        max_acceleration: f64,
+        horizon: f64,
+    ) -> Option<Rendezvous> {
+        let position = actor.position() - target.position();
+        let velocity = actor.velocity() - target.velocity();
+        let feasible = |time: f64| {
+            let (burn, brake) = Rendezvous::burns(position, velocity, time);
+            burn.length().max(brake.length()) <= max_acceleration
+        };
+        const SAMPLES: usize = 64;
+        let mut high = (1..=SAMPLES)
+            .map(|i| horizon * i as f64 / SAMPLES as f64)
+            .find(|time| feasible(*time))?;
+        let mut low = (high - horizon / SAMPLES as f64).max(TICK_LENGTH);
+        for _ in 0..32 {
+            let middle = 0.5 * (low + high);
+            if feasible(middle) {
+                high = middle;
+            } else {
+                low = middle;
+            }
+        }
+        let (burn, brake) = Rendezvous::burns(position, velocity, high);
+        Some(Rendezvous {
+            time: high,
+            flip_time: high / 2.0,
+            burn,
+            brake,
+        })
+    }
+}
+
+pub struct Rendezvousing<T: Kinematic> {
+    pub target: T,
+    pub horizon: f64,
+}
+
+impl<T: Kinematic> Move for Rendezvousing<T> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let max_acceleration = actor.max_linear_acceleration();
+        let linear = match Rendezvous::solve(actor, &self.target, max_acceleration, self.horizon) {
+            Some(plan) if plan.time > TICK_LENGTH => plan.burn,
+            Some(_) => (self.target.velocity() - actor.velocity()) / TICK_LENGTH,
+            None => {
+                Seek {
+                    target: self.target.position(),
+                }
+                .execute(actor)
+                .linear
+            }
+        };
+        let length = linear.length();
+        Output {
+            linear: if length > max_acceleration {
+                linear * (max_acceleration / length)
+            } else {
+                linear
+            },
+            angular: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransitPhase {
+    Burn,
+    Flip,
+    Brake,
+    Arrived,
+}
+
+pub struct FlipAndBurn {
+    pub goal: Vec2,
+    pub tolerance: f64,
+    pub settle_radius: f64,
+    pub horizon: f64,
+    pub attitude: Attitude,
+}
+
+impl FlipAndBurn {
+    pub fn new(goal: Vec2) -> FlipAndBurn {
+        FlipAndBurn {
+            goal,
+            tolerance: 0.05,
+            settle_radius: 50.0,
+            horizon: 600.0,
+            attitude: Attitude::default(),
+        }
+    }
+
+    pub fn flip_time(actor: &impl Motor) -> f64 {
+        let acceleration = actor.max_angular_acceleration();
+        let max_rate = actor.max_angular_velocity();
+        let bang = 2.0 * (PI / acceleration).sqrt();
+        if acceleration * bang / 2.0 > max_rate {
+            PI / max_rate + max_rate / acceleration
+        } else {
+            bang
+        }
+    }
+
+    pub fn flip_distance(actor: &(impl Kinematic + Motor)) -> f64 {
+        let speed = actor.velocity().length();
+        speed * speed / (2.0 * actor.max_linear_acceleration())
+            + speed * FlipAndBurn::flip_time(actor)
+    }
+
+    pub fn phase(&self, actor: &(impl Kinematic + Motor)) -> TransitPhase {
+        let limits = actor.limits();
+        let offset = self.goal - actor.position();
+        let velocity = actor.velocity();
+        if offset.length() <= limits.stop_radius && velocity.length() <= limits.stop_speed {
+            return TransitPhase::Arrived;
+        }
+        let closing = velocity.dot(offset) > 0.0;
+        if !closing || offset.length() > FlipAndBurn::flip_distance(actor) {
+            return TransitPhase::Burn;
+        }
+        if angle_diff(actor.heading(), (-velocity).angle()).abs() <= self.tolerance {
+            TransitPhase::Brake
+        } else {
+            TransitPhase::Flip
+        }
+    }
+
+    pub fn eta(&self, actor: &(impl Kinematic + Motor)) -> f64 {
+        let offset = self.goal - actor.position();
+        let distance = offset.length();
+        let closing = if distance > 0.0 {
+            actor.velocity().dot(offset) / distance
+        } else {
+            0.0
+        };
+        transit_time(distance, closing, actor.max_linear_acceleration(), true)
+            + FlipAndBurn::flip_time(actor)
+    }
+}
+
+impl Move for FlipAndBurn {
+    fn steer(&self, actor: &(impl Kinematic + Motor)) -> Steering {
+        if self.phase(actor) == TransitPhase::Arrived {
+            return Steering::Done;
+        }
+        Steering::checked(self.execute(actor))
+    }
+
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let offset = self.goal - actor.position();
+        if offset.length() <= self.settle_radius {
+            return Arrive::new(self.goal).execute(actor);
+        }
+        let max_acceleration = actor.max_linear_acceleration();
+        let velocity = actor.velocity();
+        let (direction, limit) = match self.phase(actor) {
+            TransitPhase::Burn => {
+                let goal = Body {
+                    position: self.goal,
+                    velocity: vec2(0.0, 0.0),
+                };
+                let burn = Rendezvous::solve(actor, &goal, max_acceleration, self.horizon)
+                    .map(|plan| plan.burn)
+                    .filter(|burn| burn.length() > 0.0)
+                    .unwrap_or(offset);
+                (burn, max_acceleration)
+            }
+            TransitPhase::Flip | TransitPhase::Brake => {
+                (-velocity, velocity.length() / TICK_LENGTH)
+            }
+            TransitPhase::Arrived => return Output::zero(),
+        };
+        let heading = direction.angle();
+        let angular = Align::new(heading)
+            .attitude(self.attitude)
+            .execute(actor)
+            .angular;
+        let aligned = angle_diff(actor.heading(), heading).abs() <= self.tolerance;
+        let thrust = if aligned {
+            max_acceleration.min(limit)
+        } else {
+            0.0
+        };
+        Output {
+            linear: vec2(1.0, 0.0).rotate(actor.heading()) * thrust,
+            angular,
+        }
+        .sanitized()
+    }
+}
+
+pub fn transit_time(distance: f64, speed: f64, acceleration: f64, brake: bool) -> f64 {
+    if acceleration <= 0.0 {
+        return if speed > 0.0 {
+            distance / speed
+        } else {
+            f64::INFINITY
+        };
+    }
+    if !brake {
+        return (-speed + (speed * speed + 2.0 * acceleration * distance).sqrt()) / acceleration;
+    }
+    if speed > 0.0 && speed * speed / (2.0 * acceleration) >= distance {
+        return speed / acceleration;
+    }
+    let peak = (acceleration * distance + speed * speed / 2.0).sqrt();
+    (peak - speed) / acceleration + peak / acceleration
+}
+
+#[cfg(feature = "oort")]
+pub struct BoostPlan {
+    pub acceleration: f64,
+    pub alignment: f64,
+}
+
+#[cfg(feature = "oort")]
+impl Default for BoostPlan {
+    fn default() -> Self {
+        BoostPlan {
+            acceleration: Spec::of(Ability::Boost).acceleration,
+            alignment: 0.1,
+        }
+    }
+}
+
+#[cfg(feature = "oort")]
+impl BoostPlan {
+    pub fn boosted_time(
+        &self,
+        distance: f64,
+        speed: f64,
+        acceleration: f64,
+        brake: bool,
+    ) -> Option<f64> {
+        let duration = Spec::of(Ability::Boost).duration;
+        let boosted = acceleration + self.acceleration;
+        let covered = speed * duration + 0.5 * boosted * duration * duration;
+        let exit_speed = speed + boosted * duration;
+        let remaining = distance - covered;
+        if remaining < 0.0 {
+            return None;
+        }
+        if brake && exit_speed * exit_speed / (2.0 * acceleration) > remaining {
+            return None;
+        }
+        Some(duration + transit_time(remaining, exit_speed, acceleration, brake))
+    }
+
+    pub fn worthwhile(&self, distance: f64, speed: f64, acceleration: f64, brake: bool) -> bool {
+        self.boosted_time(distance, speed, acceleration, brake)
+            .is_some_and(|time| time < transit_time(distance, speed, acceleration, brake))
+    }
+
+    pub fn apply(
+        &self,
+        abilities: &mut Abilities,
+        actor: &(impl Kinematic + Motor),
+        goal: Vec2,
+        brake: bool,
+        now: f64,
+    ) -> bool {
+        let offset = goal - actor.position();
+        let distance = offset.length();
+        if distance < 1.0 || angle_diff(actor.heading(), offset.angle()).abs() > self.alignment {
+            return false;
+        }
+        let speed = actor.velocity().dot(offset / distance);
+        self.worthwhile(distance, speed, actor.max_linear_acceleration(), brake)
+            && abilities.activate(Ability::Boost, now)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Arena {
+    pub half_size: f64,
+    pub margin: f64,
+}
+
+impl Arena {
+    #[cfg(feature = "oort")]
+    pub fn current(margin: f64) -> Arena {
+        Arena {
+            half_size: world_size() / 2.0,
+            margin,
+        }
+    }
+
+    pub fn limit(&self) -> f64 {
+        (self.half_size - self.margin).max(0.0)
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x.abs() <= self.limit() && point.y.abs() <= self.limit()
+    }
+
+    pub fn clamp(&self, point: Vec2) -> Vec2 {
+        let limit = self.limit();
+        vec2(point.x.clamp(-limit, limit), point.y.clamp(-limit, limit))
+    }
+
+    pub fn reflect(&self, point: Vec2) -> Vec2 {
+        let limit = self.limit();
+        let fold = |coordinate: f64| {
+            if limit <= 0.0 {
+                return 0.0;
+            }
+            let period = 4.0 * limit;
+            let shifted = (coordinate + limit).rem_euclid(period);
+            if shifted <= 2.0 * limit {
+                shifted - limit
+            } else {
+                3.0 * limit - shifted
+            }
+        };
+        vec2(fold(point.x), fold(point.y))
+    }
+
+    pub fn clamp_path(&self, path: &mut [Vec2]) {
+        for point in path {
+            *point = self.clamp(*point);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+pub use crate::control::{Attitude, Pid, Thrusters, VelocityController};
+pub use crate::math::{angle_diff, vec2, Vec2, Vec2 as Vector, TICK_LENGTH};
+pub use crate::movement::{
+    Align, ArrayBlend, Arrive, Blend, DynMove, Face, KeepDistance, Kinematic, Motor, Move,
+    Move as MovementStrategy, MovementBlend, OffsetPursuit, Output, Priority, Seek, Station,
+    Steering, SteeringLimits, Strategy,
+};
+pub use crate::perception::{RadarScheduler, Track, Tracker};
+pub use crate::planning::{Arena, FollowPath};
+pub use crate::targeting::{closest_approach, intercept, lead, Intercept};
+pub use crate::time::{Seconds, Ticks};
+
+#[cfg(feature = "oort")]
+pub use crate::avoidance::{Avoid, Neighbor, VelocityObstacle};
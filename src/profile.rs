@@ -0,0 +1,69 @@
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Profile {
+    pub turn_gain: f64,
+    pub gun_tolerance: f64,
+    pub engagement_range: f64,
+    pub danger_radius: f64,
+    pub guidance_gain: f64,
+    pub radar_revisit: f64,
+    pub track_gate: f64,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Profile {
+            turn_gain: 10.0,
+            gun_tolerance: 0.02,
+            engagement_range: 5_000.0,
+            danger_radius: 100.0,
+            guidance_gain: 4.0,
+            radar_revisit: 0.5,
+            track_gate: 200.0,
+        }
+    }
+}
+
+impl Profile {
+    pub fn current() -> Profile {
+        Profile::for_scenario(scenario_name())
+    }
+
+    pub fn for_scenario(name: &str) -> Profile {
+        let default = Profile::default();
+        match name {
+            name if name.starts_with("tutorial") => Profile {
+                radar_revisit: 0.25,
+                ..default
+            },
+            name if name.contains("gunnery") => Profile {
+                turn_gain: 20.0,
+                gun_tolerance: 0.002,
+                engagement_range: 20_000.0,
+                radar_revisit: 0.1,
+                track_gate: 500.0,
+                ..default
+            },
+            name if name.contains("furball") => Profile {
+                turn_gain: 15.0,
+                gun_tolerance: 0.05,
+                engagement_range: 1_000.0,
+                danger_radius: 50.0,
+                radar_revisit: 0.2,
+                ..default
+            },
+            "frigate_duel" => Profile {
+                engagement_range: 6_000.0,
+                ..default
+            },
+            "cruiser_duel" => Profile {
+                engagement_range: 10_000.0,
+                danger_radius: 200.0,
+                guidance_gain: 3.0,
+                ..default
+            },
+            _ => default,
+        }
+    }
+}
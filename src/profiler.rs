@@ -0,0 +1,140 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Subsystem {
+    Radar,
+    Tracker,
+    Targeting,
+    Movement,
+    Radio,
+    Tactics,
+}
+
+impl Subsystem {
+    pub const ALL: [Subsystem; 6] = [
+        Subsystem::Radar,
+        Subsystem::Tracker,
+        Subsystem::Targeting,
+        Subsystem::Movement,
+        Subsystem::Radio,
+        Subsystem::Tactics,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Subsystem::Radar => "radar",
+            Subsystem::Tracker => "tracker",
+            Subsystem::Targeting => "targeting",
+            Subsystem::Movement => "movement",
+            Subsystem::Radio => "radio",
+            Subsystem::Tactics => "tactics",
+        }
+    }
+}
+
+pub trait Clock {
+    fn now(&self) -> u64;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub struct SystemClock {
+    start: std::time::Instant,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock {
+            start: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        self.start.elapsed().as_nanos() as u64
+    }
+}
+
+impl<F: Fn() -> u64> Clock for F {
+    fn now(&self) -> u64 {
+        self()
+    }
+}
+
+impl Clock for Box<dyn Clock> {
+    fn now(&self) -> u64 {
+        self.as_ref().now()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Sample {
+    pub last: u64,
+    pub average: f64,
+    pub max: u64,
+    current: u64,
+}
+
+pub struct Profiler<C: Clock = Box<dyn Clock>> {
+    pub clock: C,
+    pub smoothing: f64,
+    pub enabled: bool,
+    samples: [Sample; Subsystem::ALL.len()],
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Default for Profiler<SystemClock> {
+    fn default() -> Self {
+        Profiler::new(SystemClock::default())
+    }
+}
+
+impl<C: Clock> Profiler<C> {
+    pub fn new(clock: C) -> Profiler<C> {
+        Profiler {
+            clock,
+            smoothing: 0.1,
+            enabled: true,
+            samples: [Sample::default(); Subsystem::ALL.len()],
+        }
+    }
+
+    pub fn measure<R>(&mut self, subsystem: Subsystem, work: impl FnOnce() -> R) -> R {
+        if !self.enabled {
+            return work();
+        }
+        let start = self.clock.now();
+        let result = work();
+        let elapsed = self.clock.now().saturating_sub(start);
+        self.samples[subsystem as usize].current += elapsed;
+        result
+    }
+
+    pub fn end_tick(&mut self) {
+        for sample in &mut self.samples {
+            sample.last = sample.current;
+            sample.max = sample.max.max(sample.current);
+            sample.average += self.smoothing * (sample.current as f64 - sample.average);
+            sample.current = 0;
+        }
+    }
+
+    pub fn sample(&self, subsystem: Subsystem) -> Sample {
+        self.samples[subsystem as usize]
+    }
+
+    pub fn total(&self) -> u64 {
+        self.samples.iter().map(|sample| sample.last).sum()
+    }
+
+    pub fn heaviest(&self) -> Subsystem {
+        Subsystem::ALL
+            .into_iter()
+            .max_by(|a, b| self.sample(*a).average.total_cmp(&self.sample(*b).average))
+            .unwrap_or(Subsystem::Movement)
+    }
+
+    pub fn reset(&mut self) {
+        self.samples = [Sample::default(); Subsystem::ALL.len()];
+    }
+}
@@ -0,0 +1,917 @@
+pub use crate::contact::{class_code, class_from_code, ContactReport};
+use crate::fleet::Role;
+use crate::missile::{Designation, Retarget, RetargetAck, TargetState};
+use crate::tactics::{AmbushCue, AmbushPhase, PincerOrder, PriorityKill, VolleyOrder};
+use oort_api::prelude::*;
+
+pub const CHANNELS: usize = 10;
+pub const VERSION: u8 = 2;
+
+pub const POSITION_LIMIT: f64 = 1_048_576.0;
+pub const VELOCITY_LIMIT: f64 = 16_384.0;
+const POSITION_BITS: u32 = 32;
+const VELOCITY_BITS: u32 = 24;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub version: u8,
+    pub kind: u8,
+    pub sender: u16,
+    pub aux: u16,
+}
+
+impl Header {
+    pub fn encode(&self) -> f64 {
+        ((self.version as u64) << 40
+            | (self.kind as u64) << 32
+            | (self.sender as u64) << 16
+            | self.aux as u64) as f64
+    }
+
+    pub fn decode(word: f64) -> Option<Header> {
+        if !(0.0..(1u64 << 48) as f64).contains(&word) || word.fract() != 0.0 {
+            return None;
+        }
+        let bits = word as u64;
+        Some(Header {
+            version: (bits >> 40) as u8,
+            kind: (bits >> 32) as u8,
+            sender: (bits >> 16) as u16,
+            aux: bits as u16,
+        })
+    }
+}
+
+pub trait Payload: Sized {
+    const KIND: u8;
+    fn encode(&self) -> (u16, [f64; 3]);
+    fn decode(aux: u16, body: [f64; 3]) -> Option<Self>;
+}
+
+pub fn encode<P: Payload>(payload: &P, sender: u16) -> Message {
+    let (aux, [a, b, c]) = payload.encode();
+    let header = Header {
+        version: VERSION,
+        kind: P::KIND,
+        sender,
+        aux,
+    };
+    [header.encode(), a, b, c]
+}
+
+pub fn decode<P: Payload>(message: Message) -> Option<P> {
+    let header = header(message)?;
+    if header.kind != P::KIND {
+        return None;
+    }
+    P::decode(header.aux, [message[1], message[2], message[3]])
+}
+
+pub fn header(message: Message) -> Option<Header> {
+    Header::decode(message[0]).filter(|header| header.version == VERSION)
+}
+
+pub fn transmit<P: Payload>(channel: usize, payload: &P) {
+    set_radio_channel(channel);
+    send(encode(payload, id() as u16));
+}
+
+pub fn receive_on(channel: usize) -> impl Iterator<Item = Message> {
+    set_radio_channel(channel);
+    std::iter::from_fn(receive)
+}
+
+pub fn quantize(value: f64, limit: f64, width: u32) -> u64 {
+    if !(-limit..=limit).contains(&value) {
+        return mask(width);
+    }
+    let steps = (mask(width) - 1) as f64;
+    let unit = (value + limit) / (2.0 * limit);
+    (unit * steps).round() as u64
+}
+
+pub fn dequantize(code: u64, limit: f64, width: u32) -> Option<f64> {
+    if code >= mask(width) {
+        return None;
+    }
+    let steps = (mask(width) - 1) as f64;
+    Some(code as f64 / steps * 2.0 * limit - limit)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bits {
+    words: [u64; 3],
+    cursor: u32,
+}
+
+impl Bits {
+    pub const CAPACITY: u32 = 192;
+
+    pub fn new() -> Bits {
+        Bits::default()
+    }
+
+    pub fn read(body: [f64; 3]) -> Bits {
+        Bits {
+            words: body.map(f64::to_bits),
+            cursor: 0,
+        }
+    }
+
+    pub fn put(&mut self, value: u64, width: u32) -> &mut Bits {
+        debug_assert!(width <= 64 && self.cursor + width <= Bits::CAPACITY);
+        let value = value & mask(width);
+        let (index, offset) = ((self.cursor / 64) as usize, self.cursor % 64);
+        self.words[index] |= value << offset;
+        if offset + width > 64 {
+            self.words[index + 1] |= value >> (64 - offset);
+        }
+        self.cursor += width;
+        self
+    }
+
+    pub fn take(&mut self, width: u32) -> Option<u64> {
+        if width > 64 || self.cursor + width > Bits::CAPACITY {
+            return None;
+        }
+        let (index, offset) = ((self.cursor / 64) as usize, self.cursor % 64);
+        let mut value = self.words[index] >> offset;
+        if offset + width > 64 {
+            value |= self.words[index + 1] << (64 - offset);
+        }
+        self.cursor += width;
+        Some(value & mask(width))
+    }
+
+    pub fn put_scalar(&mut self, value: f64, limit: f64, width: u32) -> &mut Bits {
+        self.put(quantize(value, limit, width), width)
+    }
+
+    pub fn take_scalar(&mut self, limit: f64, width: u32) -> Option<f64> {
+        dequantize(self.take(width)?, limit, width)
+    }
+
+    pub fn put_vec(&mut self, v: Vec2, limit: f64, width: u32) -> &mut Bits {
+        self.put_scalar(v.x, limit, width)
+            .put_scalar(v.y, limit, width)
+    }
+
+    pub fn take_vec(&mut self, limit: f64, width: u32) -> Option<Vec2> {
+        Some(vec2(
+            self.take_scalar(limit, width)?,
+            self.take_scalar(limit, width)?,
+        ))
+    }
+
+    pub fn put_angle(&mut self, angle: f64) -> &mut Bits {
+        self.put_scalar(angle_diff(0.0, angle), std::f64::consts::PI, 32)
+    }
+
+    pub fn take_angle(&mut self) -> Option<f64> {
+        self.take_scalar(std::f64::consts::PI, 32)
+    }
+
+    pub fn put_time(&mut self, time: f64) -> &mut Bits {
+        self.put((time / TICK_LENGTH).round().max(0.0) as u64, 32)
+    }
+
+    pub fn take_time(&mut self) -> Option<f64> {
+        Some(self.take(32)? as f64 * TICK_LENGTH)
+    }
+
+    pub fn put_state(&mut self, position: Vec2, velocity: Vec2) -> &mut Bits {
+        self.put_vec(position, POSITION_LIMIT, POSITION_BITS)
+            .put_vec(velocity, VELOCITY_LIMIT, VELOCITY_BITS)
+    }
+
+    pub fn take_state(&mut self) -> Option<(Vec2, Vec2)> {
+        Some((
+            self.take_vec(POSITION_LIMIT, POSITION_BITS)?,
+            self.take_vec(VELOCITY_LIMIT, VELOCITY_BITS)?,
+        ))
+    }
+
+    pub fn finish(&self) -> Option<()> {
+        let mut rest = *self;
+        while rest.cursor < Bits::CAPACITY {
+            let width = (Bits::CAPACITY - rest.cursor).min(64);
+            if rest.take(width)? != 0 {
+                return None;
+            }
+        }
+        Some(())
+    }
+
+    pub fn words(&self) -> [f64; 3] {
+        self.words.map(f64::from_bits)
+    }
+}
+
+fn mask(width: u32) -> u64 {
+    if width >= 64 {
+        u64::MAX
+    } else {
+        (1u64 << width) - 1
+    }
+}
+
+fn pack_state(position: Vec2, velocity: Vec2) -> [f64; 3] {
+    Bits::new().put_state(position, velocity).words()
+}
+
+fn unpack_state(body: [f64; 3]) -> Option<(Vec2, Vec2)> {
+    let mut bits = Bits::read(body);
+    let state = bits.take_state()?;
+    bits.finish()?;
+    Some(state)
+}
+
+fn finite(x: f64, y: f64) -> Option<Vec2> {
+    (x.is_finite() && y.is_finite()).then(|| vec2(x, y))
+}
+
+impl Payload for TargetState {
+    const KIND: u8 = 1;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        (0, pack_state(self.position, self.velocity))
+    }
+    fn decode(_: u16, body: [f64; 3]) -> Option<Self> {
+        let (position, velocity) = unpack_state(body)?;
+        Some(TargetState { position, velocity })
+    }
+}
+
+impl Payload for Designation {
+    const KIND: u8 = 16;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        (
+            self.track,
+            pack_state(self.target.position, self.target.velocity),
+        )
+    }
+    fn decode(track: u16, body: [f64; 3]) -> Option<Self> {
+        let (position, velocity) = unpack_state(body)?;
+        Some(Designation {
+            track,
+            target: TargetState { position, velocity },
+        })
+    }
+}
+
+impl Payload for Retarget {
+    const KIND: u8 = 14;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        let words = Bits::new()
+            .put_state(self.target.position, self.target.velocity)
+            .put(self.track as u64, 16)
+            .words();
+        (self.missile, words)
+    }
+    fn decode(missile: u16, body: [f64; 3]) -> Option<Self> {
+        let mut bits = Bits::read(body);
+        let (position, velocity) = bits.take_state()?;
+        let track = bits.take(16)? as u16;
+        bits.finish()?;
+        Some(Retarget {
+            missile,
+            track,
+            target: TargetState { position, velocity },
+        })
+    }
+}
+
+impl Payload for RetargetAck {
+    const KIND: u8 = 15;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        let words = Bits::new().put(self.intercept_time.to_bits(), 64).words();
+        (self.accepted as u16, words)
+    }
+    fn decode(accepted: u16, body: [f64; 3]) -> Option<Self> {
+        let mut bits = Bits::read(body);
+        let intercept_time = f64::from_bits(bits.take(64)?);
+        bits.finish()?;
+        if accepted > 1 || intercept_time.is_nan() {
+            return None;
+        }
+        Some(RetargetAck {
+            accepted: accepted == 1,
+            intercept_time,
+        })
+    }
+}
+
+impl Payload for ContactReport {
+    const KIND: u8 = 2;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        (
+            class_code(self.class),
+            pack_state(self.position, self.velocity),
+        )
+    }
+    fn decode(aux: u16, body: [f64; 3]) -> Option<Self> {
+        let (position, velocity) = unpack_state(body)?;
+        Some(ContactReport {
+            class: class_from_code(aux),
+            position,
+            velocity,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TargetAssignment {
+    pub ship: u16,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+impl Payload for TargetAssignment {
+    const KIND: u8 = 3;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        (self.ship, pack_state(self.position, self.velocity))
+    }
+    fn decode(ship: u16, body: [f64; 3]) -> Option<Self> {
+        let (position, velocity) = unpack_state(body)?;
+        Some(TargetAssignment {
+            ship,
+            position,
+            velocity,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct FormationCommand {
+    pub shape: u16,
+    pub anchor: Vec2,
+    pub heading: f64,
+    pub spacing: f64,
+}
+
+impl Payload for FormationCommand {
+    const KIND: u8 = 4;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        let words = Bits::new()
+            .put_vec(self.anchor, POSITION_LIMIT, POSITION_BITS)
+            .put_angle(self.heading)
+            .put_scalar(self.spacing, POSITION_LIMIT, POSITION_BITS)
+            .words();
+        (self.shape, words)
+    }
+    fn decode(shape: u16, body: [f64; 3]) -> Option<Self> {
+        let mut bits = Bits::read(body);
+        let anchor = bits.take_vec(POSITION_LIMIT, POSITION_BITS)?;
+        let heading = bits.take_angle()?;
+        let spacing = bits.take_scalar(POSITION_LIMIT, POSITION_BITS)?;
+        bits.finish()?;
+        Some(FormationCommand {
+            shape,
+            anchor,
+            heading,
+            spacing,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Status {
+    pub health: u16,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+impl Payload for Status {
+    const KIND: u8 = 5;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        (self.health, pack_state(self.position, self.velocity))
+    }
+    fn decode(health: u16, body: [f64; 3]) -> Option<Self> {
+        let (position, velocity) = unpack_state(body)?;
+        Some(Status {
+            health,
+            position,
+            velocity,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Packet {
+    Target(TargetState),
+    Contact(ContactReport),
+    Assignment(TargetAssignment),
+    Formation(FormationCommand),
+    Status(Status),
+    Bid(Bid),
+    Role(RoleAssignment),
+    Fragment(Fragment),
+    Pincer(PincerOrder),
+    Priority(PriorityKill),
+    Volley(VolleyOrder),
+    Ambush(AmbushCue),
+    Bearing(BearingReport),
+}
+
+impl Packet {
+    pub fn encode(&self, sender: u16) -> Message {
+        match self {
+            Packet::Target(payload) => encode(payload, sender),
+            Packet::Contact(payload) => encode(payload, sender),
+            Packet::Assignment(payload) => encode(payload, sender),
+            Packet::Formation(payload) => encode(payload, sender),
+            Packet::Status(payload) => encode(payload, sender),
+            Packet::Bid(payload) => encode(payload, sender),
+            Packet::Role(payload) => encode(payload, sender),
+            Packet::Fragment(payload) => encode(payload, sender),
+            Packet::Pincer(payload) => encode(payload, sender),
+            Packet::Priority(payload) => encode(payload, sender),
+            Packet::Volley(payload) => encode(payload, sender),
+            Packet::Ambush(payload) => encode(payload, sender),
+            Packet::Bearing(payload) => encode(payload, sender),
+        }
+    }
+
+    pub fn decode(message: Message) -> Option<(Header, Packet)> {
+        let header = header(message)?;
+        let packet = match header.kind {
+            TargetState::KIND => Packet::Target(decode(message)?),
+            ContactReport::KIND => Packet::Contact(decode(message)?),
+            TargetAssignment::KIND => Packet::Assignment(decode(message)?),
+            FormationCommand::KIND => Packet::Formation(decode(message)?),
+            Status::KIND => Packet::Status(decode(message)?),
+            Bid::KIND => Packet::Bid(decode(message)?),
+            RoleAssignment::KIND => Packet::Role(decode(message)?),
+            Fragment::KIND => Packet::Fragment(decode(message)?),
+            PincerOrder::KIND => Packet::Pincer(decode(message)?),
+            PriorityKill::KIND => Packet::Priority(decode(message)?),
+            VolleyOrder::KIND => Packet::Volley(decode(message)?),
+            AmbushCue::KIND => Packet::Ambush(decode(message)?),
+            BearingReport::KIND => Packet::Bearing(decode(message)?),
+            _ => return None,
+        };
+        Some((header, packet))
+    }
+
+    pub fn transmit(&self, channel: usize) {
+        set_radio_channel(channel);
+        send(self.encode(id() as u16));
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Bid {
+    pub position: Vec2,
+    pub score: f64,
+}
+
+impl Payload for Bid {
+    const KIND: u8 = 6;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        let words = Bits::new()
+            .put_vec(self.position, POSITION_LIMIT, POSITION_BITS)
+            .put(self.score.to_bits(), 64)
+            .words();
+        (0, words)
+    }
+    fn decode(_: u16, body: [f64; 3]) -> Option<Self> {
+        let mut bits = Bits::read(body);
+        let position = bits.take_vec(POSITION_LIMIT, POSITION_BITS)?;
+        let score = f64::from_bits(bits.take(64)?);
+        bits.finish()?;
+        if !score.is_finite() {
+            return None;
+        }
+        Some(Bid { position, score })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RoleAssignment {
+    pub ship: u16,
+    pub role: Role,
+}
+
+impl Payload for RoleAssignment {
+    const KIND: u8 = 7;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        (self.ship, [self.role.code() as f64, 0.0, 0.0])
+    }
+    fn decode(ship: u16, [role, _, _]: [f64; 3]) -> Option<Self> {
+        if !(0.0..=u8::MAX as f64).contains(&role) {
+            return None;
+        }
+        Some(RoleAssignment {
+            ship,
+            role: Role::from_code(role as u8)?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Fragment {
+    pub message: u8,
+    pub index: u8,
+    pub count: u8,
+    pub words: [f64; 3],
+}
+
+impl Payload for Fragment {
+    const KIND: u8 = 8;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        let aux = (self.message as u16 & 0x3f) << 10
+            | (self.index as u16 & 0x1f) << 5
+            | (self.count.saturating_sub(1) as u16 & 0x1f);
+        (aux, self.words)
+    }
+    fn decode(aux: u16, words: [f64; 3]) -> Option<Self> {
+        let fragment = Fragment {
+            message: (aux >> 10) as u8,
+            index: (aux >> 5 & 0x1f) as u8,
+            count: (aux & 0x1f) as u8 + 1,
+            words,
+        };
+        (fragment.index < fragment.count).then_some(fragment)
+    }
+}
+
+impl Payload for PincerOrder {
+    const KIND: u8 = 9;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        let words = Bits::new()
+            .put_vec(self.target, POSITION_LIMIT, POSITION_BITS)
+            .put_angle(self.bearing)
+            .put_time(self.strike_time)
+            .words();
+        (self.element, words)
+    }
+    fn decode(element: u16, body: [f64; 3]) -> Option<Self> {
+        let mut bits = Bits::read(body);
+        let target = bits.take_vec(POSITION_LIMIT, POSITION_BITS)?;
+        let bearing = bits.take_angle()?;
+        let strike_time = bits.take_time()?;
+        bits.finish()?;
+        Some(PincerOrder {
+            element,
+            target,
+            bearing,
+            strike_time,
+        })
+    }
+}
+
+impl Payload for PriorityKill {
+    const KIND: u8 = 10;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        (self.designation, pack_state(self.position, self.velocity))
+    }
+    fn decode(designation: u16, body: [f64; 3]) -> Option<Self> {
+        let (position, velocity) = unpack_state(body)?;
+        Some(PriorityKill {
+            designation,
+            position,
+            velocity,
+        })
+    }
+}
+
+impl Payload for VolleyOrder {
+    const KIND: u8 = 11;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        let words = Bits::new()
+            .put_vec(self.target, POSITION_LIMIT, POSITION_BITS)
+            .put_time(self.launch_time)
+            .put_time(self.arrival)
+            .words();
+        (self.ship, words)
+    }
+    fn decode(ship: u16, body: [f64; 3]) -> Option<Self> {
+        let mut bits = Bits::read(body);
+        let target = bits.take_vec(POSITION_LIMIT, POSITION_BITS)?;
+        let launch_time = bits.take_time()?;
+        let arrival = bits.take_time()?;
+        bits.finish()?;
+        Some(VolleyOrder {
+            ship,
+            target,
+            launch_time,
+            arrival,
+        })
+    }
+}
+
+impl Payload for AmbushCue {
+    const KIND: u8 = 12;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        (self.phase.code(), pack_state(self.target, self.velocity))
+    }
+    fn decode(phase: u16, body: [f64; 3]) -> Option<Self> {
+        let (target, velocity) = unpack_state(body)?;
+        Some(AmbushCue {
+            phase: AmbushPhase::from_code(phase)?,
+            target,
+            velocity,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BearingReport {
+    pub class: Class,
+    pub origin: Vec2,
+    pub bearing: f64,
+    pub time: f64,
+}
+
+impl Payload for BearingReport {
+    const KIND: u8 = 13;
+    fn encode(&self) -> (u16, [f64; 3]) {
+        let words = Bits::new()
+            .put_vec(self.origin, POSITION_LIMIT, POSITION_BITS)
+            .put_angle(self.bearing)
+            .put_time(self.time)
+            .words();
+        (class_code(self.class), words)
+    }
+    fn decode(class: u16, body: [f64; 3]) -> Option<Self> {
+        let mut bits = Bits::read(body);
+        let origin = bits.take_vec(POSITION_LIMIT, POSITION_BITS)?;
+        let bearing = bits.take_angle()?;
+        let time = bits.take_time()?;
+        bits.finish()?;
+        Some(BearingReport {
+            class: class_from_code(class),
+            origin,
+            bearing,
+            time,
+        })
+    }
+}
+
+pub const MAX_FRAGMENTS: usize = 32;
+
+pub struct Fragmenter {
+    pub repeat: usize,
+    next_message: u8,
+    pending: std::collections::VecDeque<Packet>,
+}
+
+impl Default for Fragmenter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fragmenter {
+    pub fn new() -> Fragmenter {
+        Fragmenter {
+            repeat: 1,
+            next_message: 0,
+            pending: std::collections::VecDeque::new(),
+        }
+    }
+
+    pub fn send(&mut self, data: &[f64]) -> bool {
+        let mut words = Vec::with_capacity(data.len() + 1);
+        words.push(data.len() as f64);
+        words.extend_from_slice(data);
+        let count = words.len().div_ceil(3);
+        if count > MAX_FRAGMENTS {
+            return false;
+        }
+        let message = self.next_message;
+        self.next_message = (self.next_message + 1) & 0x3f;
+        for _ in 0..self.repeat.max(1) {
+            for (index, chunk) in words.chunks(3).enumerate() {
+                let mut body = [0.0; 3];
+                body[..chunk.len()].copy_from_slice(chunk);
+                self.pending.push_back(Packet::Fragment(Fragment {
+                    message,
+                    index: index as u8,
+                    count: count as u8,
+                    words: body,
+                }));
+            }
+        }
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<Packet> {
+        self.pending.pop_front()
+    }
+
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+struct Partial {
+    sender: u16,
+    message: u8,
+    words: Vec<Option<[f64; 3]>>,
+    started: f64,
+}
+
+pub struct Reassembler {
+    pub timeout: f64,
+    pub dedup: f64,
+    partial: Vec<Partial>,
+    completed: Vec<(u16, u8, f64)>,
+}
+
+impl Default for Reassembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Reassembler {
+    pub fn new() -> Reassembler {
+        Reassembler {
+            timeout: 2.0,
+            dedup: 0.5,
+            partial: Vec::new(),
+            completed: Vec::new(),
+        }
+    }
+
+    pub fn accept(&mut self, inbox: &[(Header, Packet)], now: f64) -> Vec<(u16, Vec<f64>)> {
+        let mut complete = Vec::new();
+        for (header, packet) in inbox {
+            let Packet::Fragment(fragment) = packet else {
+                continue;
+            };
+            let sender = header.sender;
+            if self
+                .completed
+                .iter()
+                .any(|(s, message, _)| *s == sender && *message == fragment.message)
+            {
+                continue;
+            }
+            let existing = self
+                .partial
+                .iter()
+                .position(|p| p.sender == sender && p.message == fragment.message);
+            let index = match existing {
+                Some(index) => index,
+                None => {
+                    self.partial.push(Partial {
+                        sender,
+                        message: fragment.message,
+                        words: vec![None; fragment.count as usize],
+                        started: now,
+                    });
+                    self.partial.len() - 1
+                }
+            };
+            let partial = &mut self.partial[index];
+            if let Some(slot) = partial.words.get_mut(fragment.index as usize) {
+                *slot = Some(fragment.words);
+            }
+            if partial.words.iter().all(Option::is_some) {
+                let partial = self.partial.remove(index);
+                self.completed.push((sender, partial.message, now));
+                let words: Vec<f64> = partial.words.into_iter().flatten().flatten().collect();
+                let length = words[0];
+                if length >= 0.0 && (length as usize) < words.len() {
+                    complete.push((sender, words[1..=length as usize].to_vec()));
+                }
+            }
+        }
+        let (timeout, dedup) = (self.timeout, self.dedup);
+        self.partial.retain(|p| now - p.started <= timeout);
+        self.completed.retain(|(_, _, at)| now - at <= dedup);
+        complete
+    }
+}
+
+impl ContactReport {
+    pub const WORDS: usize = 5;
+
+    pub fn to_words(&self) -> [f64; 5] {
+        [
+            class_code(self.class) as f64,
+            self.position.x,
+            self.position.y,
+            self.velocity.x,
+            self.velocity.y,
+        ]
+    }
+
+    pub fn from_words(words: &[f64]) -> Option<ContactReport> {
+        let [class, x, y, vx, vy] = words.try_into().ok()?;
+        if !(0.0..=u16::MAX as f64).contains(&class) {
+            return None;
+        }
+        Some(ContactReport {
+            class: class_from_code(class as u16),
+            position: finite(x, y)?,
+            velocity: finite(vx, vy)?,
+        })
+    }
+}
+
+pub struct ChannelPlan {
+    pub command: usize,
+    pub first_flight: usize,
+}
+
+impl Default for ChannelPlan {
+    fn default() -> Self {
+        ChannelPlan {
+            command: 0,
+            first_flight: 1,
+        }
+    }
+}
+
+impl ChannelPlan {
+    pub fn flight(&self, flight: usize) -> usize {
+        let first = self.first_flight.min(CHANNELS - 1);
+        first + flight % (CHANNELS - first)
+    }
+
+    pub fn flight_of(ship: u16, flight_size: usize) -> usize {
+        ship as usize / flight_size.max(1)
+    }
+}
+
+const QUEUE_LIMIT: usize = 8;
+
+pub struct Radio {
+    pub channel: usize,
+    pub slots: usize,
+    slot: usize,
+    ship: u16,
+    sent: Option<u32>,
+    inbox: Vec<(Header, Packet)>,
+    outbox: std::collections::VecDeque<Packet>,
+}
+
+impl Radio {
+    pub fn new(channel: usize) -> Radio {
+        Radio::slotted(channel, 0, 1)
+    }
+
+    pub fn slotted(channel: usize, ship: u16, slots: usize) -> Radio {
+        let slots = slots.max(1);
+        Radio {
+            channel,
+            slots,
+            slot: ship as usize % slots,
+            ship,
+            sent: None,
+            inbox: Vec::new(),
+            outbox: std::collections::VecDeque::new(),
+        }
+    }
+
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    pub fn poll(&mut self, tick: u32) {
+        self.inbox.clear();
+        self.inbox
+            .extend(receive_on(self.channel).filter_map(Packet::decode));
+        let previous = tick.wrapping_sub(1);
+        let occupied = self.is_slot(previous)
+            && self.sent != Some(previous)
+            && self
+                .inbox
+                .iter()
+                .any(|(header, _)| header.sender != self.ship);
+        if occupied && self.slots > 1 {
+            self.slot = (self.slot + 1 + self.ship as usize) % self.slots;
+        }
+    }
+
+    fn is_slot(&self, tick: u32) -> bool {
+        tick as usize % self.slots == self.slot
+    }
+
+    pub fn inbox(&self) -> &[(Header, Packet)] {
+        &self.inbox
+    }
+
+    pub fn queue(&mut self, packet: Packet) {
+        self.outbox.push_back(packet);
+        if self.outbox.len() > QUEUE_LIMIT {
+            self.outbox.pop_front();
+        }
+    }
+
+    pub fn pending(&self) -> usize {
+        self.outbox.len()
+    }
+
+    pub fn flush(&mut self, tick: u32) -> Option<Packet> {
+        if !self.is_slot(tick) {
+            return None;
+        }
+        let packet = self.outbox.pop_front()?;
+        packet.transmit(self.channel);
+        self.sent = Some(tick);
+        Some(packet)
+    }
+}
@@ -0,0 +1,160 @@
+use crate::context::TickContext;
+use crate::controllers::{
+    BaseDefense, CruiserDuel, EcmDuel, FrigateDuel, Furball, Gunnery, Squadron, Transit,
+};
+use crate::draw::OortDraw;
+use crate::profile::Profile;
+use crate::ships::Brain;
+use crate::tutorials::{Acceleration, Lead, Rotation};
+use crate::Oort;
+use oort_api::prelude::*;
+
+pub trait Controller {
+    fn tick(&mut self, context: &mut TickContext<OortDraw>);
+}
+
+macro_rules! controller {
+    ($($t:ty),*) => {
+        $(impl Controller for $t {
+            fn tick(&mut self, context: &mut TickContext<OortDraw>) {
+                <$t>::tick(self, &*context)
+            }
+        })*
+    };
+}
+
+impl Controller for Brain {
+    fn tick(&mut self, context: &mut TickContext<OortDraw>) {
+        self.drive(context, &mut Oort::default());
+    }
+}
+
+impl Controller for Gunnery {
+    fn tick(&mut self, context: &mut TickContext<OortDraw>) {
+        self.drive(context, &mut Oort::default());
+    }
+}
+
+controller!(
+    FrigateDuel,
+    CruiserDuel,
+    Furball,
+    Squadron,
+    BaseDefense,
+    Transit,
+    EcmDuel,
+    Acceleration,
+    Rotation,
+    Lead
+);
+
+pub type Factory = fn(&Profile) -> Box<dyn Controller>;
+
+pub const SQUADRON_SIZE: usize = 4;
+
+struct Entry {
+    pattern: &'static str,
+    class: Option<Class>,
+    factory: Factory,
+}
+
+pub fn matches(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+pub struct Registry {
+    entries: Vec<Entry>,
+    fallback: Factory,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Registry::new(|profile| Box::new(Brain::tuned(profile)))
+            .register("tutorial_acceleration*", Some(Class::Fighter), |_| {
+                Box::new(Acceleration::default())
+            })
+            .register("tutorial_rotation", Some(Class::Fighter), |_| {
+                Box::new(Rotation::default())
+            })
+            .register("tutorial_lead", Some(Class::Fighter), |_| {
+                Box::new(Lead::default())
+            })
+            .register("*gunnery*", Some(Class::Fighter), |profile| {
+                let mut gunnery = Gunnery::new();
+                gunnery.tune(profile);
+                Box::new(gunnery)
+            })
+            .register("frigate_duel", Some(Class::Frigate), |_| {
+                Box::new(FrigateDuel::new())
+            })
+            .register("cruiser_duel", Some(Class::Cruiser), |_| {
+                Box::new(CruiserDuel::new())
+            })
+            .register("*furball*", Some(Class::Fighter), |profile| {
+                let mut furball = Furball::new();
+                furball.gunnery.tune(profile);
+                Box::new(furball)
+            })
+            .register("*squadron*", Some(Class::Fighter), |_| {
+                Box::new(Squadron::new(id() as u16, SQUADRON_SIZE))
+            })
+            .register("*defense*", Some(Class::Fighter), |_| {
+                Box::new(BaseDefense::new(position(), id() as usize, SQUADRON_SIZE))
+            })
+            .register("*transit*", None, |_| Box::new(Transit::new(target())))
+            .register("*ecm*", Some(Class::Fighter), |_| Box::new(EcmDuel::new()))
+    }
+}
+
+impl Registry {
+    pub fn new(fallback: Factory) -> Registry {
+        Registry {
+            entries: Vec::new(),
+            fallback,
+        }
+    }
+
+    pub fn register(
+        mut self,
+        pattern: &'static str,
+        class: Option<Class>,
+        factory: Factory,
+    ) -> Registry {
+        self.entries.push(Entry {
+            pattern,
+            class,
+            factory,
+        });
+        self
+    }
+
+    pub fn lookup(&self, name: &str, class: Class) -> Factory {
+        self.entries
+            .iter()
+            .find(|entry| entry.class.is_none_or(|c| c == class) && matches(entry.pattern, name))
+            .map_or(self.fallback, |entry| entry.factory)
+    }
+
+    pub fn build(&self, name: &str, class: Class, profile: &Profile) -> Box<dyn Controller> {
+        (self.lookup(name, class))(profile)
+    }
+
+    pub fn current(&self, profile: &Profile) -> Box<dyn Controller> {
+        self.build(scenario_name(), class(), profile)
+    }
+}
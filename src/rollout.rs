@@ -0,0 +1,217 @@
+use crate::math::*;
+use crate::movement::*;
+use crate::random::Rng;
+use crate::sim::{MockMotor, RandomJink, Script};
+use crate::targeting::intercept;
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Maneuver {
+    Hold,
+    BreakLeft,
+    BreakRight,
+    Extend,
+    Climb,
+}
+
+impl Maneuver {
+    pub const ALL: [Maneuver; 5] = [
+        Maneuver::Hold,
+        Maneuver::BreakLeft,
+        Maneuver::BreakRight,
+        Maneuver::Extend,
+        Maneuver::Climb,
+    ];
+
+    pub fn direction(self, actor: &impl Kinematic, enemy: &impl Kinematic) -> Vec2 {
+        let offset = enemy.position() - actor.position();
+        if sq_length(offset) == 0.0 {
+            return vec2(0.0, 0.0);
+        }
+        let line_of_sight = offset.normalize();
+        let left = perpendicular(line_of_sight);
+        match self {
+            Maneuver::Hold => vec2(0.0, 0.0),
+            Maneuver::BreakLeft => left,
+            Maneuver::BreakRight => -left,
+            Maneuver::Extend => -line_of_sight,
+            Maneuver::Climb => {
+                let relative = actor.velocity() - enemy.velocity();
+                let side = if cross(line_of_sight, relative) < 0.0 {
+                    -1.0
+                } else {
+                    1.0
+                };
+                (left * side - line_of_sight).normalize()
+            }
+        }
+    }
+}
+
+pub struct Perform<T: Kinematic> {
+    pub maneuver: Maneuver,
+    pub enemy: T,
+}
+
+impl<T: Kinematic> Move for Perform<T> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        Output {
+            linear: self.maneuver.direction(actor, &self.enemy) * actor.max_linear_acceleration(),
+            angular: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Score {
+    pub maneuver: Maneuver,
+    pub exposure: f64,
+    pub solution_time: f64,
+    pub rollouts: u32,
+}
+
+impl Score {
+    pub fn value(&self, exposure_weight: f64) -> f64 {
+        if self.rollouts == 0 {
+            return f64::NEG_INFINITY;
+        }
+        (self.solution_time - exposure_weight * self.exposure) / self.rollouts as f64
+    }
+}
+
+pub struct Rollout {
+    pub budget: usize,
+    pub horizon: f64,
+    pub step: f64,
+    pub max_rollouts: u32,
+    pub enemy_acceleration: f64,
+    pub jink_period: f64,
+    pub gun_range: f64,
+    pub bullet_speed: f64,
+    pub threat_range: f64,
+    pub crossing_speed: f64,
+    pub exposure_weight: f64,
+    pub candidates: Vec<Maneuver>,
+}
+
+impl Default for Rollout {
+    fn default() -> Self {
+        Rollout {
+            budget: 1_500,
+            horizon: 3.0,
+            step: 0.1,
+            max_rollouts: 40,
+            enemy_acceleration: 30.0,
+            jink_period: 1.0,
+            gun_range: 2_000.0,
+            bullet_speed: 1_000.0,
+            threat_range: 2_000.0,
+            crossing_speed: 100.0,
+            exposure_weight: 1.0,
+            candidates: Maneuver::ALL.to_vec(),
+        }
+    }
+}
+
+impl Rollout {
+    fn steps(&self) -> usize {
+        (self.horizon / self.step).round().max(1.0) as usize
+    }
+
+    fn simulate(
+        &self,
+        actor: &(impl Kinematic + Motor),
+        enemy: &impl Kinematic,
+        maneuver: Maneuver,
+        seed: u64,
+    ) -> (f64, f64) {
+        let mut ship = MockMotor {
+            position: actor.position(),
+            velocity: actor.velocity(),
+            heading: actor.heading(),
+            angular_velocity: actor.angular_velocity(),
+            max_linear_acceleration: actor.max_linear_acceleration(),
+            max_angular_acceleration: actor.max_angular_acceleration(),
+            max_angular_velocity: actor.max_angular_velocity(),
+            limits: actor.limits(),
+        };
+        let mut enemy = RandomJink::new(
+            enemy.position(),
+            enemy.velocity(),
+            self.enemy_acceleration,
+            self.jink_period,
+            seed,
+        );
+        let (mut exposure, mut solution_time) = (0.0, 0.0);
+        for _ in 0..self.steps() {
+            let linear = maneuver.direction(&ship, &enemy) * ship.max_linear_acceleration;
+            ship.velocity += linear * self.step;
+            ship.position += ship.velocity * self.step;
+            enemy.advance(self.step);
+            let offset = enemy.position - ship.position;
+            let range = offset.length();
+            if range <= 0.0 {
+                exposure += self.step;
+                continue;
+            }
+            let relative = ship.velocity - enemy.velocity;
+            let crossing = cross(offset / range, relative).abs();
+            if range <= self.threat_range {
+                exposure += self.step * (1.0 - crossing / self.crossing_speed).max(0.0);
+            }
+            if range <= self.gun_range && intercept(&ship, &enemy, self.bullet_speed).is_some() {
+                solution_time += self.step;
+            }
+        }
+        (exposure, solution_time)
+    }
+
+    pub fn evaluate(
+        &self,
+        actor: &(impl Kinematic + Motor),
+        enemy: &impl Kinematic,
+        seed: u64,
+    ) -> Vec<Score> {
+        let mut scores: Vec<Score> = self
+            .candidates
+            .iter()
+            .map(|&maneuver| Score {
+                maneuver,
+                exposure: 0.0,
+                solution_time: 0.0,
+                rollouts: 0,
+            })
+            .collect();
+        let cost = self.steps() * scores.len();
+        let mut spent = 0;
+        let mut rng = Rng::new(seed);
+        for round in 0..self.max_rollouts.max(1) {
+            if round > 0 && spent + cost > self.budget {
+                break;
+            }
+            spent += cost;
+            let seed = rng.next_u64();
+            for score in &mut scores {
+                let (exposure, solution_time) = self.simulate(actor, enemy, score.maneuver, seed);
+                score.exposure += exposure;
+                score.solution_time += solution_time;
+                score.rollouts += 1;
+            }
+        }
+        scores
+    }
+
+    pub fn best(
+        &self,
+        actor: &(impl Kinematic + Motor),
+        enemy: &impl Kinematic,
+        seed: u64,
+    ) -> Option<Score> {
+        self.evaluate(actor, enemy, seed)
+            .into_iter()
+            .max_by(|a, b| {
+                a.value(self.exposure_weight)
+                    .total_cmp(&b.value(self.exposure_weight))
+            })
+    }
+}
@@ -0,0 +1,216 @@
+use crate::context::TickContext;
+use crate::controllers::Gunnery;
+use crate::draw::NoDraw;
+use crate::hardware::Hardware;
+use crate::math::*;
+use crate::movement::*;
+use crate::perception::Beam;
+use crate::ships::Fighter;
+use crate::sim::{MockMotor, Script};
+use core::f64::consts::TAU;
+use oort_api::prelude::{Class, ScanResult};
+
+pub struct Rig {
+    pub health: f64,
+    pub class: Class,
+    ship: MockMotor,
+    target: Option<(Vec2, Vec2)>,
+    beam: Beam,
+    acceleration: Vec2,
+    angular: f64,
+    firing: bool,
+}
+
+impl Rig {
+    pub fn new(ship: MockMotor) -> Rig {
+        Rig {
+            health: 100.0,
+            class: Class::Fighter,
+            ship,
+            target: None,
+            beam: Beam {
+                heading: 0.0,
+                width: TAU,
+                min_distance: 0.0,
+                max_distance: f64::INFINITY,
+            },
+            acceleration: vec2(0.0, 0.0),
+            angular: 0.0,
+            firing: false,
+        }
+    }
+
+    pub fn prepare(&mut self, ship: &MockMotor, target: &dyn Kinematic) {
+        self.ship = *ship;
+        self.target = Some((target.position(), target.velocity()));
+        self.acceleration = vec2(0.0, 0.0);
+        self.angular = 0.0;
+        self.firing = false;
+    }
+
+    pub fn beam(&self) -> &Beam {
+        &self.beam
+    }
+
+    pub fn firing(&self) -> bool {
+        self.firing
+    }
+
+    pub fn output(&self) -> Output {
+        Output {
+            linear: self.acceleration,
+            angular: self.angular,
+        }
+    }
+
+    fn illuminated(&self, position: Vec2) -> bool {
+        let offset = position - self.ship.position;
+        let distance = offset.length();
+        distance >= self.beam.min_distance
+            && distance <= self.beam.max_distance
+            && angle_diff(self.beam.heading, offset.angle()).abs() <= self.beam.width / 2.0
+    }
+}
+
+impl Hardware for Rig {
+    fn health(&self) -> f64 {
+        self.health
+    }
+    fn scan(&mut self) -> Option<ScanResult> {
+        let (position, velocity) = self.target?;
+        self.illuminated(position).then_some(ScanResult {
+            class: self.class,
+            position,
+            velocity,
+            rssi: 0.0,
+            snr: 0.0,
+        })
+    }
+    fn aim_radar(&mut self, beam: &Beam) {
+        self.beam = *beam;
+    }
+    fn accelerate(&mut self, acceleration: Vec2) {
+        self.acceleration = acceleration;
+    }
+    fn torque(&mut self, acceleration: f64) {
+        self.angular = acceleration;
+    }
+    fn turn(&mut self, rate: f64) {
+        self.angular = (rate - self.ship.angular_velocity) / TICK_LENGTH;
+    }
+    fn fire(&mut self, _gun: usize) {
+        self.firing = true;
+    }
+}
+
+pub trait Pilot {
+    fn fly(&mut self, context: &TickContext<NoDraw>, rig: &mut Rig);
+}
+
+impl Pilot for Gunnery {
+    fn fly(&mut self, context: &TickContext<NoDraw>, rig: &mut Rig) {
+        self.drive(context, rig);
+    }
+}
+
+impl Pilot for Fighter {
+    fn fly(&mut self, context: &TickContext<NoDraw>, rig: &mut Rig) {
+        self.drive(context, rig);
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bullet {
+    position: Vec2,
+    velocity: Vec2,
+    expires: f64,
+}
+
+pub struct Scenario {
+    pub duration: f64,
+    pub bullet_speed: f64,
+    pub bullet_lifetime: f64,
+    pub reload: f64,
+    pub hit_radius: f64,
+    pub hits_to_kill: u32,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Scenario {
+            duration: 60.0,
+            bullet_speed: 1_000.0,
+            bullet_lifetime: 5.0,
+            reload: 4.0 * TICK_LENGTH,
+            hit_radius: 10.0,
+            hits_to_kill: 5,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Outcome {
+    pub killed_at: Option<f64>,
+    pub shots: u32,
+    pub hits: u32,
+}
+
+impl Scenario {
+    pub fn run(
+        &self,
+        ship: &mut MockMotor,
+        target: &mut impl Script,
+        pilot: &mut impl Pilot,
+    ) -> Outcome {
+        let mut bullets: Vec<Bullet> = Vec::new();
+        let mut outcome = Outcome {
+            killed_at: None,
+            shots: 0,
+            hits: 0,
+        };
+        let mut rig = Rig::new(*ship);
+        let mut ready_at = 0.0;
+        let ticks = (self.duration / TICK_LENGTH) as u32;
+        for tick in 0..ticks {
+            let now = tick as f64 * TICK_LENGTH;
+            rig.prepare(ship, &*target);
+            pilot.fly(&TickContext::with_draw(&*ship, now, NoDraw), &mut rig);
+            if rig.firing() && now >= ready_at {
+                bullets.push(Bullet {
+                    position: ship.position,
+                    velocity: ship.velocity + vec2(self.bullet_speed, 0.0).rotate(ship.heading),
+                    expires: now + self.bullet_lifetime,
+                });
+                outcome.shots += 1;
+                ready_at = now + self.reload;
+            }
+            ship.step(&rig.output());
+            target.advance(TICK_LENGTH);
+            let position = target.position();
+            let velocity = target.velocity();
+            bullets.retain(|bullet| {
+                let start = bullet.position - position;
+                let relative = bullet.velocity - velocity;
+                let speed_squared = relative.dot(relative);
+                let t = if speed_squared > 0.0 {
+                    (-start.dot(relative) / speed_squared).clamp(0.0, TICK_LENGTH)
+                } else {
+                    0.0
+                };
+                let hit = (start + relative * t).length() <= self.hit_radius;
+                if hit {
+                    outcome.hits += 1;
+                }
+                !hit && now < bullet.expires
+            });
+            for bullet in &mut bullets {
+                bullet.position += bullet.velocity * TICK_LENGTH;
+            }
+            if outcome.hits >= self.hits_to_kill {
+                outcome.killed_at = Some(now);
+                break;
+            }
+        }
+        outcome
+    }
+}
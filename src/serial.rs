@@ -0,0 +1,26 @@
+pub mod vec2 {
+    use crate::math::{vec2, Vec2};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Vec2, serializer: S) -> Result<S::Ok, S::Error> {
+        [value.x, value.y].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2, D::Error> {
+        let [x, y] = <[f64; 2]>::deserialize(deserializer)?;
+        Ok(vec2(x, y))
+    }
+}
+
+pub mod class {
+    use crate::contact::{class_code, class_from_code, Class};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &Class, serializer: S) -> Result<S::Ok, S::Error> {
+        class_code(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Class, D::Error> {
+        u16::deserialize(deserializer).map(class_from_code)
+    }
+}
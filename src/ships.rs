@@ -0,0 +1,709 @@
+use crate::abilities::Abilities;
+use crate::assignment::Auction;
+use crate::context::TickContext;
+use crate::defense::{PointDefense, Turret};
+use crate::draw::{DebugDraw, Layer, Layers, OortDraw};
+use crate::evasion::{Retreat, SelfPreservation};
+use crate::events::{Event, EventLog};
+use crate::fusion::Sharing;
+use crate::hardware::Hardware;
+use crate::hud::Hud;
+use crate::missile::{time_to_go, Missile, Performance, Torpedo};
+use crate::movement::*;
+use crate::perception::{RadarScheduler, Sector, Track, TrackId, Tracker, TRACK_CAPACITY};
+use crate::profile::Profile;
+use crate::profiler::{Clock, Profiler, Subsystem};
+use crate::radio::Radio;
+use crate::storage::Scratch;
+use crate::tactics::EngagementPlanner;
+use crate::targeting::intercept;
+use crate::telemetry::{Tag, Telemetry};
+use crate::time::Seconds;
+use crate::warning::{MissileWarning, Warning};
+use crate::weapons::{Assignment, Coordinator, Gun, Launcher, Volley};
+use crate::Oort;
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Behavior {
+    Waypoint,
+    Hunt,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    Search,
+    Engage(TrackId),
+}
+
+pub struct Fighter {
+    pub behavior: Behavior,
+    pub gun: Gun,
+    pub tracker: Tracker,
+    pub radar: RadarScheduler,
+    pub preservation: SelfPreservation,
+    pub abilities: Abilities,
+    pub radio: Radio,
+    pub sharing: Option<Sharing>,
+    pub auction: Option<Auction>,
+    pub telemetry: Telemetry,
+    pub hud: Hud,
+    pub layers: Layers,
+    pub profiler: Option<Profiler>,
+    pub events: EventLog,
+    pub waypoint: Vec2,
+    state: State,
+    scratch: Scratch,
+}
+
+impl Default for Fighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fighter {
+    pub fn new() -> Fighter {
+        Fighter {
+            behavior: Behavior::Hunt,
+            gun: Gun {
+                index: 0,
+                speed: 1000.0,
+                tolerance: 0.02,
+                turn_gain: 10.0,
+                max_age: 1.0,
+            },
+            tracker: Tracker::new(),
+            radar: RadarScheduler::new(),
+            preservation: SelfPreservation::new(),
+            abilities: Abilities::for_class(Class::Fighter),
+            radio: Radio::new(0),
+            sharing: None,
+            auction: None,
+            telemetry: Telemetry::default(),
+            hud: Hud::default(),
+            layers: Layers::none(),
+            profiler: None,
+            events: EventLog::default(),
+            waypoint: vec2(0.0, 0.0),
+            state: State::Search,
+            scratch: Scratch::new(),
+        }
+    }
+
+    pub fn tutorial() -> Fighter {
+        Fighter {
+            behavior: Behavior::Waypoint,
+            waypoint: target(),
+            ..Fighter::new()
+        }
+    }
+
+    pub fn with_profiler(mut self, clock: impl Clock + 'static) -> Fighter {
+        self.profiler = Some(Profiler::new(Box::new(clock)));
+        self
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn tune(&mut self, profile: &Profile) {
+        self.gun.tolerance = profile.gun_tolerance;
+        self.gun.turn_gain = profile.turn_gain;
+        self.tracker.gate = profile.track_gate;
+        self.radar.revisit = profile.radar_revisit;
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let context =
+            TickContext::with_draw(actor, current_time(), OortDraw).with_ship(id() as u16);
+        self.drive(&context, &mut Oort::default());
+    }
+
+    pub fn drive(&mut self, context: &TickContext<impl DebugDraw>, hardware: &mut impl Hardware) {
+        let mut scratch = core::mem::take(&mut self.scratch);
+        scratch.reset();
+        self.drive_in(&mut scratch, context, hardware);
+        self.scratch = scratch;
+    }
+
+    pub fn drive_in(
+        &mut self,
+        scratch: &mut Scratch,
+        context: &TickContext<impl DebugDraw>,
+        hardware: &mut impl Hardware,
+    ) {
+        if self.behavior == Behavior::Waypoint {
+            let result = Seek {
+                target: self.waypoint,
+            }
+            .execute(context);
+            hardware.accelerate(result.linear);
+            return;
+        }
+        let now = context.time;
+        if let Some(profiler) = &mut self.profiler {
+            profiler.end_tick();
+        }
+        if let Some(contact) = hardware.scan() {
+            let created = measure(&mut self.profiler, Subsystem::Tracker, || {
+                self.tracker.update_all(
+                    context.position,
+                    core::slice::from_ref(&contact),
+                    now,
+                    scratch,
+                )
+            });
+            match self.tracker.tracks().last() {
+                Some(track) if created > 0 => {
+                    self.events.push(
+                        now,
+                        Event::TrackCreated {
+                            id: track.id,
+                            class: contact.class,
+                        },
+                    );
+                    self.telemetry.debug(
+                        Tag::Perception,
+                        format_args!(
+                            "track {} <- {:?} at {:?}",
+                            track.id, contact.class, contact.position
+                        ),
+                    )
+                }
+                _ if !Tracker::<TRACK_CAPACITY>::valid(contact.position, contact.velocity, now) => {
+                    self.telemetry.warn(
+                        Tag::Perception,
+                        format_args!("rejected contact {:?}", contact.class),
+                    )
+                }
+                _ => {}
+            }
+        }
+        self.radio.poll(context.tick);
+        for (header, _) in self.radio.inbox() {
+            self.events.push(
+                now,
+                Event::MessageReceived {
+                    sender: header.sender,
+                    kind: header.kind,
+                },
+            );
+        }
+        if let Some(sharing) = &mut self.sharing {
+            sharing.receive(&mut self.tracker, self.radio.inbox(), now);
+            if let Some(packet) = sharing.broadcast(&self.tracker, now) {
+                self.radio.queue(packet);
+            }
+        }
+        let lost = measure(&mut self.profiler, Subsystem::Tracker, || {
+            self.tracker.prune(now)
+        });
+        for &id in lost.iter() {
+            self.events.push(now, Event::TrackLost { id });
+        }
+        let beam = measure(&mut self.profiler, Subsystem::Radar, || {
+            self.radar
+                .next(context.position, self.tracker.tracks(), now)
+        });
+        hardware.aim_radar(&beam);
+        let previous = self.state;
+        self.state = self.next_state(context);
+        if let Some(auction) = &mut self.auction {
+            auction.receive(self.radio.inbox(), now);
+            self.state = auction
+                .resolve(context.ship, context, self.tracker.tracks(), now)
+                .map_or(State::Search, State::Engage);
+            if let Some(packet) = auction.broadcast(self.tracker.tracks()) {
+                self.radio.queue(packet);
+            }
+        }
+        if self.state != previous {
+            let target = |state: State| match state {
+                State::Engage(id) => Some(id),
+                State::Search => None,
+            };
+            self.events.push(
+                now,
+                Event::TargetSwitched {
+                    from: target(previous),
+                    to: target(self.state),
+                },
+            );
+            self.telemetry.info(
+                Tag::Tactics,
+                format_args!("{:?} -> {:?}", previous, self.state),
+            );
+        }
+        self.radio.flush(context.tick);
+        let output = match self.state {
+            State::Engage(id) => self.engage(context, hardware, id),
+            State::Search => None,
+        };
+        if self.layers.enabled(Layer::Hud) {
+            let (strategy, output) = output.unwrap_or(("search", Output::zero()));
+            self.hud
+                .with_draw(self.layers.on(Layer::Hud, &context.draw))
+                .draw(
+                    context,
+                    self.tracker.tracks(),
+                    now,
+                    &format!("{:?}", self.state),
+                    &[(strategy, 1.0)],
+                    &output,
+                );
+        }
+    }
+
+    fn engage(
+        &mut self,
+        context: &TickContext<impl DebugDraw>,
+        hardware: &mut impl Hardware,
+        id: TrackId,
+    ) -> Option<(&'static str, Output)> {
+        let now = context.time;
+        let track = self.tracker.get(id).map(|track| track.predict(now))?;
+        let range = distance_to(context, &track);
+        let (strategy, result) = measure(&mut self.profiler, Subsystem::Movement, || {
+            if self.preservation.update(hardware.health(), Some(range)) {
+                if self.abilities.boost(now, f64::INFINITY) {
+                    self.events.push(now, Event::AbilityUsed(Ability::Boost));
+                }
+                let result = Retreat {
+                    threat: track.position,
+                    obstacles: self.tracker.tracks(),
+                    cover_radius: 100.0,
+                    cover_range: 2_000.0,
+                    side: self.preservation.side(now),
+                    deflection: std::f64::consts::FRAC_PI_4,
+                }
+                .execute(context);
+                hardware.accelerate(result.linear);
+                ("retreat", result)
+            } else {
+                let intercept = time_to_go(context, &track).map_or(0.0, Seconds::get);
+                if self.abilities.boost(now, intercept) {
+                    self.events.push(now, Event::AbilityUsed(Ability::Boost));
+                }
+                let result = Seek {
+                    target: track.position,
+                }
+                .execute(context);
+                hardware.accelerate(result.linear);
+                ("seek", result)
+            }
+        });
+        let fired = measure(&mut self.profiler, Subsystem::Targeting, || {
+            self.gun.engage_track_with(hardware, context, &track)
+        });
+        if fired {
+            self.events.push(
+                now,
+                Event::WeaponFired {
+                    index: self.gun.index,
+                    target: id,
+                },
+            );
+        }
+        Some((strategy, result))
+    }
+
+    fn next_state(&self, actor: &impl Kinematic) -> State {
+        nearest_hull(actor, self.tracker.tracks(), self.state)
+    }
+}
+
+fn measure<R>(
+    profiler: &mut Option<Profiler>,
+    subsystem: Subsystem,
+    work: impl FnOnce() -> R,
+) -> R {
+    match profiler {
+        Some(profiler) => profiler.measure(subsystem, work),
+        None => work(),
+    }
+}
+
+fn distance_to(actor: &impl Kinematic, track: &Track) -> f64 {
+    actor.position().distance(track.position)
+}
+
+pub struct Frigate {
+    pub main_gun: Gun,
+    pub launcher: Launcher,
+    pub point_defense: PointDefense,
+    pub tracker: Tracker,
+    pub radar: RadarScheduler,
+    pub range: f64,
+    pub planner: EngagementPlanner,
+    state: State,
+    leakers: Vec<Warning>,
+    scratch: Scratch,
+}
+
+impl Default for Frigate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Frigate {
+    pub fn new() -> Frigate {
+        let turret = |index| Turret {
+            index,
+            bullet_speed: 1000.0,
+            range: 2_500.0,
+        };
+        Frigate {
+            main_gun: Gun {
+                index: 0,
+                speed: 4000.0,
+                tolerance: 0.01,
+                turn_gain: 5.0,
+                max_age: 1.5,
+            },
+            launcher: Launcher::new(
+                3,
+                Performance {
+                    acceleration: 300.0,
+                    delta_v: 3_000.0,
+                    lifetime: 20.0,
+                },
+                1,
+            ),
+            point_defense: PointDefense {
+                turrets: vec![turret(1), turret(2)],
+                warning: MissileWarning {
+                    danger_radius: 100.0,
+                    horizon: Seconds(10.0),
+                    bullet_speed: 900.0,
+                },
+                leak_time: 0.5,
+                arc: Sector::full(),
+            },
+            tracker: Tracker::new(),
+            radar: RadarScheduler::new(),
+            range: 5_000.0,
+            planner: EngagementPlanner::default(),
+            state: State::Search,
+            leakers: Vec::new(),
+            scratch: Scratch::new(),
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn leakers(&self) -> &[Warning] {
+        &self.leakers
+    }
+
+    pub fn tune(&mut self, profile: &Profile) {
+        self.main_gun.turn_gain = profile.turn_gain;
+        self.range = profile.engagement_range;
+        self.point_defense.warning.danger_radius = profile.danger_radius;
+        self.tracker.gate = profile.track_gate;
+        self.radar.revisit = profile.radar_revisit;
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let mut scratch = core::mem::take(&mut self.scratch);
+        scratch.reset();
+        self.tick_in(&mut scratch, actor);
+        self.scratch = scratch;
+    }
+
+    pub fn tick_in(&mut self, scratch: &mut Scratch, actor: &(impl Kinematic + Motor)) {
+        let now = current_time();
+        if let Some(contact) = scan() {
+            self.tracker
+                .update_all(actor.position(), &[contact], now, scratch);
+        }
+        for &id in self.tracker.prune(now).iter() {
+            self.launcher.forget(id);
+        }
+        self.launcher.reassign(actor, self.tracker.tracks(), now);
+        self.radar
+            .next(actor.position(), self.tracker.tracks(), now)
+            .apply();
+        let (engagements, leakers) = self.point_defense.assign(actor, self.tracker.tracks());
+        for engagement in &engagements {
+            aim(engagement.turret, engagement.heading);
+            fire(engagement.turret);
+        }
+        self.leakers = leakers;
+        self.state = nearest_hull(actor, self.tracker.tracks(), self.state);
+        let State::Engage(id) = self.state else {
+            return;
+        };
+        let Some(track) = self.tracker.get(id).map(|track| track.predict(now)) else {
+            return;
+        };
+        let reload = reload_ticks(self.launcher.index) as f64 * TICK_LENGTH;
+        let performance = &self.launcher.performance;
+        let reach = performance.reach(performance.lifetime);
+        let situation = self
+            .planner
+            .assess(actor, &track, reach, reload, &self.leakers);
+        let result = KeepDistance {
+            target: track.position,
+            distance: self.planner.distance(&situation),
+        }
+        .execute(actor);
+        accelerate(result.linear);
+        self.main_gun.engage_track(actor, &track);
+        let fired = !track.is_decoy() && self.launcher.engage(actor, &track);
+        self.planner.update(&situation, fired);
+        for turret in &self.point_defense.turrets {
+            if engagements.iter().any(|e| e.turret == turret.index) {
+                continue;
+            }
+            if let Some(solution) = intercept(actor, &track, turret.bullet_speed) {
+                aim(turret.index, solution.heading);
+                if solution.time.get() * turret.bullet_speed <= turret.range {
+                    fire(turret.index);
+                }
+            }
+        }
+    }
+}
+
+fn nearest_hull(actor: &impl Kinematic, tracks: &[Track], current: State) -> State {
+    match current {
+        State::Engage(id) if tracks.iter().any(|track| track.id == id) => current,
+        _ => tracks
+            .iter()
+            .filter(|track| track.is_hull())
+            .min_by(|a, b| distance_to(actor, a).total_cmp(&distance_to(actor, b)))
+            .map_or(State::Search, |track| State::Engage(track.id)),
+    }
+}
+
+pub struct Cruiser {
+    pub turret: Turret,
+    pub missiles: Volley,
+    pub torpedoes: Volley,
+    pub point_defense: PointDefense,
+    pub coordinator: Coordinator,
+    pub tracker: Tracker,
+    pub radar: RadarScheduler,
+    pub range: f64,
+    pub derating: f64,
+    pub abilities: Abilities,
+    assignment: Assignment,
+    leakers: Vec<Warning>,
+    scratch: Scratch,
+}
+
+impl Default for Cruiser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cruiser {
+    pub fn new() -> Cruiser {
+        let launcher = |index, channel, acceleration, delta_v, lifetime| {
+            Launcher::new(
+                index,
+                Performance {
+                    acceleration,
+                    delta_v,
+                    lifetime,
+                },
+                channel,
+            )
+        };
+        let turret = Turret {
+            index: 0,
+            bullet_speed: 2000.0,
+            range: 4_000.0,
+        };
+        Cruiser {
+            turret,
+            missiles: Volley {
+                launchers: vec![
+                    launcher(1, 1, 300.0, 3_000.0, 20.0),
+                    launcher(2, 1, 300.0, 3_000.0, 20.0),
+                ],
+                min_ready: 2,
+            },
+            torpedoes: Volley {
+                launchers: vec![launcher(3, 2, 70.0, 4_000.0, 60.0)],
+                min_ready: 1,
+            },
+            point_defense: PointDefense {
+                turrets: vec![turret],
+                warning: MissileWarning {
+                    danger_radius: 200.0,
+                    horizon: Seconds(15.0),
+                    bullet_speed: 900.0,
+                },
+                leak_time: 1.0,
+                arc: Sector::full(),
+            },
+            coordinator: Coordinator {
+                gun_range: 4_000.0,
+                missile_range: 20_000.0,
+            },
+            tracker: Tracker::new(),
+            radar: RadarScheduler::new(),
+            range: 8_000.0,
+            derating: 0.5,
+            abilities: Abilities::for_class(Class::Cruiser),
+            assignment: Assignment::default(),
+            leakers: Vec::new(),
+            scratch: Scratch::new(),
+        }
+    }
+
+    pub fn assignment(&self) -> Assignment {
+        self.assignment
+    }
+
+    pub fn leakers(&self) -> &[Warning] {
+        &self.leakers
+    }
+
+    pub fn tune(&mut self, profile: &Profile) {
+        self.range = profile.engagement_range;
+        self.point_defense.warning.danger_radius = profile.danger_radius;
+        self.tracker.gate = profile.track_gate;
+        self.radar.revisit = profile.radar_revisit;
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let mut scratch = core::mem::take(&mut self.scratch);
+        scratch.reset();
+        self.tick_in(&mut scratch, actor);
+        self.scratch = scratch;
+    }
+
+    pub fn tick_in(&mut self, scratch: &mut Scratch, actor: &(impl Kinematic + Motor)) {
+        let now = current_time();
+        if let Some(contact) = scan() {
+            self.tracker
+                .update_all(actor.position(), &[contact], now, scratch);
+        }
+        for &id in self.tracker.prune(now).iter() {
+            self.missiles.forget(id);
+            self.torpedoes.forget(id);
+        }
+        self.missiles.reassign(actor, self.tracker.tracks(), now);
+        self.torpedoes.reassign(actor, self.tracker.tracks(), now);
+        self.radar
+            .next(actor.position(), self.tracker.tracks(), now)
+            .apply();
+        self.assignment = self.coordinator.assign(actor, self.tracker.tracks());
+        let predict = |id: Option<TrackId>| {
+            id.and_then(|id| self.tracker.get(id))
+                .map(|track| track.predict(now))
+        };
+        let (engagements, leakers) = self.point_defense.assign(actor, self.tracker.tracks());
+        self.abilities.shield(now, &leakers);
+        self.leakers = leakers;
+        if let Some(engagement) = engagements.first() {
+            aim(engagement.turret, engagement.heading);
+            fire(engagement.turret);
+        } else if let Some(track) = predict(self.assignment.guns) {
+            if let Some(solution) = intercept(actor, &track, self.turret.bullet_speed) {
+                aim(self.turret.index, solution.heading);
+                fire(self.turret.index);
+            }
+        }
+        if let Some(track) = predict(self.assignment.missiles) {
+            self.missiles.engage(actor, &track);
+            let result = KeepDistance {
+                target: track.position,
+                distance: self.range,
+            }
+            .execute(&Derated {
+                actor,
+                factor: self.derating,
+            });
+            accelerate(result.linear);
+        }
+        if let Some(track) = predict(self.assignment.torpedoes) {
+            self.torpedoes.engage(actor, &track);
+        }
+    }
+}
+
+pub struct Brain {
+    pub role: Role,
+    scratch: Scratch,
+}
+
+pub enum Role {
+    Fighter(Box<Fighter>),
+    Frigate(Frigate),
+    Cruiser(Cruiser),
+    Missile(Missile),
+    Torpedo(Torpedo),
+    Idle,
+}
+
+impl Default for Brain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Brain {
+    pub fn new() -> Brain {
+        Brain::tuned(&Profile::current())
+    }
+
+    pub fn tuned(profile: &Profile) -> Brain {
+        let mut brain = Brain::for_class(class());
+        brain.tune(profile);
+        brain
+    }
+
+    pub fn tune(&mut self, profile: &Profile) {
+        match &mut self.role {
+            Role::Fighter(fighter) => fighter.tune(profile),
+            Role::Frigate(frigate) => frigate.tune(profile),
+            Role::Cruiser(cruiser) => cruiser.tune(profile),
+            Role::Missile(missile) => missile.gain = profile.guidance_gain,
+            Role::Torpedo(torpedo) => torpedo.gain = profile.guidance_gain.min(torpedo.gain),
+            Role::Idle => {}
+        }
+    }
+
+    pub fn for_class(class: Class) -> Brain {
+        let role = match class {
+            Class::Fighter => Role::Fighter(Box::default()),
+            Class::Frigate => Role::Frigate(Frigate::new()),
+            Class::Cruiser => Role::Cruiser(Cruiser::new()),
+            Class::Missile => Role::Missile(Missile::new(1)),
+            Class::Torpedo => Role::Torpedo(Torpedo::new(2)),
+            _ => Role::Idle,
+        };
+        Brain {
+            role,
+            scratch: Scratch::new(),
+        }
+    }
+
+    pub fn tick(&mut self, actor: &(impl Kinematic + Motor)) {
+        let context =
+            TickContext::with_draw(actor, current_time(), OortDraw).with_ship(id() as u16);
+        self.drive(&context, &mut Oort::default());
+    }
+
+    pub fn drive(&mut self, context: &TickContext<impl DebugDraw>, hardware: &mut impl Hardware) {
+        self.scratch.reset();
+        let scratch = &mut self.scratch;
+        match &mut self.role {
+            Role::Fighter(fighter) => fighter.drive_in(scratch, context, hardware),
+            Role::Frigate(frigate) => frigate.tick_in(scratch, context),
+            Role::Cruiser(cruiser) => cruiser.tick_in(scratch, context),
+            Role::Missile(missile) => missile.tick(context),
+            Role::Torpedo(torpedo) => torpedo.tick(context),
+            Role::Idle => {}
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use crate::math::*;
+use crate::movement::*;
+use crate::random::Rng;
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, Debug)]
+pub struct MockMotor {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub heading: f64,
+    pub angular_velocity: f64,
+    pub max_linear_acceleration: f64,
+    pub max_angular_acceleration: f64,
+    pub max_angular_velocity: f64,
+    pub limits: SteeringLimits,
+}
+
+impl Default for MockMotor {
+    fn default() -> Self {
+        MockMotor::fighter(vec2(0.0, 0.0))
+    }
+}
+
+impl MockMotor {
+    pub fn fighter(position: Vec2) -> MockMotor {
+        MockMotor {
+            position,
+            velocity: vec2(0.0, 0.0),
+            heading: 0.0,
+            angular_velocity: 0.0,
+            max_linear_acceleration: 60.0,
+            max_angular_acceleration: core::f64::consts::TAU,
+            max_angular_velocity: core::f64::consts::TAU,
+            limits: SteeringLimits::default(),
+        }
+    }
+
+    pub fn missile(position: Vec2, velocity: Vec2) -> MockMotor {
+        MockMotor {
+            velocity,
+            max_linear_acceleration: 300.0,
+            ..MockMotor::fighter(position)
+        }
+    }
+
+    pub fn step(&mut self, output: &Output) {
+        let linear = output.linear;
+        let length = linear.length();
+        let linear = if length > self.max_linear_acceleration {
+            linear * (self.max_linear_acceleration / length)
+        } else {
+            linear
+        };
+        let angular = output.angular.clamp(
+            -self.max_angular_acceleration,
+            self.max_angular_acceleration,
+        );
+        self.velocity += linear * TICK_LENGTH;
+        self.position += self.velocity * TICK_LENGTH;
+        self.angular_velocity = (self.angular_velocity + angular * TICK_LENGTH)
+            .clamp(-self.max_angular_velocity, self.max_angular_velocity);
+        self.heading =
+            (self.heading + self.angular_velocity * TICK_LENGTH).rem_euclid(core::f64::consts::TAU);
+    }
+
+    pub fn tick(&mut self, strategy: &impl Move) -> Output {
+        let output = strategy.execute(&*self);
+        self.step(&output);
+        output
+    }
+
+    pub fn run(&mut self, strategy: &impl Move, ticks: usize) -> Vec<MockMotor> {
+        (0..ticks)
+            .map(|_| {
+                self.tick(strategy);
+                *self
+            })
+            .collect()
+    }
+}
+
+impl Kinematic for MockMotor {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.heading
+    }
+    fn angular_velocity(&self) -> f64 {
+        self.angular_velocity
+    }
+}
+
+impl Motor for MockMotor {
+    fn max_linear_acceleration(&self) -> f64 {
+        self.max_linear_acceleration
+    }
+    fn max_angular_acceleration(&self) -> f64 {
+        self.max_angular_acceleration
+    }
+    fn max_angular_velocity(&self) -> f64 {
+        self.max_angular_velocity
+    }
+    fn limits(&self) -> SteeringLimits {
+        self.limits
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Body {
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+impl Body {
+    pub fn step(&mut self) {
+        self.advance(TICK_LENGTH);
+    }
+}
+
+pub trait Script: Kinematic {
+    fn advance(&mut self, dt: f64);
+}
+
+impl Script for Body {
+    fn advance(&mut self, dt: f64) {
+        self.position += self.velocity * dt;
+    }
+}
+
+impl Kinematic for Body {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.velocity.angle()
+    }
+    fn angular_velocity(&self) -> f64 {
+        0.0
+    }
+}
+
+pub fn chase<M: Move>(
+    chaser: &mut MockMotor,
+    target: &mut Body,
+    strategy: impl Fn(Body) -> M,
+    ticks: usize,
+) -> Option<usize> {
+    let mut closest = f64::INFINITY;
+    for tick in 0..ticks {
+        chaser.tick(&strategy(*target));
+        target.step();
+        let distance = chaser.position.distance(target.position);
+        if distance > closest {
+            return Some(tick);
+        }
+        closest = distance;
+    }
+    None
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct ConstantAcceleration {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub acceleration: Vec2,
+}
+
+impl Script for ConstantAcceleration {
+    fn advance(&mut self, dt: f64) {
+        self.position += self.velocity * dt + self.acceleration * (0.5 * dt * dt);
+        self.velocity += self.acceleration * dt;
+    }
+}
+
+impl Kinematic for ConstantAcceleration {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.velocity.angle()
+    }
+    fn angular_velocity(&self) -> f64 {
+        0.0
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CoordinatedTurn {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub turn_rate: f64,
+}
+
+impl Script for CoordinatedTurn {
+    fn advance(&mut self, dt: f64) {
+        let angle = self.turn_rate * dt;
+        if angle.abs() < 1e-9 {
+            self.position += self.velocity * dt;
+            return;
+        }
+        let (sin, cos) = angle.sin_cos();
+        let v = self.velocity;
+        let displacement = vec2(v.x * sin - v.y * (1.0 - cos), v.x * (1.0 - cos) + v.y * sin);
+        self.position += displacement / self.turn_rate;
+        self.velocity = v.rotate(angle);
+    }
+}
+
+impl Kinematic for CoordinatedTurn {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.velocity.angle()
+    }
+    fn angular_velocity(&self) -> f64 {
+        self.turn_rate
+    }
+}
+
+pub struct RandomJink {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub acceleration: f64,
+    pub period: f64,
+    rng: Rng,
+    lateral: f64,
+    elapsed: f64,
+}
+
+impl RandomJink {
+    pub fn new(
+        position: Vec2,
+        velocity: Vec2,
+        acceleration: f64,
+        period: f64,
+        seed: u64,
+    ) -> RandomJink {
+        RandomJink {
+            position,
+            velocity,
+            acceleration,
+            period,
+            rng: Rng::new(seed),
+            lateral: 0.0,
+            elapsed: f64::INFINITY,
+        }
+    }
+
+    pub fn lateral(&self) -> f64 {
+        self.lateral
+    }
+}
+
+impl Script for RandomJink {
+    fn advance(&mut self, dt: f64) {
+        if self.elapsed >= self.period {
+            self.lateral = self.rng.uniform(-self.acceleration, self.acceleration);
+            self.elapsed = 0.0;
+        }
+        self.elapsed += dt;
+        let speed = self.velocity.length();
+        let acceleration = if speed > 0.0 {
+            perpendicular(self.velocity) * (self.lateral / speed)
+        } else {
+            vec2(0.0, 0.0)
+        };
+        self.position += self.velocity * dt + acceleration * (0.5 * dt * dt);
+        self.velocity += acceleration * dt;
+    }
+}
+
+impl Kinematic for RandomJink {
+    fn position(&self) -> Vec2 {
+        self.position
+    }
+    fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+    fn heading(&self) -> f64 {
+        self.velocity.angle()
+    }
+    fn angular_velocity(&self) -> f64 {
+        let speed = self.velocity.length();
+        if speed > 0.0 {
+            self.lateral / speed
+        } else {
+            0.0
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::math::Vec2;
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+pub struct FixedVec<T, const N: usize> {
+    slots: [T; N],
+    len: usize,
+}
+
+impl<T: Clone, const N: usize> FixedVec<T, N> {
+    pub fn new(fill: T) -> FixedVec<T, N> {
+        FixedVec {
+            slots: core::array::from_fn(|_| fill.clone()),
+            len: 0,
+        }
+    }
+}
+
+impl<T, const N: usize> FixedVec<T, N> {
+    pub const CAPACITY: usize = N;
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(value);
+        }
+        self.slots[self.len] = value;
+        self.len += 1;
+        Ok(())
+    }
+
+    pub fn swap_remove(&mut self, index: usize) {
+        assert!(index < self.len, "index {} out of bounds", index);
+        self.len -= 1;
+        self.slots.swap(index, self.len);
+    }
+
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        let mut kept = 0;
+        for index in 0..self.len {
+            if keep(&self.slots[index]) {
+                self.slots.swap(kept, index);
+                kept += 1;
+            }
+        }
+        self.len = kept;
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl<T, const N: usize> Deref for FixedVec<T, N> {
+    type Target = [T];
+    fn deref(&self) -> &[T] {
+        &self.slots[..self.len]
+    }
+}
+
+impl<T, const N: usize> DerefMut for FixedVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        &mut self.slots[..self.len]
+    }
+}
+
+pub struct Pool<T> {
+    buffer: Vec<T>,
+    high_water: usize,
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Pool::with_capacity(0)
+    }
+}
+
+impl<T> Pool<T> {
+    pub fn with_capacity(capacity: usize) -> Pool<T> {
+        Pool {
+            buffer: Vec::with_capacity(capacity),
+            high_water: 0,
+        }
+    }
+
+    pub fn alloc(&mut self, len: usize, fill: T) -> &mut [T]
+    where
+        T: Clone,
+    {
+        let start = self.buffer.len();
+        self.buffer.resize(start + len, fill);
+        self.high_water = self.high_water.max(self.buffer.len());
+        &mut self.buffer[start..]
+    }
+
+    pub fn extend(&mut self, items: impl IntoIterator<Item = T>) -> &mut [T] {
+        let start = self.buffer.len();
+        self.buffer.extend(items);
+        self.high_water = self.high_water.max(self.buffer.len());
+        &mut self.buffer[start..]
+    }
+
+    pub fn reset(&mut self) {
+        self.buffer.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    pub fn high_water(&self) -> usize {
+        self.high_water
+    }
+}
+
+#[derive(Default)]
+pub struct Scratch {
+    pub vectors: Pool<Vec2>,
+    pub scalars: Pool<f64>,
+    pub indices: Pool<usize>,
+    pub flags: Pool<bool>,
+}
+
+impl Scratch {
+    pub fn new() -> Scratch {
+        Scratch::with_capacity(256)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Scratch {
+        Scratch {
+            vectors: Pool::with_capacity(capacity),
+            scalars: Pool::with_capacity(capacity),
+            indices: Pool::with_capacity(capacity),
+            flags: Pool::with_capacity(capacity),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.vectors.reset();
+        self.scalars.reset();
+        self.indices.reset();
+        self.flags.reset();
+    }
+}
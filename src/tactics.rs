@@ -0,0 +1,681 @@
+use crate::fleet::{Friendly, Role};
+use crate::missile::{launch_solution, LaunchSolution, Performance};
+use crate::movement::*;
+use crate::perception::{Track, TrackId};
+use crate::radio::{Header, Packet, RoleAssignment};
+use crate::warning::Warning;
+use crate::weapons::Coordinator;
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+pub struct PincerOrder {
+    pub element: u16,
+    pub target: Vec2,
+    pub bearing: f64,
+    pub strike_time: f64,
+}
+
+pub struct Pincer {
+    pub spread: f64,
+    pub standoff: f64,
+    pub strike_speed: f64,
+}
+
+impl Default for Pincer {
+    fn default() -> Self {
+        Pincer {
+            spread: std::f64::consts::FRAC_PI_2,
+            standoff: 3_000.0,
+            strike_speed: 300.0,
+        }
+    }
+}
+
+impl Pincer {
+    pub fn element(rank: usize) -> u16 {
+        (rank % 2) as u16
+    }
+
+    pub fn plan(&self, origin: Vec2, target: Vec2, strike_time: f64) -> [PincerOrder; 2] {
+        let approach = (origin - target).angle();
+        [1.0, -1.0].map(|side| PincerOrder {
+            element: if side > 0.0 { 0 } else { 1 },
+            target,
+            bearing: approach + side * self.spread / 2.0,
+            strike_time,
+        })
+    }
+
+    pub fn orders(&self, origin: Vec2, target: Vec2, strike_time: f64) -> Vec<Packet> {
+        self.plan(origin, target, strike_time)
+            .into_iter()
+            .map(Packet::Pincer)
+            .collect()
+    }
+
+    pub fn order_for(element: u16, inbox: &[(Header, Packet)]) -> Option<PincerOrder> {
+        inbox.iter().rev().find_map(|(_, packet)| match packet {
+            Packet::Pincer(order) if order.element == element => Some(*order),
+            _ => None,
+        })
+    }
+
+    pub fn waypoint(&self, order: &PincerOrder) -> Vec2 {
+        order.target + vec2(self.standoff, 0.0).rotate(order.bearing)
+    }
+}
+
+pub struct PincerApproach<'a> {
+    pub pincer: &'a Pincer,
+    pub order: PincerOrder,
+    pub now: f64,
+}
+
+impl Move for PincerApproach<'_> {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let max_acceleration = actor.max_linear_acceleration();
+        let release = self.order.strike_time - self.pincer.standoff / self.pincer.strike_speed;
+        let waypoint = self.pincer.waypoint(&self.order);
+        let offset = waypoint - actor.position();
+        if self.now >= release {
+            return Seek {
+                target: self.order.target,
+            }
+            .execute(actor);
+        }
+        let time_left = (release - self.now).max(TICK_LENGTH);
+        let desired = offset / time_left;
+        let correction = (desired - actor.velocity()) / TICK_LENGTH;
+        let length = correction.length();
+        Output {
+            linear: if length > max_acceleration {
+                correction * (max_acceleration / length)
+            } else {
+                correction
+            },
+            angular: 0.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PriorityKill {
+    pub designation: u16,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+pub struct FocusFire {
+    pub range: f64,
+    designation: u16,
+    target: Option<TrackId>,
+}
+
+impl Default for FocusFire {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FocusFire {
+    pub fn new() -> FocusFire {
+        FocusFire {
+            range: 10_000.0,
+            designation: 0,
+            target: None,
+        }
+    }
+
+    pub fn target(&self) -> Option<TrackId> {
+        self.target
+    }
+
+    pub fn designate(&mut self, origin: Vec2, fused: &[Track]) -> Option<Packet> {
+        let alive = self
+            .target
+            .is_some_and(|id| fused.iter().any(|track| track.id == id));
+        if !alive {
+            self.target = fused
+                .iter()
+                .filter(|track| track.is_hull() && track.position.distance(origin) < self.range)
+                .max_by(|a, b| {
+                    let score = |track: &Track| {
+                        Coordinator::value(track.class) / track.position.distance(origin).max(1.0)
+                    };
+                    score(a).total_cmp(&score(b))
+                })
+                .map(|track| track.id);
+            self.designation = self.designation.wrapping_add(1);
+        }
+        let track = fused.iter().find(|track| Some(track.id) == self.target)?;
+        Some(Packet::Priority(PriorityKill {
+            designation: self.designation,
+            position: track.position,
+            velocity: track.velocity,
+        }))
+    }
+}
+
+pub struct Focus {
+    pub bonus: f64,
+    pub gate: f64,
+    pub timeout: f64,
+    designated: Option<(PriorityKill, f64)>,
+}
+
+impl Default for Focus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Focus {
+    pub fn new() -> Focus {
+        Focus {
+            bonus: 10.0,
+            gate: 300.0,
+            timeout: 2.0,
+            designated: None,
+        }
+    }
+
+    pub fn observe(&mut self, inbox: &[(Header, Packet)], now: f64) {
+        for (_, packet) in inbox {
+            if let Packet::Priority(kill) = packet {
+                self.designated = Some((*kill, now));
+            }
+        }
+        if self
+            .designated
+            .is_some_and(|(_, received)| now - received > self.timeout)
+        {
+            self.designated = None;
+        }
+    }
+
+    pub fn designated(&self, now: f64) -> Option<Vec2> {
+        self.designated
+            .map(|(kill, received)| kill.position + kill.velocity * (now - received))
+    }
+
+    pub fn priority(&self, track: &Track, score: f64, now: f64) -> f64 {
+        match self.designated(now) {
+            Some(position) if track.position.distance(position) < self.gate => score * self.bonus,
+            _ => score,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct VolleyOrder {
+    pub ship: u16,
+    pub target: Vec2,
+    pub launch_time: f64,
+    pub arrival: f64,
+}
+
+pub struct VolleyPlan {
+    pub margin: f64,
+    pub min_spread: f64,
+}
+
+impl Default for VolleyPlan {
+    fn default() -> Self {
+        VolleyPlan {
+            margin: 1.0,
+            min_spread: std::f64::consts::FRAC_PI_6,
+        }
+    }
+}
+
+impl VolleyPlan {
+    pub fn plan(
+        &self,
+        shooters: &[(u16, Friendly)],
+        target: &impl Kinematic,
+        missile: &Performance,
+        now: f64,
+    ) -> Vec<VolleyOrder> {
+        let flights: Vec<(u16, f64, f64)> = shooters
+            .iter()
+            .filter_map(|(ship, shooter)| {
+                let solution = launch_solution(shooter, target, missile)?;
+                let bearing = (shooter.position - target.position()).angle();
+                Some((*ship, solution.intercept_time, bearing))
+            })
+            .collect();
+        if flights.len() > 1 && Self::spread(&flights) < self.min_spread {
+            return Vec::new();
+        }
+        let Some(longest) = flights.iter().map(|(_, time, _)| *time).reduce(f64::max) else {
+            return Vec::new();
+        };
+        let arrival = now + longest + self.margin;
+        flights
+            .into_iter()
+            .map(|(ship, time, _)| VolleyOrder {
+                ship,
+                target: target.position(),
+                launch_time: arrival - time,
+                arrival,
+            })
+            .collect()
+    }
+
+    fn spread(flights: &[(u16, f64, f64)]) -> f64 {
+        flights
+            .iter()
+            .flat_map(|(_, _, a)| flights.iter().map(|(_, _, b)| angle_diff(*a, *b).abs()))
+            .fold(0.0, f64::max)
+    }
+}
+
+pub struct VolleyTiming {
+    pub window: f64,
+    pub tolerance: f64,
+    order: Option<VolleyOrder>,
+}
+
+impl Default for VolleyTiming {
+    fn default() -> Self {
+        VolleyTiming {
+            window: 0.5,
+            tolerance: 1.0,
+            order: None,
+        }
+    }
+}
+
+impl VolleyTiming {
+    pub fn observe(&mut self, me: u16, inbox: &[(Header, Packet)]) {
+        for (_, packet) in inbox {
+            if let Packet::Volley(order) = packet {
+                if order.ship == me {
+                    self.order = Some(*order);
+                }
+            }
+        }
+    }
+
+    pub fn order(&self) -> Option<&VolleyOrder> {
+        self.order.as_ref()
+    }
+
+    pub fn due(&mut self, now: f64) -> Option<VolleyOrder> {
+        let order = self.order?;
+        if now < order.launch_time {
+            return None;
+        }
+        self.order = None;
+        (now - order.launch_time <= self.window).then_some(order)
+    }
+
+    pub fn launch(
+        &mut self,
+        actor: &impl Kinematic,
+        target: &impl Kinematic,
+        missile: &Performance,
+        now: f64,
+    ) -> Option<LaunchSolution> {
+        let order = self.due(now)?;
+        let solution = launch_solution(actor, target, missile)?;
+        ((now + solution.intercept_time - order.arrival).abs() <= self.tolerance)
+            .then_some(solution)
+    }
+}
+
+pub struct Withdrawal {
+    pub loss_threshold: f64,
+    pub rally: Vec2,
+    pub swap_period: f64,
+    strength: usize,
+    started: Option<f64>,
+}
+
+impl Withdrawal {
+    pub fn new(rally: Vec2) -> Withdrawal {
+        Withdrawal {
+            loss_threshold: 0.4,
+            rally,
+            swap_period: 5.0,
+            strength: 0,
+            started: None,
+        }
+    }
+
+    pub fn active(&self) -> bool {
+        self.started.is_some()
+    }
+
+    pub fn update(&mut self, alive: usize, now: f64) -> bool {
+        self.strength = self.strength.max(alive);
+        let losses = 1.0 - alive as f64 / self.strength.max(1) as f64;
+        if self.started.is_none() && losses >= self.loss_threshold {
+            self.started = Some(now);
+        }
+        self.active()
+    }
+
+    pub fn rearguard(&self, rank: usize, count: usize, now: f64) -> bool {
+        let Some(started) = self.started else {
+            return false;
+        };
+        let phase = ((now - started) / self.swap_period) as usize;
+        let covering = rank < count.div_ceil(2);
+        covering == phase.is_multiple_of(2)
+    }
+
+    pub fn roles(&self, members: &[Friendly], threat: Vec2, now: f64) -> Vec<(u16, Role)> {
+        let axis = (threat - self.rally).normalize();
+        let exposure = |member: &Friendly| (member.position - self.rally).dot(axis);
+        let mut ranked: Vec<&Friendly> = members.iter().collect();
+        ranked.sort_by(|a, b| exposure(b).total_cmp(&exposure(a)));
+        members
+            .iter()
+            .map(|member| {
+                let rank = ranked
+                    .iter()
+                    .position(|other| other.ship == member.ship)
+                    .unwrap_or(0);
+                let role = if self.rearguard(rank, members.len(), now) {
+                    Role::Rearguard
+                } else {
+                    Role::Wingman
+                };
+                (member.ship, role)
+            })
+            .collect()
+    }
+
+    pub fn orders(&self, members: &[Friendly], threat: Vec2, now: f64) -> Vec<Packet> {
+        self.roles(members, threat, now)
+            .into_iter()
+            .map(|(ship, role)| Packet::Role(RoleAssignment { ship, role }))
+            .collect()
+    }
+}
+
+pub struct FightingWithdrawal {
+    pub rally: Vec2,
+    pub threat: Option<Vec2>,
+    pub rearguard: bool,
+    pub cover_distance: f64,
+    pub drift: f64,
+}
+
+impl Move for FightingWithdrawal {
+    fn execute(&self, actor: &(impl Kinematic + Motor)) -> Output {
+        let withdraw = Seek { target: self.rally }.execute(actor);
+        let Some(threat) = self.threat.filter(|_| self.rearguard) else {
+            return withdraw;
+        };
+        let cover = KeepDistance {
+            target: threat,
+            distance: self.cover_distance,
+        }
+        .execute(actor);
+        let linear = cover.linear * (1.0 - self.drift) + withdraw.linear * self.drift;
+        Output {
+            linear,
+            angular: cover.angular,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Roe {
+    Hold,
+    ReturnFire,
+    Free,
+}
+
+impl Roe {
+    pub fn permits(self, fired_upon: bool) -> bool {
+        match self {
+            Roe::Hold => false,
+            Roe::ReturnFire => fired_upon,
+            Roe::Free => true,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmbushPhase {
+    Lure,
+    Spring,
+}
+
+impl AmbushPhase {
+    pub fn code(self) -> u16 {
+        match self {
+            AmbushPhase::Lure => 0,
+            AmbushPhase::Spring => 1,
+        }
+    }
+
+    pub fn from_code(code: u16) -> Option<AmbushPhase> {
+        Some(match code {
+            0 => AmbushPhase::Lure,
+            1 => AmbushPhase::Spring,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AmbushCue {
+    pub phase: AmbushPhase,
+    pub target: Vec2,
+    pub velocity: Vec2,
+}
+
+pub struct Ambush {
+    pub kill_zone: Vec2,
+    pub radar_range: f64,
+    pub exposure: f64,
+    pub margin: f64,
+    pub spring_range: f64,
+    pub lookahead: f64,
+    pub spread: f64,
+    phase: AmbushPhase,
+}
+
+impl Ambush {
+    pub fn new(kill_zone: Vec2) -> Ambush {
+        Ambush {
+            kill_zone,
+            radar_range: 20_000.0,
+            exposure: 0.8,
+            margin: 2_000.0,
+            spring_range: 3_000.0,
+            lookahead: 2.0,
+            spread: 4_000.0,
+            phase: AmbushPhase::Lure,
+        }
+    }
+
+    pub fn phase(&self) -> AmbushPhase {
+        self.phase
+    }
+
+    pub fn roe(&self, bait: bool) -> Roe {
+        match (self.phase, bait) {
+            (AmbushPhase::Spring, _) => Roe::Free,
+            (AmbushPhase::Lure, true) => Roe::ReturnFire,
+            (AmbushPhase::Lure, false) => Roe::Hold,
+        }
+    }
+
+    pub fn bait_station(&self, threat: Vec2) -> Vec2 {
+        let lure = self.kill_zone - threat;
+        if lure.length() < 1.0 {
+            return self.kill_zone;
+        }
+        threat + lure.normalize() * self.radar_range * self.exposure
+    }
+
+    pub fn ambusher_station(&self, rank: usize, threat: Vec2) -> Vec2 {
+        let axis = self.kill_zone - threat;
+        let bearing = if axis.length() < 1.0 {
+            0.0
+        } else {
+            axis.angle()
+        };
+        let side = if rank.is_multiple_of(2) { 1.0 } else { -1.0 };
+        let lateral = self.spread * (1 + rank / 2) as f64 * side;
+        let station = self.kill_zone + vec2(0.0, lateral).rotate(bearing);
+        let hidden = self.radar_range + self.margin;
+        let offset = station - threat;
+        if offset.length() >= hidden || offset.length() < 1.0 {
+            return station;
+        }
+        threat + offset.normalize() * hidden
+    }
+
+    pub fn update(&mut self, threat: &impl Kinematic) -> Option<Packet> {
+        if self.phase == AmbushPhase::Spring {
+            return None;
+        }
+        let predicted = threat.position() + threat.velocity() * self.lookahead;
+        if predicted.distance(self.kill_zone) > self.spring_range {
+            return None;
+        }
+        self.phase = AmbushPhase::Spring;
+        Some(Packet::Ambush(AmbushCue {
+            phase: self.phase,
+            target: threat.position(),
+            velocity: threat.velocity(),
+        }))
+    }
+
+    pub fn observe(&mut self, inbox: &[(Header, Packet)]) -> Option<AmbushCue> {
+        let cue = inbox.iter().rev().find_map(|(_, packet)| match packet {
+            Packet::Ambush(cue) => Some(*cue),
+            _ => None,
+        })?;
+        self.phase = cue.phase;
+        Some(cue)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Run {
+    StandOff,
+    Attack,
+    Egress,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Situation {
+    pub range: f64,
+    pub closure: f64,
+    pub reach: f64,
+    pub reload: f64,
+    pub incoming: f64,
+}
+
+impl Situation {
+    pub fn assess(
+        actor: &impl Kinematic,
+        target: &impl Kinematic,
+        reach: f64,
+        reload: f64,
+    ) -> Situation {
+        let offset = target.position() - actor.position();
+        let range = offset.length();
+        let closure = if range > 0.0 {
+            -(target.velocity() - actor.velocity()).dot(offset) / range
+        } else {
+            0.0
+        };
+        Situation {
+            range,
+            closure,
+            reach,
+            reload,
+            incoming: 0.0,
+        }
+    }
+}
+
+pub struct EngagementPlanner {
+    pub standoff: f64,
+    pub approach_speed: f64,
+    pub press: f64,
+    pub threat_rate: f64,
+    pub max_exposure: f64,
+    pub abort_exposure: f64,
+    run: Run,
+}
+
+impl Default for EngagementPlanner {
+    fn default() -> Self {
+        EngagementPlanner {
+            standoff: 9_000.0,
+            approach_speed: 150.0,
+            press: 0.8,
+            threat_rate: 0.05,
+            max_exposure: 2.0,
+            abort_exposure: 4.0,
+            run: Run::StandOff,
+        }
+    }
+}
+
+impl EngagementPlanner {
+    pub fn run(&self) -> Run {
+        self.run
+    }
+
+    pub fn assess(
+        &self,
+        actor: &impl Kinematic,
+        target: &impl Kinematic,
+        reach: f64,
+        reload: f64,
+        warnings: &[Warning],
+    ) -> Situation {
+        let mut situation = Situation::assess(actor, target, reach, reload);
+        let window = self.approach_time(&situation);
+        situation.incoming = warnings
+            .iter()
+            .map(|warning| match warning.time_to_impact.get() {
+                time if time > window => window / time,
+                _ => 1.0,
+            })
+            .sum();
+        situation
+    }
+
+    pub fn approach_time(&self, situation: &Situation) -> f64 {
+        let gap = (situation.range - situation.reach * self.press).max(0.0);
+        gap / situation.closure.max(self.approach_speed)
+    }
+
+    pub fn exposure(&self, situation: &Situation) -> f64 {
+        situation.incoming + self.threat_rate * self.approach_time(situation)
+    }
+
+    pub fn update(&mut self, situation: &Situation, fired: bool) -> Run {
+        let approach = self.approach_time(situation);
+        let exposure = self.exposure(situation);
+        self.run = match self.run {
+            Run::StandOff if situation.reload <= approach && exposure <= self.max_exposure => {
+                Run::Attack
+            }
+            Run::Attack if fired || exposure > self.abort_exposure => Run::Egress,
+            Run::Egress if situation.range >= self.standoff || situation.reload <= 0.0 => {
+                Run::StandOff
+            }
+            run => run,
+        };
+        self.run
+    }
+
+    pub fn distance(&self, situation: &Situation) -> f64 {
+        match self.run {
+            Run::Attack => situation.reach * self.press,
+            Run::StandOff | Run::Egress => self.standoff.max(situation.reach),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::math::*;
+use crate::movement::Kinematic;
+use crate::time::Seconds;
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Intercept {
+    pub heading: f64,
+    pub time: Seconds,
+}
+
+pub fn intercept(
+    shooter: &impl Kinematic,
+    target: &impl Kinematic,
+    speed: f64,
+) -> Option<Intercept> {
+    lead(
+        target.position() - shooter.position(),
+        target.velocity() - shooter.velocity(),
+        speed,
+    )
+}
+
+pub fn lead(offset: Vec2, velocity: Vec2, speed: f64) -> Option<Intercept> {
+    let finite = [offset.x, offset.y, velocity.x, velocity.y, speed]
+        .iter()
+        .all(|value| value.is_finite());
+    if !finite || speed <= 0.0 {
+        return None;
+    }
+    let a = velocity.dot(velocity) - speed * speed;
+    let b = 2.0 * offset.dot(velocity);
+    let c = offset.dot(offset);
+    let time = if a.abs() < 1e-9 * speed * speed {
+        (b < 0.0).then(|| -c / b)?
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let q = -0.5 * (b + b.signum() * root);
+        let roots = if q == 0.0 { [0.0, 0.0] } else { [q / a, c / q] };
+        roots
+            .into_iter()
+            .filter(|t| *t > 0.0 && t.is_finite())
+            .reduce(f64::min)?
+    };
+    let aim = offset + velocity * time;
+    (time.is_finite() && aim.length() > 0.0).then(|| Intercept {
+        heading: aim.angle(),
+        time: Seconds(time),
+    })
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Approach {
+    pub time: Seconds,
+    pub distance: f64,
+}
+
+pub fn closest_approach(a: &impl Kinematic, b: &impl Kinematic) -> Approach {
+    let offset = b.position() - a.position();
+    let velocity = b.velocity() - a.velocity();
+    let speed_squared = velocity.dot(velocity);
+    let time = if speed_squared > 0.0 {
+        (-offset.dot(velocity) / speed_squared).max(0.0)
+    } else {
+        0.0
+    };
+    Approach {
+        time: Seconds(time),
+        distance: (offset + velocity * time).length(),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Cached {
+    key: u32,
+    used: u64,
+    offset: Vec2,
+    velocity: Vec2,
+    speed: f64,
+    solution: Option<Intercept>,
+}
+
+pub struct InterceptCache {
+    pub position_tolerance: f64,
+    pub velocity_tolerance: f64,
+    pub capacity: usize,
+    pub hits: u64,
+    pub misses: u64,
+    entries: Vec<Cached>,
+}
+
+impl Default for InterceptCache {
+    fn default() -> Self {
+        InterceptCache::new()
+    }
+}
+
+impl InterceptCache {
+    pub fn new() -> InterceptCache {
+        InterceptCache {
+            position_tolerance: 1.0,
+            velocity_tolerance: 0.5,
+            capacity: 16,
+            hits: 0,
+            misses: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn forget(&mut self, key: u32) {
+        self.entries.retain(|entry| entry.key != key);
+    }
+
+    pub fn lead(
+        &mut self,
+        key: u32,
+        offset: Vec2,
+        velocity: Vec2,
+        speed: f64,
+    ) -> Option<Intercept> {
+        let used = self.hits + self.misses;
+        let index = self.entries.iter().position(|entry| entry.key == key);
+        if let Some(entry) = index.map(|index| &mut self.entries[index]) {
+            let close = entry.speed == speed
+                && entry.offset.distance(offset) <= self.position_tolerance
+                && entry.velocity.distance(velocity) <= self.velocity_tolerance;
+            if close {
+                entry.used = used;
+                self.hits += 1;
+                return entry.solution;
+            }
+        }
+        self.misses += 1;
+        let entry = Cached {
+            key,
+            used,
+            offset,
+            velocity,
+            speed,
+            solution: lead(offset, velocity, speed),
+        };
+        match index {
+            Some(index) => self.entries[index] = entry,
+            None => {
+                if self.entries.len() >= self.capacity.max(1) {
+                    if let Some(stale) = self
+                        .entries
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, entry)| entry.used)
+                        .map(|(index, _)| index)
+                    {
+                        self.entries.swap_remove(stale);
+                    }
+                }
+                self.entries.push(entry);
+            }
+        }
+        entry.solution
+    }
+
+    pub fn intercept(
+        &mut self,
+        key: u32,
+        shooter: &impl Kinematic,
+        target: &impl Kinematic,
+        speed: f64,
+    ) -> Option<Intercept> {
+        self.lead(
+            key,
+            target.position() - shooter.position(),
+            target.velocity() - shooter.velocity(),
+            speed,
+        )
+    }
+}
@@ -0,0 +1,140 @@
+use crate::profiler::{Clock, Profiler, Subsystem};
+use oort_api::prelude::*;
+use std::fmt::Arguments;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tag {
+    Movement,
+    Perception,
+    Targeting,
+    Radio,
+    Tactics,
+    Profiler,
+}
+
+impl Tag {
+    pub const ALL: [Tag; 6] = [
+        Tag::Movement,
+        Tag::Perception,
+        Tag::Targeting,
+        Tag::Radio,
+        Tag::Tactics,
+        Tag::Profiler,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Tag::Movement => "movement",
+            Tag::Perception => "perception",
+            Tag::Targeting => "targeting",
+            Tag::Radio => "radio",
+            Tag::Tactics => "tactics",
+            Tag::Profiler => "profiler",
+        }
+    }
+}
+
+pub trait Sink {
+    fn write(&self, tag: Tag, level: Level, message: Arguments);
+}
+
+pub struct OortSink;
+
+impl Sink for OortSink {
+    fn write(&self, tag: Tag, level: Level, message: Arguments) {
+        debug!("[{:?} {}] {}", level, tag.name(), message);
+    }
+}
+
+pub struct Telemetry<S: Sink = OortSink> {
+    pub sink: S,
+    levels: [Level; Tag::ALL.len()],
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Telemetry::new(OortSink)
+    }
+}
+
+impl<S: Sink> Telemetry<S> {
+    pub fn new(sink: S) -> Telemetry<S> {
+        Telemetry {
+            sink,
+            levels: [Level::Warn; Tag::ALL.len()],
+        }
+    }
+
+    pub fn level(&self, tag: Tag) -> Level {
+        self.levels[tag as usize]
+    }
+
+    pub fn set(&mut self, tag: Tag, level: Level) {
+        self.levels[tag as usize] = level;
+    }
+
+    pub fn set_all(&mut self, level: Level) {
+        self.levels = [level; Tag::ALL.len()];
+    }
+
+    pub fn enabled(&self, tag: Tag, level: Level) -> bool {
+        level != Level::Off && level <= self.level(tag)
+    }
+
+    pub fn log(&self, tag: Tag, level: Level, message: Arguments) {
+        if self.enabled(tag, level) {
+            self.sink.write(tag, level, message);
+        }
+    }
+
+    pub fn error(&self, tag: Tag, message: Arguments) {
+        self.log(tag, Level::Error, message);
+    }
+
+    pub fn warn(&self, tag: Tag, message: Arguments) {
+        self.log(tag, Level::Warn, message);
+    }
+
+    pub fn info(&self, tag: Tag, message: Arguments) {
+        self.log(tag, Level::Info, message);
+    }
+
+    pub fn debug(&self, tag: Tag, message: Arguments) {
+        self.log(tag, Level::Debug, message);
+    }
+
+    pub fn trace(&self, tag: Tag, message: Arguments) {
+        self.log(tag, Level::Trace, message);
+    }
+}
+
+impl<S: Sink> Telemetry<S> {
+    pub fn profile<C: Clock>(&self, profiler: &Profiler<C>) {
+        if !self.enabled(Tag::Profiler, Level::Debug) {
+            return;
+        }
+        for subsystem in Subsystem::ALL {
+            let sample = profiler.sample(subsystem);
+            self.debug(
+                Tag::Profiler,
+                format_args!(
+                    "{} last {} avg {:.0} max {}",
+                    subsystem.name(),
+                    sample.last,
+                    sample.average,
+                    sample.max
+                ),
+            );
+        }
+    }
+}
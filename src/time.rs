@@ -0,0 +1,185 @@
+use crate::math::*;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Seconds(pub f64);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ticks(pub i64);
+
+impl Seconds {
+    pub const ZERO: Seconds = Seconds(0.0);
+    pub const TICK: Seconds = Seconds(TICK_LENGTH);
+
+    #[cfg(feature = "oort")]
+    pub fn now() -> Seconds {
+        Seconds(oort_api::prelude::current_time())
+    }
+
+    pub fn get(self) -> f64 {
+        self.0
+    }
+
+    pub fn abs(self) -> Seconds {
+        Seconds(self.0.abs())
+    }
+
+    pub fn min(self, other: Seconds) -> Seconds {
+        Seconds(self.0.min(other.0))
+    }
+
+    pub fn max(self, other: Seconds) -> Seconds {
+        Seconds(self.0.max(other.0))
+    }
+
+    pub fn total_cmp(&self, other: &Seconds) -> core::cmp::Ordering {
+        self.0.total_cmp(&other.0)
+    }
+
+    pub fn is_finite(self) -> bool {
+        self.0.is_finite()
+    }
+
+    pub fn ticks(self) -> Ticks {
+        Ticks((self.0 / TICK_LENGTH).round() as i64)
+    }
+}
+
+impl Ticks {
+    pub const ZERO: Ticks = Ticks(0);
+    pub const ONE: Ticks = Ticks(1);
+
+    #[cfg(feature = "oort")]
+    pub fn now() -> Ticks {
+        Ticks(oort_api::prelude::current_tick() as i64)
+    }
+
+    pub fn get(self) -> i64 {
+        self.0
+    }
+
+    pub fn seconds(self) -> Seconds {
+        Seconds(self.0 as f64 * TICK_LENGTH)
+    }
+}
+
+impl From<Ticks> for Seconds {
+    fn from(ticks: Ticks) -> Seconds {
+        ticks.seconds()
+    }
+}
+
+impl From<Seconds> for Ticks {
+    fn from(seconds: Seconds) -> Ticks {
+        seconds.ticks()
+    }
+}
+
+impl From<Seconds> for f64 {
+    fn from(seconds: Seconds) -> f64 {
+        seconds.0
+    }
+}
+
+impl Add for Seconds {
+    type Output = Seconds;
+    fn add(self, other: Seconds) -> Seconds {
+        Seconds(self.0 + other.0)
+    }
+}
+
+impl Sub for Seconds {
+    type Output = Seconds;
+    fn sub(self, other: Seconds) -> Seconds {
+        Seconds(self.0 - other.0)
+    }
+}
+
+impl Neg for Seconds {
+    type Output = Seconds;
+    fn neg(self) -> Seconds {
+        Seconds(-self.0)
+    }
+}
+
+impl Mul<f64> for Seconds {
+    type Output = Seconds;
+    fn mul(self, scale: f64) -> Seconds {
+        Seconds(self.0 * scale)
+    }
+}
+
+impl Div<f64> for Seconds {
+    type Output = Seconds;
+    fn div(self, scale: f64) -> Seconds {
+        Seconds(self.0 / scale)
+    }
+}
+
+impl Div for Seconds {
+    type Output = f64;
+    fn div(self, other: Seconds) -> f64 {
+        self.0 / other.0
+    }
+}
+
+impl Mul<Seconds> for Vec2 {
+    type Output = Vec2;
+    fn mul(self, time: Seconds) -> Vec2 {
+        self * time.0
+    }
+}
+
+impl AddAssign for Seconds {
+    fn add_assign(&mut self, other: Seconds) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Seconds {
+    fn sub_assign(&mut self, other: Seconds) {
+        self.0 -= other.0;
+    }
+}
+
+impl Add for Ticks {
+    type Output = Ticks;
+    fn add(self, other: Ticks) -> Ticks {
+        Ticks(self.0 + other.0)
+    }
+}
+
+impl Sub for Ticks {
+    type Output = Ticks;
+    fn sub(self, other: Ticks) -> Ticks {
+        Ticks(self.0 - other.0)
+    }
+}
+
+impl Neg for Ticks {
+    type Output = Ticks;
+    fn neg(self) -> Ticks {
+        Ticks(-self.0)
+    }
+}
+
+impl Mul<i64> for Ticks {
+    type Output = Ticks;
+    fn mul(self, scale: i64) -> Ticks {
+        Ticks(self.0 * scale)
+    }
+}
+
+impl AddAssign for Ticks {
+    fn add_assign(&mut self, other: Ticks) {
+        self.0 += other.0;
+    }
+}
+
+impl SubAssign for Ticks {
+    fn sub_assign(&mut self, other: Ticks) {
+        self.0 -= other.0;
+    }
+}
@@ -0,0 +1,168 @@
+use crate::math::*;
+use crate::movement::*;
+use crate::sim::MockMotor;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Frame {
+    pub tick: u32,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub heading: f64,
+    pub angular_velocity: f64,
+    pub target: Vec2,
+    pub linear: Vec2,
+    pub angular: f64,
+}
+
+impl Frame {
+    const FIELDS: usize = 12;
+
+    fn values(&self) -> [f64; Frame::FIELDS] {
+        [
+            self.tick as f64,
+            self.position.x,
+            self.position.y,
+            self.velocity.x,
+            self.velocity.y,
+            self.heading,
+            self.angular_velocity,
+            self.target.x,
+            self.target.y,
+            self.linear.x,
+            self.linear.y,
+            self.angular,
+        ]
+        .map(|value| value as f32 as f64)
+    }
+
+    fn from_values(values: &[f64]) -> Option<Frame> {
+        let [tick, px, py, vx, vy, heading, angular_velocity, tx, ty, lx, ly, angular] =
+            <[f64; Frame::FIELDS]>::try_from(values).ok()?;
+        Some(Frame {
+            tick: tick as u32,
+            position: vec2(px, py),
+            velocity: vec2(vx, vy),
+            heading,
+            angular_velocity,
+            target: vec2(tx, ty),
+            linear: vec2(lx, ly),
+            angular,
+        })
+    }
+
+    pub fn actor(&self, template: &MockMotor) -> MockMotor {
+        MockMotor {
+            position: self.position,
+            velocity: self.velocity,
+            heading: self.heading,
+            angular_velocity: self.angular_velocity,
+            ..*template
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Trace {
+    pub frames: Vec<Frame>,
+}
+
+impl Trace {
+    pub fn record(&mut self, tick: u32, actor: &impl Kinematic, target: Vec2, output: &Output) {
+        let frame = Frame {
+            tick,
+            position: actor.position(),
+            velocity: actor.velocity(),
+            heading: actor.heading(),
+            angular_velocity: actor.angular_velocity(),
+            target,
+            linear: output.linear,
+            angular: output.angular,
+        };
+        let values = frame.values();
+        self.frames
+            .push(Frame::from_values(&values).unwrap_or(frame));
+    }
+
+    pub fn capture<M: Move>(
+        actor: &mut MockMotor,
+        target: Vec2,
+        strategy: impl Fn(Vec2) -> M,
+        ticks: u32,
+    ) -> Trace {
+        let mut trace = Trace::default();
+        for tick in 0..ticks {
+            let output = strategy(target).execute(&*actor);
+            trace.record(tick, &*actor, target, &output);
+            actor.step(&output);
+        }
+        trace
+    }
+
+    pub fn encode(&self) -> String {
+        self.frames
+            .iter()
+            .map(|frame| {
+                frame
+                    .values()
+                    .iter()
+                    .map(|value| format!("{}", *value as f32))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub fn decode(text: &str) -> Option<Trace> {
+        let frames = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let values: Vec<f64> = line
+                    .split_whitespace()
+                    .map(|word| word.parse::<f32>().map(f64::from))
+                    .collect::<Result<_, _>>()
+                    .ok()?;
+                Frame::from_values(&values)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Trace { frames })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Divergence {
+    pub tick: u32,
+    pub expected: Vec2,
+    pub actual: Vec2,
+    pub angular_error: f64,
+}
+
+pub fn replay<M: Move>(
+    trace: &Trace,
+    template: &MockMotor,
+    strategy: impl Fn(Vec2) -> M,
+    tolerance: f64,
+) -> Vec<Divergence> {
+    trace
+        .frames
+        .iter()
+        .filter_map(|frame| {
+            let output = strategy(frame.target).execute(&frame.actor(template));
+            let linear = vec2(output.linear.x as f32 as f64, output.linear.y as f32 as f64);
+            let angular = output.angular as f32 as f64;
+            let scale = frame.linear.length().max(1.0);
+            let diverged = linear.distance(frame.linear) > tolerance * scale
+                || (angular - frame.angular).abs() > tolerance * frame.angular.abs().max(1.0);
+            diverged.then_some(Divergence {
+                tick: frame.tick,
+                expected: frame.linear,
+                actual: linear,
+                angular_error: angular - frame.angular,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,363 @@
+use crate::controllers::Gunnery;
+use crate::filter::{Gate, VecKalman};
+use crate::math::*;
+use crate::movement::*;
+use crate::random::Rng;
+use crate::scenario::Scenario;
+use crate::sim::{Body, ConstantAcceleration, CoordinatedTurn, MockMotor, RandomJink, Script};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub low: f64,
+    pub high: f64,
+}
+
+impl Parameter {
+    pub fn at(&self, fraction: f64) -> f64 {
+        self.low + (self.high - self.low) * fraction.clamp(0.0, 1.0)
+    }
+
+    pub fn clamp(&self, value: f64) -> f64 {
+        value.clamp(self.low, self.high)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    pub entries: Vec<(String, f64)>,
+}
+
+impl Config {
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn set(&mut self, name: &str, value: f64) {
+        match self.entries.iter_mut().find(|(key, _)| key == name) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((name.to_string(), value)),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        self.entries
+            .iter()
+            .map(|(name, value)| format!("{} = {}\n", name, value))
+            .collect()
+    }
+
+    pub fn parse(text: &str) -> Option<Config> {
+        let mut config = Config::default();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, value) = line.split_once('=')?;
+            config.set(name.trim(), value.trim().parse().ok()?);
+        }
+        Some(config)
+    }
+}
+
+pub trait Tunable {
+    fn parameters() -> Vec<Parameter>;
+    fn load(&mut self, config: &Config);
+}
+
+impl Tunable for Gunnery {
+    fn parameters() -> Vec<Parameter> {
+        vec![
+            Parameter {
+                name: "angle_gain",
+                low: 1.0,
+                high: 10.0,
+            },
+            Parameter {
+                name: "rate_gain",
+                low: 0.2,
+                high: 2.0,
+            },
+            Parameter {
+                name: "radar_width",
+                low: 0.01,
+                high: 0.5,
+            },
+        ]
+    }
+
+    fn load(&mut self, config: &Config) {
+        self.attitude.angle_gain = config.get("angle_gain").unwrap_or(self.attitude.angle_gain);
+        self.attitude.rate_gain = config.get("rate_gain").unwrap_or(self.attitude.rate_gain);
+        self.radar_width = config.get("radar_width").unwrap_or(self.radar_width);
+    }
+}
+
+impl Tunable for Arrive {
+    fn parameters() -> Vec<Parameter> {
+        vec![Parameter {
+            name: "arrive_gain",
+            low: 0.05,
+            high: 5.0,
+        }]
+    }
+
+    fn load(&mut self, config: &Config) {
+        self.gain = config.get("arrive_gain").unwrap_or(self.gain);
+    }
+}
+
+impl Tunable for VecKalman {
+    fn parameters() -> Vec<Parameter> {
+        vec![Parameter {
+            name: "process_noise",
+            low: 10.0,
+            high: 20_000.0,
+        }]
+    }
+
+    fn load(&mut self, config: &Config) {
+        self.process_noise = config.get("process_noise").unwrap_or(self.process_noise);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Trial {
+    pub config: Config,
+    pub cost: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Evolution {
+    pub population: usize,
+    pub generations: usize,
+    pub elite: usize,
+    pub mutation: f64,
+    pub decay: f64,
+    pub seed: u64,
+}
+
+impl Default for Evolution {
+    fn default() -> Self {
+        Evolution {
+            population: 16,
+            generations: 10,
+            elite: 4,
+            mutation: 0.25,
+            decay: 0.8,
+            seed: 1,
+        }
+    }
+}
+
+pub struct Tuner {
+    pub parameters: Vec<Parameter>,
+}
+
+impl Tuner {
+    pub fn of<T: Tunable>() -> Tuner {
+        Tuner {
+            parameters: T::parameters(),
+        }
+    }
+
+    pub fn config(&self, values: &[f64]) -> Config {
+        Config {
+            entries: self
+                .parameters
+                .iter()
+                .zip(values)
+                .map(|(parameter, value)| (parameter.name.to_string(), parameter.clamp(*value)))
+                .collect(),
+        }
+    }
+
+    fn evaluate(&self, values: &[f64], cost: &mut impl FnMut(&Config) -> f64) -> Trial {
+        let config = self.config(values);
+        let cost = cost(&config);
+        Trial {
+            config,
+            cost: if cost.is_nan() { f64::INFINITY } else { cost },
+        }
+    }
+
+    fn keep(best: &mut Option<Trial>, trial: &Trial) {
+        if best.as_ref().is_none_or(|best| trial.cost < best.cost) {
+            *best = Some(trial.clone());
+        }
+    }
+
+    pub fn grid(&self, steps: usize, mut cost: impl FnMut(&Config) -> f64) -> Option<Trial> {
+        let steps = steps.max(1);
+        let dimensions = self.parameters.len() as u32;
+        let mut best = None;
+        for index in 0..steps.pow(dimensions) {
+            let values: Vec<f64> = self
+                .parameters
+                .iter()
+                .scan(index, |rest, parameter| {
+                    let step = *rest % steps;
+                    *rest /= steps;
+                    let fraction = if steps > 1 {
+                        step as f64 / (steps - 1) as f64
+                    } else {
+                        0.5
+                    };
+                    Some(parameter.at(fraction))
+                })
+                .collect();
+            Tuner::keep(&mut best, &self.evaluate(&values, &mut cost));
+        }
+        best
+    }
+
+    pub fn evolve(
+        &self,
+        search: &Evolution,
+        mut cost: impl FnMut(&Config) -> f64,
+    ) -> Option<Trial> {
+        let mut rng = Rng::new(search.seed);
+        let mut population: Vec<Vec<f64>> = (0..search.population.max(1))
+            .map(|_| {
+                self.parameters
+                    .iter()
+                    .map(|parameter| parameter.at(rng.unit()))
+                    .collect()
+            })
+            .collect();
+        let mut mutation = search.mutation;
+        let mut best = None;
+        for _ in 0..search.generations.max(1) {
+            let mut scored: Vec<(Vec<f64>, f64)> = population
+                .drain(..)
+                .map(|values| {
+                    let trial = self.evaluate(&values, &mut cost);
+                    Tuner::keep(&mut best, &trial);
+                    (values, trial.cost)
+                })
+                .collect();
+            scored.sort_by(|a, b| a.1.total_cmp(&b.1));
+            let elite = search.elite.clamp(1, scored.len());
+            population.extend(scored[..elite].iter().map(|(values, _)| values.clone()));
+            while population.len() < search.population.max(1) {
+                let parent = &scored[(rng.unit() * elite as f64) as usize % elite].0;
+                let child = self
+                    .parameters
+                    .iter()
+                    .zip(parent)
+                    .map(|(parameter, value)| {
+                        let spread = (parameter.high - parameter.low) * mutation;
+                        parameter.clamp(value + rng.uniform(-spread, spread))
+                    })
+                    .collect();
+                population.push(child);
+            }
+            mutation *= search.decay;
+        }
+        best
+    }
+}
+
+fn engagement(scenario: &Scenario, target: &mut impl Script, config: &Config) -> f64 {
+    let mut pilot = Gunnery::default();
+    pilot.load(config);
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let outcome = scenario.run(&mut ship, target, &mut pilot);
+    outcome.killed_at.unwrap_or(2.0 * scenario.duration)
+}
+
+pub fn gunnery_cost(config: &Config) -> f64 {
+    let scenario = Scenario {
+        duration: 30.0,
+        ..Scenario::default()
+    };
+    let costs = [
+        engagement(
+            &scenario,
+            &mut Body {
+                position: vec2(2_000.0, 500.0),
+                velocity: vec2(0.0, 0.0),
+            },
+            config,
+        ),
+        engagement(
+            &scenario,
+            &mut ConstantAcceleration {
+                position: vec2(2_500.0, 0.0),
+                velocity: vec2(0.0, 100.0),
+                acceleration: vec2(0.0, -10.0),
+            },
+            config,
+        ),
+        engagement(
+            &scenario,
+            &mut CoordinatedTurn {
+                position: vec2(2_000.0, 1_000.0),
+                velocity: vec2(100.0, 0.0),
+                turn_rate: 0.1,
+            },
+            config,
+        ),
+        engagement(
+            &scenario,
+            &mut RandomJink::new(vec2(2_000.0, -500.0), vec2(80.0, 0.0), 20.0, 1.5, 1),
+            config,
+        ),
+    ];
+    costs.iter().sum::<f64>() / costs.len() as f64
+}
+
+pub fn arrival_cost(config: &Config) -> f64 {
+    let limit = 3_600;
+    let goals = [
+        vec2(500.0, 0.0),
+        vec2(3_000.0, 1_000.0),
+        vec2(-8_000.0, 0.0),
+    ];
+    let ticks: usize = goals
+        .iter()
+        .map(|&goal| {
+            let mut arrive = Arrive::new(goal);
+            arrive.load(config);
+            let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+            (0..limit)
+                .position(|_| match arrive.steer(&ship) {
+                    Steering::Done => true,
+                    steering => {
+                        ship.step(&steering.output());
+                        false
+                    }
+                })
+                .unwrap_or(2 * limit)
+        })
+        .sum();
+    ticks as f64 * TICK_LENGTH / goals.len() as f64
+}
+
+pub fn tracking_cost(config: &Config) -> f64 {
+    let mut rng = Rng::new(7);
+    let mut target = RandomJink::new(vec2(5_000.0, 0.0), vec2(0.0, 150.0), 40.0, 2.0, 3);
+    let mut filter = VecKalman::default().with_gate(Gate::open());
+    filter.load(config);
+    let (position_sigma, velocity_sigma) =
+        (filter.position_noise.sqrt(), filter.velocity_noise.sqrt());
+    let mut noise =
+        |sigma: f64| vec2(rng.uniform(-sigma, sigma), rng.uniform(-sigma, sigma)) * 3f64.sqrt();
+    let (warmup, ticks) = (60, 1_200);
+    let mut error = 0.0;
+    for tick in 0..ticks {
+        target.advance(TICK_LENGTH);
+        filter.predict(TICK_LENGTH);
+        filter.update(
+            target.position + noise(position_sigma),
+            target.velocity + noise(velocity_sigma),
+        );
+        if tick >= warmup {
+            error += sq_length(filter.position - target.position);
+        }
+    }
+    (error / (ticks - warmup) as f64).sqrt()
+}
@@ -0,0 +1,104 @@
+use crate::control::Attitude;
+use crate::movement::*;
+use crate::sim::Body;
+use crate::targeting::intercept;
+use oort_api::prelude::*;
+
+pub struct Acceleration {
+    pub gain: f64,
+}
+
+impl Default for Acceleration {
+    fn default() -> Self {
+        Acceleration { gain: 1.0 }
+    }
+}
+
+impl Acceleration {
+    pub fn command(&self, actor: &(impl Kinematic + Motor), target: Vec2) -> Output {
+        Arrive::new(target).gain(self.gain).execute(actor)
+    }
+
+    pub fn tick(&self, actor: &(impl Kinematic + Motor)) {
+        accelerate(self.command(actor, target()).linear);
+    }
+}
+
+pub struct Rotation {
+    pub attitude: Attitude,
+    pub gun: usize,
+    pub tolerance: f64,
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Rotation {
+            attitude: Attitude::default(),
+            gun: 0,
+            tolerance: 0.02,
+        }
+    }
+}
+
+impl Rotation {
+    pub fn command(&self, actor: &(impl Kinematic + Motor), target: Vec2) -> (Output, bool) {
+        let heading = (target - actor.position()).angle();
+        let output = Align::new(heading).attitude(self.attitude).execute(actor);
+        let aligned = angle_diff(actor.heading(), heading).abs() < self.tolerance;
+        (output, aligned)
+    }
+
+    pub fn tick(&self, actor: &(impl Kinematic + Motor)) {
+        let (output, aligned) = self.command(actor, target());
+        torque(output.angular);
+        if aligned {
+            fire(self.gun);
+        }
+    }
+}
+
+pub struct Lead {
+    pub attitude: Attitude,
+    pub gun: usize,
+    pub bullet_speed: f64,
+    pub tolerance: f64,
+}
+
+impl Default for Lead {
+    fn default() -> Self {
+        Lead {
+            attitude: Attitude::default(),
+            gun: 0,
+            bullet_speed: 1_000.0,
+            tolerance: 0.02,
+        }
+    }
+}
+
+impl Lead {
+    pub fn command(&self, actor: &(impl Kinematic + Motor), target: &Body) -> (Output, bool) {
+        let (bearing, feedforward) = Face::new(*target).line_of_sight(actor);
+        let heading = intercept(actor, target, self.bullet_speed).map(|solution| solution.heading);
+        let output = Align::new(heading.unwrap_or(bearing))
+            .feedforward(feedforward)
+            .attitude(self.attitude)
+            .execute(actor);
+        let Some(heading) = heading else {
+            return (output, false);
+        };
+        let aligned = angle_diff(actor.heading(), heading).abs() < self.tolerance;
+        (output, aligned)
+    }
+
+    pub fn tick(&self, actor: &(impl Kinematic + Motor)) {
+        let target = Body {
+            position: target(),
+            velocity: target_velocity(),
+        };
+        let (output, aligned) = self.command(actor, &target);
+        torque(output.angular);
+        if aligned {
+            fire(self.gun);
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::movement::Kinematic;
+use crate::perception::{Track, TrackId};
+use crate::targeting::closest_approach;
+use crate::time::Seconds;
+use oort_api::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Munition {
+    Missile,
+    Torpedo,
+    Bullet,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Warning {
+    pub track: TrackId,
+    pub munition: Munition,
+    pub time_to_impact: Seconds,
+    pub miss_distance: f64,
+}
+
+pub struct MissileWarning {
+    pub danger_radius: f64,
+    pub horizon: Seconds,
+    pub bullet_speed: f64,
+}
+
+impl MissileWarning {
+    pub fn classify(&self, track: &Track, own: &impl Kinematic) -> Option<Munition> {
+        match track.class {
+            Class::Missile => Some(Munition::Missile),
+            Class::Torpedo => Some(Munition::Torpedo),
+            Class::Unknown if (track.velocity - own.velocity()).length() >= self.bullet_speed => {
+                Some(Munition::Bullet)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn scan(&self, own: &impl Kinematic, tracks: &[Track]) -> Vec<Warning> {
+        let mut warnings: Vec<Warning> = tracks
+            .iter()
+            .filter_map(|track| {
+                let munition = self.classify(track, own)?;
+                let approach = closest_approach(own, track);
+                let inbound = approach.time > Seconds::ZERO
+                    && approach.time <= self.horizon
+                    && approach.distance < self.danger_radius;
+                inbound.then_some(Warning {
+                    track: track.id,
+                    munition,
+                    time_to_impact: approach.time,
+                    miss_distance: approach.distance,
+                })
+            })
+            .collect();
+        warnings.sort_by(|a, b| a.time_to_impact.total_cmp(&b.time_to_impact));
+        warnings
+    }
+
+    pub fn most_urgent(&self, own: &impl Kinematic, tracks: &[Track]) -> Option<Warning> {
+        self.scan(own, tracks).into_iter().next()
+    }
+}
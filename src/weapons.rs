@@ -0,0 +1,243 @@
+use crate::hardware::Hardware;
+use crate::missile::{LaunchCache, LaunchSolution, Performance, Uplink, ALL_MISSILES};
+use crate::movement::Kinematic;
+use crate::perception::{Track, TrackId};
+use crate::targeting::intercept;
+use crate::Oort;
+use oort_api::prelude::*;
+
+pub struct Gun {
+    pub index: usize,
+    pub speed: f64,
+    pub tolerance: f64,
+    pub turn_gain: f64,
+    pub max_age: f64,
+}
+
+impl Gun {
+    pub fn engage(&self, actor: &impl Kinematic, target: &impl Kinematic) -> bool {
+        self.aim(&mut Oort::default(), actor, target, true)
+    }
+
+    pub fn engage_track(&self, actor: &impl Kinematic, track: &Track) -> bool {
+        self.engage_track_with(&mut Oort::default(), actor, track)
+    }
+
+    pub fn engage_track_with(
+        &self,
+        hardware: &mut impl Hardware,
+        actor: &impl Kinematic,
+        track: &Track,
+    ) -> bool {
+        self.aim(hardware, actor, track, track.age <= self.max_age)
+    }
+
+    fn aim(
+        &self,
+        hardware: &mut impl Hardware,
+        actor: &impl Kinematic,
+        target: &impl Kinematic,
+        fresh: bool,
+    ) -> bool {
+        let Some(solution) = intercept(actor, target, self.speed) else {
+            return false;
+        };
+        let error = angle_diff(actor.heading(), solution.heading);
+        hardware.turn(error * self.turn_gain);
+        let firing = fresh && error.abs() < self.tolerance;
+        if firing {
+            hardware.fire(self.index);
+        }
+        firing
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Salvo {
+    pub target: TrackId,
+    pub launched: f64,
+    pub accepted: usize,
+    pub rejected: usize,
+}
+
+pub struct Launcher {
+    pub index: usize,
+    pub performance: Performance,
+    pub uplink: Uplink,
+    pub solutions: LaunchCache,
+    salvo: Option<Salvo>,
+}
+
+impl Launcher {
+    pub fn new(index: usize, performance: Performance, channel: usize) -> Launcher {
+        Launcher {
+            index,
+            performance,
+            uplink: Uplink { channel },
+            solutions: LaunchCache::new(),
+            salvo: None,
+        }
+    }
+
+    pub fn salvo(&self) -> Option<Salvo> {
+        self.salvo
+    }
+
+    pub fn solve(&mut self, actor: &impl Kinematic, target: &Track) -> Option<LaunchSolution> {
+        self.solutions
+            .solve(target.id, actor, target, &self.performance)
+    }
+
+    pub fn forget(&mut self, id: TrackId) {
+        self.solutions.forget(id);
+    }
+
+    pub fn engage(&mut self, actor: &impl Kinematic, target: &Track) -> bool {
+        self.uplink.transmit(target.id, target);
+        let ready = reload_ticks(self.index) == 0;
+        let reachable = self.solve(actor, target).is_some();
+        if ready && reachable {
+            fire(self.index);
+            self.salvo = Some(Salvo {
+                target: target.id,
+                launched: current_time(),
+                accepted: 0,
+                rejected: 0,
+            });
+        }
+        ready && reachable
+    }
+
+    pub fn reassign(
+        &mut self,
+        actor: &impl Kinematic,
+        tracks: &[Track],
+        now: f64,
+    ) -> Option<TrackId> {
+        let mut salvo = self.salvo?;
+        for (_, ack) in self.uplink.acknowledgements() {
+            if ack.accepted {
+                salvo.accepted += 1;
+            } else {
+                salvo.rejected += 1;
+            }
+        }
+        if now - salvo.launched > self.performance.lifetime {
+            self.salvo = None;
+            return None;
+        }
+        self.salvo = Some(salvo);
+        if tracks.iter().any(|track| track.id == salvo.target) {
+            return None;
+        }
+        let next = tracks
+            .iter()
+            .filter(|track| track.is_target())
+            .map(|track| track.predict(now))
+            .min_by(|a, b| {
+                let range = |track: &Track| actor.position().distance(track.position);
+                range(a).total_cmp(&range(b))
+            })?;
+        self.uplink.retarget(ALL_MISSILES, next.id, &next);
+        self.salvo = Some(Salvo {
+            target: next.id,
+            accepted: 0,
+            rejected: 0,
+            ..salvo
+        });
+        Some(next.id)
+    }
+}
+
+pub struct Volley {
+    pub launchers: Vec<Launcher>,
+    pub min_ready: usize,
+}
+
+impl Volley {
+    pub fn ready(&self) -> usize {
+        self.launchers
+            .iter()
+            .filter(|launcher| reload_ticks(launcher.index) == 0)
+            .count()
+    }
+
+    pub fn engage(&mut self, actor: &impl Kinematic, target: &Track) -> usize {
+        for launcher in &self.launchers {
+            launcher.uplink.transmit(target.id, target);
+        }
+        if self.ready() < self.min_ready.max(1) {
+            return 0;
+        }
+        self.launchers
+            .iter_mut()
+            .map(|launcher| launcher.engage(actor, target))
+            .filter(|fired| *fired)
+            .count()
+    }
+
+    pub fn flight_time(&mut self, actor: &impl Kinematic, target: &Track) -> Option<f64> {
+        let launcher = self.launchers.first_mut()?;
+        launcher
+            .solve(actor, target)
+            .map(|solution| solution.intercept_time)
+    }
+
+    pub fn forget(&mut self, id: TrackId) {
+        for launcher in &mut self.launchers {
+            launcher.forget(id);
+        }
+    }
+
+    pub fn reassign(&mut self, actor: &impl Kinematic, tracks: &[Track], now: f64) -> usize {
+        self.launchers
+            .iter_mut()
+            .filter_map(|launcher| launcher.reassign(actor, tracks, now))
+            .count()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Assignment {
+    pub guns: Option<TrackId>,
+    pub missiles: Option<TrackId>,
+    pub torpedoes: Option<TrackId>,
+}
+
+pub struct Coordinator {
+    pub gun_range: f64,
+    pub missile_range: f64,
+}
+
+impl Coordinator {
+    pub fn value(class: Class) -> f64 {
+        match class {
+            Class::Cruiser => 4.0,
+            Class::Frigate => 3.0,
+            Class::Fighter => 2.0,
+            Class::Missile | Class::Torpedo => 1.0,
+            _ => 0.5,
+        }
+    }
+
+    pub fn assign(&self, actor: &impl Kinematic, tracks: &[Track]) -> Assignment {
+        let score = |track: &Track| {
+            Coordinator::value(track.class) / actor.position().distance(track.position).max(1.0)
+        };
+        let best = |filter: &dyn Fn(&Track) -> bool| {
+            tracks
+                .iter()
+                .filter(|track| filter(track))
+                .max_by(|a, b| score(a).total_cmp(&score(b)))
+                .map(|track| track.id)
+        };
+        let range = |track: &Track| actor.position().distance(track.position);
+        Assignment {
+            guns: best(&|track| track.is_hull() && range(track) <= self.gun_range),
+            missiles: best(&|track| track.is_target() && range(track) <= self.missile_range),
+            torpedoes: best(&|track| {
+                track.is_target() && matches!(track.class, Class::Frigate | Class::Cruiser)
+            }),
+        }
+    }
+}
@@ -0,0 +1,361 @@
+#![cfg(feature = "oort")]
+
+use spacepoort::controllers::{no_escape_range, FrigateDuel};
+use spacepoort::missile::Performance;
+
+#[test]
+fn no_escape_range_shrinks_with_flee_acceleration() {
+    let missile = Performance {
+        acceleration: 300.0,
+        delta_v: 3_000.0,
+        lifetime: 20.0,
+    };
+    let standing = no_escape_range(&missile, 0.0);
+    assert!((standing - missile.reach(missile.lifetime)).abs() < 1e-6);
+    assert!(no_escape_range(&missile, 60.0) < standing);
+    assert_eq!(no_escape_range(&missile, 1_000.0), 0.0);
+}
+
+#[test]
+fn frigate_duel_standoff_sits_inside_gun_envelope() {
+    let duel = FrigateDuel::new();
+    let standoff = duel.standoff(300.0);
+    assert!(standoff <= duel.gun_range);
+    assert!(standoff > no_escape_range(&duel.enemy_missile, 300.0));
+    assert_eq!(duel.standoff(0.0), duel.gun_range);
+}
+
+#[test]
+fn cruiser_duel_releases_missiles_to_arrive_with_torpedoes() {
+    use spacepoort::controllers::CruiserDuel;
+    let mut duel = CruiserDuel::new();
+    assert!(!duel.release(0.0, 10.0));
+    duel.open_wave(0.0, 40.0);
+    assert!(!duel.release(20.0, 10.0));
+    assert!(duel.release(30.0, 10.0));
+}
+
+#[test]
+fn base_defense_takes_earliest_arrival_in_its_sector() {
+    use oort_api::prelude::{Class, ScanResult};
+    use spacepoort::controllers::BaseDefense;
+    use spacepoort::math::*;
+    use spacepoort::perception::Tracker;
+
+    let mut tracker = Tracker::new();
+    for (position, velocity) in [
+        (vec2(2_000.0, 100.0), vec2(-100.0, 0.0)),
+        (vec2(3_000.0, 0.0), vec2(-300.0, 0.0)),
+        (vec2(-1_500.0, 0.0), vec2(300.0, 0.0)),
+        (vec2(2_000.0, 2_000.0), vec2(0.0, 100.0)),
+    ] {
+        let contact = ScanResult {
+            class: Class::Missile,
+            position,
+            velocity,
+            rssi: 0.0,
+            snr: 0.0,
+        };
+        tracker.update(&contact, 0.0);
+    }
+    let defense = BaseDefense::new(vec2(0.0, 0.0), 0, 2);
+    let id = defense.priority(tracker.tracks()).unwrap();
+    let track = tracker.get(id).unwrap();
+    assert_eq!(track.position.x, 3_000.0);
+    let other = BaseDefense::new(vec2(0.0, 0.0), 1, 2);
+    let id = other.priority(tracker.tracks()).unwrap();
+    assert_eq!(tracker.get(id).unwrap().position.x, -1_500.0);
+}
+
+#[test]
+fn furball_turns_back_before_leaving_the_arena() {
+    use spacepoort::controllers::Furball;
+    use spacepoort::math::*;
+    use spacepoort::planning::Arena;
+    use spacepoort::sim::MockMotor;
+
+    let furball = Furball::new();
+    let arena = Arena {
+        half_size: 5_000.0,
+        margin: 500.0,
+    };
+    let mut ship = MockMotor::fighter(vec2(4_000.0, 0.0));
+    assert!(furball.boundary(&ship, &arena).is_none());
+    ship.velocity = vec2(400.0, 0.0);
+    let inward = furball.boundary(&ship, &arena).unwrap();
+    assert!(inward.x < 0.0);
+}
+
+#[test]
+fn strobes_from_two_positions_triangulate_the_jammer() {
+    use spacepoort::math::*;
+    use spacepoort::perception::{triangulate, Strobe};
+
+    let jammer = vec2(4_000.0, 3_000.0);
+    let strobe = |origin: Vec2, time| Strobe {
+        origin,
+        bearing: (jammer - origin).angle(),
+        time,
+    };
+    let one = strobe(vec2(0.0, 0.0), 0.0);
+    assert!(triangulate(&[one], 0.05).is_none());
+    assert!(triangulate(&[one, strobe(vec2(1.0, 1.0), 0.1)], 0.05).is_none());
+    let estimate = triangulate(&[one, strobe(vec2(2_000.0, -1_000.0), 1.0)], 0.05).unwrap();
+    assert!(estimate.distance(jammer) < 1e-6);
+}
+
+#[test]
+fn bearing_only_motion_analysis_needs_own_ship_maneuver() {
+    use spacepoort::math::*;
+    use spacepoort::perception::{motion_analysis, Strobe};
+
+    let target = |t: f64| vec2(6_000.0, 2_000.0) + vec2(-40.0, 90.0) * t;
+    let strobe = |origin: Vec2, t: f64| Strobe {
+        origin,
+        bearing: (target(t) - origin).angle(),
+        time: t,
+    };
+    let straight: Vec<Strobe> = (0..40)
+        .map(|i| i as f64 * 0.25)
+        .map(|t| strobe(vec2(100.0, 0.0) * t, t))
+        .collect();
+    assert!(motion_analysis(&straight, 1e-9).is_none());
+
+    let dogleg: Vec<Strobe> = (0..40)
+        .map(|i| i as f64 * 0.25)
+        .map(|t| {
+            let origin = if t < 5.0 {
+                vec2(100.0, 0.0) * t
+            } else {
+                vec2(500.0, 0.0) + vec2(0.0, -100.0) * (t - 5.0)
+            };
+            strobe(origin, t)
+        })
+        .collect();
+    let motion = motion_analysis(&dogleg, 1e-9).unwrap();
+    assert_eq!(motion.time, 9.75);
+    assert!(motion.position.distance(target(9.75)) < 1.0);
+    assert!(motion.velocity.distance(vec2(-40.0, 90.0)) < 0.1);
+}
+
+#[test]
+fn registry_patterns_support_wildcards() {
+    use spacepoort::registry::matches;
+    assert!(matches("frigate_duel", "frigate_duel"));
+    assert!(!matches("frigate_duel", "frigate_duel_2"));
+    assert!(matches("tutorial_acceleration*", "tutorial_acceleration2"));
+    assert!(matches("*gunnery*", "tutorial_gunnery_range"));
+    assert!(matches("*", ""));
+    assert!(!matches("a*b*c", "acb"));
+    assert!(matches("a*b*c", "abbc"));
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn shield_waits_for_the_densest_salvo() {
+    use oort_api::prelude::{Ability, Class};
+    use spacepoort::abilities::{densest_window, Abilities};
+    use spacepoort::time::Seconds;
+    use spacepoort::warning::{Munition, Warning};
+
+    let warnings = |times: &[f64]| -> Vec<Warning> {
+        times
+            .iter()
+            .enumerate()
+            .map(|(i, &time_to_impact)| Warning {
+                track: i as u32,
+                munition: Munition::Missile,
+                time_to_impact: Seconds(time_to_impact),
+                miss_distance: 0.0,
+            })
+            .collect()
+    };
+    let window = densest_window(&[0.2, 1.5, 1.6, 1.7, 3.0], 0.75, 0.0).unwrap();
+    assert_eq!(window.start, 1.5);
+    assert_eq!(window.covered, 3);
+    assert!(densest_window(&[0.2, 0.3], 0.75, 1.0).is_none());
+
+    let mut abilities = Abilities::for_class(Class::Cruiser);
+    let salvo = warnings(&[0.2, 1.5, 1.6, 1.7]);
+    assert_eq!(abilities.shield_plan(0.0, &salvo).unwrap().start, 1.5);
+    assert!(!abilities.shield(0.0, &salvo));
+    let arrived = warnings(&[0.2, 0.3, 0.4]);
+    assert!(abilities.shield(1.3, &arrived));
+    assert!(abilities.active(Ability::Shield, 1.5));
+    assert!(abilities.shield_plan(2.0, &arrived).is_none());
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn engagement_planner_cycles_attack_and_egress() {
+    use spacepoort::math::*;
+    use spacepoort::sim::Body;
+    use spacepoort::tactics::{EngagementPlanner, Run, Situation};
+    use spacepoort::time::Seconds;
+    use spacepoort::warning::{Munition, Warning};
+
+    let situation = |range: f64, reload: f64, incoming: f64| Situation {
+        range,
+        closure: 0.0,
+        reach: 5_000.0,
+        reload,
+        incoming,
+    };
+    let mut planner = EngagementPlanner::default();
+    let far = situation(9_000.0, 60.0, 0.0);
+    assert_eq!(planner.update(&far, false), Run::StandOff);
+    assert_eq!(planner.distance(&far), 9_000.0);
+
+    let ready = situation(9_000.0, 10.0, 0.0);
+    assert!(planner.approach_time(&ready) > 10.0);
+    assert_eq!(planner.update(&ready, false), Run::Attack);
+    assert_eq!(planner.distance(&ready), 4_000.0);
+
+    let close = situation(4_000.0, 0.0, 0.0);
+    assert_eq!(planner.update(&close, true), Run::Egress);
+    let reloading = situation(6_000.0, 5.0, 0.0);
+    assert_eq!(planner.update(&reloading, false), Run::Egress);
+    assert_eq!(
+        planner.update(&situation(9_000.0, 5.0, 0.0), false),
+        Run::StandOff
+    );
+
+    let hot = situation(9_000.0, 0.0, 3.0);
+    assert_eq!(planner.update(&hot, false), Run::StandOff);
+    assert_eq!(planner.update(&ready, false), Run::Attack);
+    assert_eq!(
+        planner.update(&situation(6_000.0, 0.0, 5.0), false),
+        Run::Egress
+    );
+
+    let body = |x: f64| Body {
+        position: vec2(x, 0.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    let warning = |time_to_impact: f64| Warning {
+        track: 0,
+        munition: Munition::Missile,
+        time_to_impact: Seconds(time_to_impact),
+        miss_distance: 0.0,
+    };
+    let assessed = planner.assess(
+        &body(0.0),
+        &body(9_000.0),
+        5_000.0,
+        0.0,
+        &[warning(10.0), warning(200.0 / 3.0)],
+    );
+    assert!((planner.approach_time(&assessed) - 100.0 / 3.0).abs() < 1e-9);
+    assert!((assessed.incoming - 1.5).abs() < 1e-9);
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn evasion_adapts_after_repeated_hits_from_one_bearing() {
+    use spacepoort::evasion::AdaptiveEvasion;
+
+    let mut evasion = AdaptiveEvasion::new(7);
+    let bearing = Some(0.5);
+    assert!(evasion.observe(1_000.0, bearing, 0.0).is_none());
+    assert!(evasion.observe(1_000.0, bearing, 0.1).is_none());
+    let (period, amplitude) = (evasion.period, evasion.amplitude);
+    let hit = evasion.observe(900.0, bearing, 0.2).unwrap();
+    assert_eq!(hit.damage, 100.0);
+    assert_eq!(hit.side, 1.0);
+    assert!(evasion.amplitude > amplitude);
+    assert_ne!(evasion.period, period);
+    assert!(evasion.bias(bearing) < 0.0);
+    assert_eq!(evasion.bias(Some(-2.0)), 0.0);
+
+    let mut health = 900.0;
+    for tick in 0..600 {
+        let now = 0.2 + tick as f64 / 60.0;
+        if evasion.side(bearing) > 0.0 && tick % 30 == 0 {
+            health -= 10.0;
+        }
+        evasion.observe(health, bearing, now);
+        assert!((evasion.min_period..=evasion.max_period).contains(&evasion.period));
+    }
+    assert!(evasion.hits().len() > 1);
+    assert!(evasion.hits().iter().all(|hit| hit.side > 0.0));
+    assert_eq!(evasion.bias(bearing), -evasion.max_bias);
+    assert!(evasion.amplitude <= evasion.max_amplitude);
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn danger_map_steers_out_of_a_missile_envelope() {
+    use oort_api::prelude::{Class, ScanResult};
+    use spacepoort::danger::{AvoidDanger, DangerMap, Envelope};
+    use spacepoort::math::*;
+    use spacepoort::movement::Move;
+    use spacepoort::perception::Tracker;
+    use spacepoort::sim::{Body, MockMotor};
+
+    let mut tracker = Tracker::new();
+    tracker.update(
+        &ScanResult {
+            class: Class::Missile,
+            position: vec2(3_000.0, 0.0),
+            velocity: vec2(-400.0, 0.0),
+            rssi: 0.0,
+            snr: 0.0,
+        },
+        0.0,
+    );
+    let map = DangerMap::build(vec2(0.0, 0.0), 5_000.0, 250.0, tracker.tracks());
+    assert!(map.max() > 0.0);
+    assert!(map.risk(vec2(1_000.0, 0.0)) > map.risk(vec2(1_000.0, 1_500.0)));
+    assert_eq!(map.risk(vec2(4_500.0, 0.0)), 0.0);
+    assert!(map.gradient(vec2(1_000.0, 300.0)).y < 0.0);
+
+    let ship = MockMotor::fighter(vec2(1_000.0, 300.0));
+    let output = AvoidDanger {
+        map: &map,
+        gain: 1.0,
+    }
+    .execute(&ship);
+    assert!(output.linear.y > 0.0);
+
+    let fighter = Body {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(100.0, 0.0),
+    };
+    let gun = Envelope::for_class(Class::Fighter)[0];
+    assert!(gun.risk(&fighter, vec2(1_000.0, 0.0)) > gun.risk(&fighter, vec2(-1_000.0, 0.0)));
+    assert_eq!(gun.risk(&fighter, vec2(3_000.0, 0.0)), 0.0);
+}
+
+#[test]
+fn ship_state_carries_the_blackboard_across_ticks() {
+    use spacepoort::context::TickContext;
+    use spacepoort::draw::OortDraw;
+    use spacepoort::profile::Profile;
+    use spacepoort::registry::{Controller, Registry};
+    use spacepoort::Ship;
+
+    struct Counter(f64);
+
+    impl Controller for Counter {
+        fn tick(&mut self, context: &mut TickContext<OortDraw>) {
+            let count = context.blackboard.number("ticks").unwrap_or(0.0);
+            context.blackboard.set("ticks", count + 1.0);
+            context.blackboard.set("range", self.0);
+        }
+    }
+
+    let profile = Profile {
+        engagement_range: 1_234.0,
+        ..Profile::default()
+    };
+    let mut ship = Ship::configured(
+        &Registry::new(|profile| Box::new(Counter(profile.engagement_range))),
+        profile,
+    );
+    for _ in 0..3 {
+        ship.tick();
+    }
+    assert_eq!(ship.state().ticks, 3);
+    assert_eq!(ship.state().blackboard.number("ticks"), Some(3.0));
+    assert_eq!(ship.state().blackboard.number("range"), Some(1_234.0));
+}
@@ -0,0 +1,222 @@
+use spacepoort::control::{Attitude, Pid};
+use spacepoort::math::*;
+use spacepoort::movement::*;
+use spacepoort::sim::MockMotor;
+
+const SECONDS: usize = 60;
+
+fn settle_time(errors: &[f64], tolerance: f64) -> Option<usize> {
+    let last = errors.iter().rposition(|error| error.abs() > tolerance)?;
+    Some(last + 1)
+}
+
+fn sign_changes(errors: &[f64]) -> usize {
+    errors
+        .windows(2)
+        .filter(|pair| pair[0].signum() != pair[1].signum() && pair[0] != 0.0)
+        .count()
+}
+
+#[test]
+fn arrive_converges_across_limits_and_initial_speeds() {
+    for acceleration in [10.0, 60.0, 200.0] {
+        for speed in [0.0, 100.0, 500.0] {
+            for bearing in [0.0, 1.5, 3.0] {
+                let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+                ship.max_linear_acceleration = acceleration;
+                ship.velocity = vec2(speed, 0.0).rotate(bearing);
+                let target = vec2(3_000.0, -1_000.0);
+                let overshoot = speed * speed / (2.0 * acceleration);
+                let ideal = speed / acceleration
+                    + 2.0 * ((target.length() + overshoot) / acceleration).sqrt();
+                let bound = ((1.5 * ideal + 5.0) * SECONDS as f64) as usize;
+                let arrive = Arrive { target, gain: 1.0 };
+                let states = ship.run(&arrive, bound + 10 * SECONDS);
+                let errors: Vec<f64> = states
+                    .iter()
+                    .map(|state| state.position.distance(target))
+                    .collect();
+                let settled = settle_time(&errors, 5.0).unwrap_or(0);
+                assert!(
+                    settled < bound,
+                    "acceleration {} speed {} bearing {}: settled at tick {}",
+                    acceleration,
+                    speed,
+                    bearing,
+                    settled
+                );
+                assert!(states.last().unwrap().velocity.length() < 1.0);
+            }
+        }
+    }
+}
+
+#[test]
+fn align_converges_without_sustained_oscillation() {
+    for acceleration in [0.5, 2.0, std::f64::consts::TAU] {
+        for initial_rate in [-2.0, 0.0, 2.0] {
+            for target in [0.5, 2.0, -3.0] {
+                let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+                ship.max_angular_acceleration = acceleration;
+                ship.angular_velocity = initial_rate;
+                let align = Align {
+                    target,
+                    feedforward: 0.0,
+                    attitude: Attitude::default(),
+                };
+                let states = ship.run(&align, 20 * SECONDS);
+                let errors: Vec<f64> = states
+                    .iter()
+                    .map(|state| angle_diff(state.heading, target))
+                    .collect();
+                let settled = settle_time(&errors, 0.01).unwrap_or(0);
+                assert!(
+                    settled < 15 * SECONDS,
+                    "acceleration {} rate {} target {}: settled at tick {}",
+                    acceleration,
+                    initial_rate,
+                    target,
+                    settled
+                );
+                let tail = &errors[settled.max(1) - 1..];
+                assert!(
+                    sign_changes(tail) <= 4,
+                    "acceleration {} rate {} target {}: {} oscillations after settling",
+                    acceleration,
+                    initial_rate,
+                    target,
+                    sign_changes(tail)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn single_precision_pid_tracks_double_precision() {
+    let mut double = Pid::new(2.0, 0.5, 0.1).with_limits(-10.0, 10.0);
+    let mut single = Pid::<f32>::new(2.0, 0.5, 0.1).with_limits(-10.0, 10.0);
+    let (mut x, mut y) = (0.0_f64, 0.0_f32);
+    double.setpoint = 5.0;
+    single.setpoint = 5.0;
+    for _ in 0..30 * SECONDS {
+        x += double.update(x) * TICK_LENGTH;
+        y += single.update(y) * TICK_LENGTH as f32;
+    }
+    assert!((x - 5.0).abs() < 0.05, "double settled at {}", x);
+    assert!(
+        (x - y as f64).abs() < 1e-3,
+        "single {} drifted from double {}",
+        y,
+        x
+    );
+}
+
+#[test]
+fn pid_derivative_is_unfiltered_unless_requested() {
+    let mut raw = Pid::new(0.0, 0.0, 1.0);
+    let mut filtered = Pid::new(0.0, 0.0, 1.0).with_derivative_filter(0.05);
+    for pid in [&mut raw, &mut filtered] {
+        pid.update_error(0.0);
+    }
+    assert!((raw.update_error(1.0) - 1.0 / TICK_LENGTH).abs() < 1e-9);
+    assert!(filtered.update_error(1.0) < 0.5 / TICK_LENGTH);
+}
+
+#[test]
+fn velocity_controller_clamps_to_each_thruster_and_rotates_when_saturated() {
+    use spacepoort::control::{Thrusters, VelocityController};
+
+    let thrusters = Thrusters {
+        forward: 60.0,
+        backward: 30.0,
+        lateral: 20.0,
+    };
+    let ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let mut controller = VelocityController::new(thrusters);
+    let forward = controller.update(&ship, vec2(1_000.0, 0.0));
+    assert!((forward.linear.length() - 60.0).abs() < 1e-9);
+    let backward = controller.update(&ship, vec2(-1_000.0, 0.0));
+    assert!((backward.linear.length() - 30.0).abs() < 1e-9);
+    let diagonal = controller.update(&ship, vec2(1_000.0, 1_000.0));
+    assert!((diagonal.linear.y - 20.0).abs() < 1e-9);
+    assert!((diagonal.linear.x - 20.0).abs() < 1e-9);
+    assert_eq!(diagonal.angular, 0.0);
+
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let mut controller = VelocityController::new(thrusters);
+    let desired = vec2(0.0, 500.0);
+    let persistence = (controller.persistence * SECONDS as f64).ceil() as usize;
+    for _ in 0..persistence - 1 {
+        let output = controller.update(&ship, desired);
+        assert_eq!(output.angular, 0.0);
+        ship.step(&output);
+    }
+    for _ in 0..5 * SECONDS {
+        let output = controller.update(&ship, desired);
+        ship.step(&output);
+    }
+    assert!(angle_diff(ship.heading, desired.angle()).abs() < 0.1);
+    assert!(ship.velocity.y > 100.0);
+    let output = controller.update(&ship, ship.velocity);
+    assert!(!controller.rotating());
+    assert_eq!(output.angular, 0.0);
+}
+
+#[test]
+fn tracking_converges_onto_a_circular_trajectory() {
+    use spacepoort::control::Tracking;
+    use spacepoort::time::Seconds;
+
+    let (radius, rate) = (500.0, 0.2);
+    let circle = |time: Seconds| {
+        let angle = rate * time.0;
+        (
+            vec2(angle.cos(), angle.sin()) * radius,
+            vec2(-angle.sin(), angle.cos()) * (radius * rate),
+        )
+    };
+    let mut ship = MockMotor::fighter(vec2(radius + 150.0, -100.0));
+    let mut errors = Vec::new();
+    for tick in 0..20 * SECONDS {
+        let time = Seconds(tick as f64 / SECONDS as f64);
+        ship.tick(&Tracking {
+            trajectory: &circle,
+            time,
+            position_gain: 1.0,
+            velocity_gain: 2.0,
+        });
+        let (position, _) = circle(Seconds((tick + 1) as f64 / SECONDS as f64));
+        errors.push(ship.position.distance(position));
+    }
+    assert!(errors[0] > 100.0);
+    let settled = settle_time(&errors, 2.0).unwrap_or(0);
+    assert!(settled < 12 * SECONDS, "settled after {settled} ticks");
+    let (_, velocity) = circle(Seconds(20.0));
+    assert!(ship.velocity.distance(velocity) < 1.0);
+}
+
+#[test]
+fn deadband_enters_and_exits_with_hysteresis() {
+    use spacepoort::control::{Actuation, Deadband};
+
+    let mut band = Deadband::new(0.5, 2.0);
+    let sequence = [1.0, 2.0, 2.5, 1.0, 0.5, 0.4, 1.0, 2.1];
+    let active: Vec<bool> = sequence.iter().map(|m| band.active(*m)).collect();
+    assert_eq!(active, [false, false, true, true, true, false, false, true]);
+    assert!(!Deadband::new(2.0, 0.5).active(1.0));
+
+    let mut actuation = Actuation::default();
+    let output = |x: f64, angular: f64| Output {
+        linear: vec2(x, 0.0),
+        angular,
+    };
+    let quiet = actuation.apply(output(1.0, 0.03));
+    assert_eq!((quiet.linear.x, quiet.angular), (0.0, 0.0));
+    let firing = actuation.apply(output(3.0, 0.06));
+    assert_eq!((firing.linear.x, firing.angular), (3.0, 0.06));
+    let holding = actuation.apply(output(1.0, 0.03));
+    assert_eq!((holding.linear.x, holding.angular), (1.0, 0.03));
+    let released = actuation.apply(output(0.4, 0.005));
+    assert_eq!((released.linear.x, released.angular), (0.0, 0.0));
+}
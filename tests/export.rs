@@ -0,0 +1,32 @@
+use spacepoort::export::{parse, Record};
+use spacepoort::math::*;
+use spacepoort::movement::*;
+use spacepoort::sim::MockMotor;
+
+#[test]
+fn records_round_trip_through_console_text() {
+    let ship = MockMotor::fighter(vec2(120.0, -40.0));
+    let output = Seek::new(vec2(500.0, 500.0)).execute(&ship);
+    let records = [
+        Record::state(7, &ship),
+        Record::Track {
+            tick: 7,
+            id: 3,
+            class: 1,
+            position: vec2(900.0, 10.0),
+            velocity: vec2(-5.0, 2.5),
+            age: 0.5,
+        },
+        Record::steering(7, &output),
+    ];
+    let console = records
+        .iter()
+        .map(|record| format!("noise before\n{}\n", record.encode()))
+        .collect::<String>();
+    let parsed = parse(&console);
+    assert_eq!(parsed.len(), records.len());
+    for (parsed, original) in parsed.iter().zip(&records) {
+        assert_eq!(parsed.encode(), original.encode());
+        assert_eq!(parsed.tick(), 7);
+    }
+}
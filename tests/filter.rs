@@ -0,0 +1,171 @@
+use spacepoort::filter::{DynKalman, Gate, Outlier, Verdict};
+
+fn settled(gate: Gate) -> DynKalman {
+    let mut filter = DynKalman::default().with_gate(gate);
+    for tick in 0..120 {
+        let t = tick as f64 / 60.0;
+        filter.predict(1.0 / 60.0);
+        filter.update(100.0 + 50.0 * t, 50.0);
+    }
+    filter
+}
+
+#[test]
+fn single_spike_is_rejected() {
+    let mut filter = settled(Gate::default());
+    let before = filter.position;
+    filter.predict(1.0 / 60.0);
+    assert_eq!(filter.update(before + 5_000.0, 50.0), Verdict::Rejected);
+    assert!((filter.position - (before + 50.0 / 60.0)).abs() < 1e-6);
+    assert!((filter.velocity - 50.0).abs() < 1.0);
+    assert_eq!(filter.misses(), 1);
+    filter.predict(1.0 / 60.0);
+    assert_eq!(
+        filter.update(before + 100.0 / 60.0, 50.0),
+        Verdict::Accepted
+    );
+    assert_eq!(filter.misses(), 0);
+}
+
+#[test]
+fn persistent_jump_resets_after_max_misses() {
+    let mut filter = settled(Gate::default());
+    let jump = filter.position + 5_000.0;
+    let verdicts: Vec<Verdict> = (0..4).map(|_| filter.update(jump, 50.0)).collect();
+    assert_eq!(
+        verdicts,
+        [
+            Verdict::Rejected,
+            Verdict::Rejected,
+            Verdict::Rejected,
+            Verdict::Reset
+        ]
+    );
+    assert_eq!(filter.position, jump);
+}
+
+#[test]
+fn downweighting_limits_pull_of_outlier() {
+    let mut open = settled(Gate::open());
+    let mut weighted = settled(Gate::default().with_policy(Outlier::Downweight));
+    let target = open.position + 2_000.0;
+    assert_eq!(open.update(target, 50.0), Verdict::Accepted);
+    assert_eq!(weighted.update(target, 50.0), Verdict::Downweighted);
+    let start = target - 2_000.0;
+    assert!(weighted.position - start < 0.2 * (open.position - start));
+}
+
+#[test]
+fn vector_filter_matches_per_axis_filters() {
+    use spacepoort::filter::VecKalman;
+    use spacepoort::math::vec2;
+    use spacepoort::sim::Body;
+    use spacepoort::targeting::intercept;
+
+    let mut x = DynKalman::default().with_gate(Gate::open());
+    let mut y = DynKalman::default().with_gate(Gate::open());
+    let mut joint = VecKalman::default().with_gate(Gate::open());
+    for tick in 0..90 {
+        let t = tick as f64 / 60.0;
+        let (px, py) = (1_000.0 + 80.0 * t + (t * 7.0).sin(), -300.0 * t);
+        let (vx, vy) = (80.0 + (t * 3.0).cos(), -300.0);
+        for filter in [&mut x, &mut y] {
+            filter.predict(1.0 / 60.0);
+        }
+        joint.predict(1.0 / 60.0);
+        x.update(px, vx);
+        y.update(py, vy);
+        joint.update(vec2(px, py), vec2(vx, vy));
+    }
+    assert!((joint.position.x - x.position).abs() < 1e-9);
+    assert!((joint.position.y - y.position).abs() < 1e-9);
+    assert!((joint.velocity.y - y.velocity).abs() < 1e-9);
+
+    let shooter = Body {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    assert!(intercept(&shooter, &joint, 1_000.0).is_some());
+}
+
+#[test]
+fn filter_bank_follows_track_lifetimes() {
+    use spacepoort::filter::FilterBank;
+    use spacepoort::math::vec2;
+
+    let mut bank = FilterBank::<u32>::default();
+    assert_eq!(
+        bank.update(1, vec2(0.0, 0.0), vec2(10.0, 0.0), 0.0),
+        Verdict::Reset
+    );
+    bank.update(2, vec2(500.0, 0.0), vec2(0.0, 0.0), 0.0);
+    assert_eq!(
+        bank.update(1, vec2(10.0, 0.0), vec2(10.0, 0.0), 1.0),
+        Verdict::Accepted
+    );
+    let (position, velocity) = bank.estimate(1, 2.0).unwrap();
+    assert!(position.distance(vec2(20.0, 0.0)) < 1e-6);
+    assert!(velocity.distance(vec2(10.0, 0.0)) < 1e-6);
+
+    bank.predict(3.0);
+    assert!(bank.entries().iter().all(|entry| entry.updated == 3.0));
+    bank.sync(|id| id != 2);
+    assert_eq!(bank.len(), 1);
+    assert!(bank.get(2).is_none());
+    assert!(bank.remove(1));
+    assert!(bank.is_empty());
+}
+
+#[test]
+fn calibration_recovers_range_dependent_noise() {
+    use spacepoort::filter::{Calibration, VecKalman};
+    use spacepoort::math::vec2;
+    use spacepoort::random::Rng;
+
+    let reference = vec2(1_000.0, -500.0);
+    let sigma = |range: f64| 5.0 + 0.01 * range;
+    let mut rng = Rng::new(966);
+    let mut gaussian = move || {
+        let (u, v) = (rng.unit().max(1e-12), rng.unit());
+        (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+    };
+    let mut calibration = Calibration::new();
+    for i in 0..20_000 {
+        let range = 1_000.0 + (i % 40) as f64 * 1_000.0;
+        let origin = reference + vec2(range, 0.0);
+        let error = vec2(gaussian(), gaussian()) * sigma(range);
+        let drift = vec2(gaussian(), gaussian()) * 0.5 * sigma(range);
+        calibration.record(origin, reference + error, drift);
+    }
+    let model = calibration.model().unwrap();
+    for range in [2_000.0, 20_000.0, 38_000.0] {
+        let expected = sigma(range).powi(2);
+        let position = model.position.at(range);
+        assert!(
+            (position / expected - 1.0).abs() < 0.15,
+            "{} {}",
+            range,
+            position
+        );
+        let velocity = model.velocity.at(range);
+        assert!((velocity / (expected * 0.25) - 1.0).abs() < 0.15);
+    }
+    let mut filter = VecKalman::default();
+    model.apply(&mut filter, 30_000.0);
+    assert!(filter.position_noise > 50_000.0);
+}
+
+#[test]
+fn single_precision_filter_tracks_double_precision() {
+    let mut wide = DynKalman::<f64>::default();
+    let mut narrow = DynKalman::<f32>::default();
+    for tick in 0..120 {
+        let t = tick as f64 / 60.0;
+        wide.predict(1.0 / 60.0);
+        narrow.predict(1.0 / 60.0);
+        wide.update(100.0 + 50.0 * t, 50.0);
+        narrow.update(100.0 + 50.0 * t as f32, 50.0);
+    }
+    assert!((wide.position - narrow.position as f64).abs() < 0.05);
+    assert!((wide.velocity - narrow.velocity as f64).abs() < 0.05);
+}
@@ -0,0 +1,537 @@
+use spacepoort::control::{Attitude, Lqr, Pid};
+use spacepoort::math::*;
+use spacepoort::movement::*;
+use spacepoort::random::Rng;
+use spacepoort::sim::{Body, MockMotor};
+
+fn hostile(rng: &mut Rng) -> f64 {
+    const SPECIAL: [f64; 6] = [
+        f64::NAN,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        0.0,
+        1e300,
+        -1e-300,
+    ];
+    if rng.unit() < 0.3 {
+        SPECIAL[(rng.next_u64() % SPECIAL.len() as u64) as usize]
+    } else {
+        rng.uniform(-1e4, 1e4)
+    }
+}
+
+fn point(rng: &mut Rng) -> Vec2 {
+    vec2(hostile(rng), hostile(rng))
+}
+
+fn actor(rng: &mut Rng) -> MockMotor {
+    MockMotor {
+        position: point(rng),
+        velocity: point(rng),
+        heading: hostile(rng),
+        angular_velocity: hostile(rng),
+        ..MockMotor::fighter(vec2(0.0, 0.0))
+    }
+}
+
+#[test]
+fn strategies_never_emit_non_finite_output() {
+    let mut rng = Rng::new(918);
+    for _ in 0..5_000 {
+        let ship = actor(&mut rng);
+        let target = point(&mut rng);
+        let body = Body {
+            position: point(&mut rng),
+            velocity: point(&mut rng),
+        };
+        let outputs = [
+            Seek { target }.execute(&ship),
+            Arrive { target, gain: 1.0 }.execute(&ship),
+            KeepDistance {
+                target,
+                distance: hostile(&mut rng),
+            }
+            .execute(&ship),
+            Align {
+                target: hostile(&mut rng),
+                feedforward: hostile(&mut rng),
+                attitude: Attitude::default(),
+            }
+            .execute(&ship),
+            Face {
+                target: body,
+                attitude: Attitude::default(),
+            }
+            .execute(&ship),
+            OffsetPursuit {
+                leader: body,
+                offset: point(&mut rng),
+                gain: 1.0,
+            }
+            .execute(&ship),
+            Station {
+                position: target,
+                velocity: point(&mut rng),
+                heading: hostile(&mut rng),
+                lqr: Lqr::default(),
+            }
+            .execute(&ship),
+        ];
+        for output in outputs {
+            assert!(output.is_finite(), "{:?} {:?}", ship, target);
+        }
+    }
+}
+
+#[test]
+fn coincident_positions_do_not_produce_nan() {
+    let ship = MockMotor::fighter(vec2(10.0, 10.0));
+    assert!(Seek {
+        target: vec2(10.0, 10.0)
+    }
+    .execute(&ship)
+    .is_finite());
+    assert!(KeepDistance {
+        target: vec2(10.0, 10.0),
+        distance: 100.0
+    }
+    .execute(&ship)
+    .is_finite());
+}
+
+#[test]
+fn pid_ignores_non_finite_measurements() {
+    let mut pid = Pid::new(1.0, 0.5, 0.1);
+    pid.setpoint = 10.0;
+    pid.update(0.0);
+    let integral = pid.integral();
+    assert_eq!(pid.update(f64::NAN), 0.0);
+    assert_eq!(pid.update(f64::INFINITY), 0.0);
+    assert_eq!(pid.integral(), integral);
+    assert!(pid.update(5.0).is_finite());
+}
+
+#[test]
+fn tracker_rejects_poisoned_contacts() {
+    use spacepoort::contact::{Class, ScanResult};
+    use spacepoort::perception::Tracker;
+
+    let mut rng = Rng::new(919);
+    let mut tracker = Tracker::new();
+    for tick in 0..5_000 {
+        let contact = ScanResult {
+            class: Class::Fighter,
+            position: point(&mut rng),
+            velocity: point(&mut rng),
+            rssi: 0.0,
+            snr: 0.0,
+        };
+        let accepted = tracker.update(&contact, tick as f64 * TICK_LENGTH);
+        let finite = [
+            contact.position.x,
+            contact.position.y,
+            contact.velocity.x,
+            contact.velocity.y,
+        ]
+        .iter()
+        .all(|value| value.is_finite());
+        assert_eq!(accepted.is_some(), finite);
+        for track in tracker.tracks() {
+            assert!(track.position.x.is_finite() && track.position.y.is_finite());
+            assert!(track.velocity.x.is_finite() && track.velocity.y.is_finite());
+        }
+    }
+}
+
+#[test]
+fn bounded_tracker_evicts_stalest_track() {
+    use spacepoort::contact::{Class, ScanResult};
+    use spacepoort::perception::Tracker;
+
+    let mut tracker = Tracker::<4>::bounded();
+    for i in 0..6 {
+        let contact = ScanResult {
+            class: Class::Fighter,
+            position: vec2(i as f64 * 1_000.0, 0.0),
+            velocity: vec2(0.0, 0.0),
+            rssi: 0.0,
+            snr: 0.0,
+        };
+        tracker.update(&contact, i as f64);
+    }
+    assert_eq!(tracker.tracks().len(), 4);
+    let mut ids: Vec<u32> = tracker.tracks().iter().map(|track| track.id).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, [2, 3, 4, 5]);
+    assert_eq!(&*tracker.prune(5.0), [0, 1]);
+    assert!(tracker.prune(5.0).is_empty());
+}
+
+#[test]
+fn fixed_vec_retains_in_order_without_growing() {
+    use spacepoort::storage::FixedVec;
+
+    let mut values = FixedVec::<u32, 8>::new(0);
+    for value in 0..8 {
+        values.push(value).unwrap();
+    }
+    assert_eq!(values.push(8), Err(8));
+    values.retain(|value| value % 3 != 0);
+    assert_eq!(&values[..], [1, 2, 4, 5, 7]);
+    assert_eq!(values.capacity(), 8);
+}
+
+#[test]
+fn fast_math_tracks_libm() {
+    use spacepoort::math::fast;
+
+    let mut rng = Rng::new(957);
+    for _ in 0..10_000 {
+        let angle = rng.uniform(-20.0, 20.0);
+        let (sin, cos) = fast::sin_cos(angle);
+        assert!((sin - angle.sin()).abs() < 1e-5, "sin {}", angle);
+        assert!((cos - angle.cos()).abs() < 1e-5, "cos {}", angle);
+        let (y, x) = (rng.uniform(-1e3, 1e3), rng.uniform(-1e3, 1e3));
+        assert!(
+            (fast::atan2(y, x) - y.atan2(x)).abs() < 1e-5,
+            "atan2 {} {}",
+            y,
+            x
+        );
+        let value = rng.uniform(1e-6, 1e8);
+        assert!((fast::inv_sqrt(value) * value.sqrt() - 1.0).abs() < 1e-9);
+    }
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn order_payloads_round_trip_through_bits() {
+    use spacepoort::radio::{decode, encode, Bid, FormationCommand};
+    use spacepoort::tactics::{PincerOrder, VolleyOrder};
+
+    let mut rng = Rng::new(958);
+    for _ in 0..200 {
+        let position = vec2(rng.uniform(-5e4, 5e4), rng.uniform(-5e4, 5e4));
+        let angle = rng.uniform(-3.0, 3.0);
+        let time = (rng.uniform(0.0, 600.0) / TICK_LENGTH).round() * TICK_LENGTH;
+
+        let pincer = PincerOrder {
+            element: 2,
+            target: position,
+            bearing: angle,
+            strike_time: time,
+        };
+        let decoded: PincerOrder = decode(encode(&pincer, 1)).unwrap();
+        assert_eq!(decoded.element, 2);
+        assert!(decoded.target.distance(position) < 1e-3);
+        assert!((decoded.bearing - angle).abs() < 1e-8);
+        assert!((decoded.strike_time - time).abs() < 1e-9);
+
+        let formation = FormationCommand {
+            shape: 3,
+            anchor: position,
+            heading: angle,
+            spacing: 250.0,
+        };
+        let decoded: FormationCommand = decode(encode(&formation, 1)).unwrap();
+        assert_eq!(decoded.shape, 3);
+        assert!(decoded.anchor.distance(position) < 1e-3);
+        assert!((decoded.heading - angle).abs() < 1e-8);
+        assert!((decoded.spacing - 250.0).abs() < 1e-3);
+
+        let bid = Bid {
+            position,
+            score: rng.uniform(0.0, 1e-2),
+        };
+        let decoded: Bid = decode(encode(&bid, 1)).unwrap();
+        assert!(decoded.position.distance(position) < 1e-3);
+        assert_eq!(decoded.score, bid.score);
+
+        let volley = VolleyOrder {
+            ship: 5,
+            target: position,
+            launch_time: time,
+            arrival: time + 12.5,
+        };
+        let decoded: VolleyOrder = decode(encode(&volley, 1)).unwrap();
+        assert_eq!(decoded.ship, 5);
+        assert!(decoded.target.distance(position) < 1e-3);
+        assert!((decoded.launch_time - time).abs() < 1e-9);
+        assert!((decoded.arrival - (time + 12.5)).abs() < 1e-9);
+    }
+
+    let poisoned = Bid {
+        position: vec2(0.0, 0.0),
+        score: f64::NAN,
+    };
+    assert!(decode::<Bid>(encode(&poisoned, 1)).is_none());
+    let poisoned = VolleyOrder {
+        ship: 5,
+        target: vec2(f64::INFINITY, 0.0),
+        launch_time: 1.0,
+        arrival: 2.0,
+    };
+    assert!(decode::<VolleyOrder>(encode(&poisoned, 1)).is_none());
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn contact_report_packs_into_one_message() {
+    use oort_api::prelude::Class;
+    use spacepoort::radio::{decode, encode, Bits, ContactReport};
+
+    let mut rng = Rng::new(11);
+    for _ in 0..200 {
+        let report = ContactReport {
+            class: Class::Frigate,
+            position: vec2(rng.uniform(-5e4, 5e4), rng.uniform(-5e4, 5e4)),
+            velocity: vec2(rng.uniform(-3e3, 3e3), rng.uniform(-3e3, 3e3)),
+        };
+        let decoded: ContactReport = decode(encode(&report, 3)).unwrap();
+        assert_eq!(decoded.class, Class::Frigate);
+        assert!(decoded.position.distance(report.position) < 1e-3);
+        assert!(decoded.velocity.distance(report.velocity) < 2e-3);
+    }
+
+    let mut bits = Bits::new();
+    bits.put(0b101, 3).put(u64::MAX, 64).put(7, 70 - 64);
+    let mut read = Bits::read(bits.words());
+    assert_eq!(read.take(3), Some(0b101));
+    assert_eq!(read.take(64), Some(u64::MAX));
+    assert_eq!(read.take(6), Some(7));
+    assert_eq!(read.finish(), Some(()));
+
+    let mut message = encode(
+        &ContactReport {
+            class: Class::Fighter,
+            position: vec2(1.0, 2.0),
+            velocity: vec2(0.0, 0.0),
+        },
+        1,
+    );
+    message[3] = f64::from_bits(u64::MAX);
+    assert!(decode::<ContactReport>(message).is_none());
+
+    for poison in [f64::NAN, f64::INFINITY, -f64::INFINITY, 1e9] {
+        let poisoned = [
+            ContactReport {
+                class: Class::Fighter,
+                position: vec2(poison, 0.0),
+                velocity: vec2(0.0, 0.0),
+            },
+            ContactReport {
+                class: Class::Fighter,
+                position: vec2(0.0, 0.0),
+                velocity: vec2(0.0, poison),
+            },
+        ];
+        for report in poisoned {
+            assert!(decode::<ContactReport>(encode(&report, 1)).is_none());
+        }
+    }
+}
+
+#[test]
+fn scratch_arena_stops_growing_after_first_tick() {
+    use spacepoort::planning::{Obstacle, VisibilityGraph};
+    use spacepoort::storage::Scratch;
+
+    let planner = VisibilityGraph::default();
+    let obstacles = [
+        Obstacle {
+            position: vec2(1_000.0, 0.0),
+            radius: 300.0,
+        },
+        Obstacle {
+            position: vec2(2_000.0, 200.0),
+            radius: 300.0,
+        },
+    ];
+    let (start, goal) = (vec2(0.0, 0.0), vec2(3_000.0, 0.0));
+    let mut scratch = Scratch::with_capacity(0);
+    let first = planner
+        .plan_in(&mut scratch, start, goal, &obstacles)
+        .unwrap();
+    let capacity = scratch.vectors.capacity() + scratch.scalars.capacity();
+    for _ in 0..10 {
+        scratch.reset();
+        let path = planner
+            .plan_in(&mut scratch, start, goal, &obstacles)
+            .unwrap();
+        assert_eq!(path.len(), first.len());
+    }
+    assert_eq!(
+        scratch.vectors.capacity() + scratch.scalars.capacity(),
+        capacity
+    );
+    assert_eq!(scratch.vectors.high_water(), scratch.vectors.len());
+}
+
+#[test]
+fn batch_association_assigns_globally_nearest() {
+    use spacepoort::contact::{Class, ScanResult};
+    use spacepoort::filter::{Gate, NoiseModel, Variance, VecKalman};
+    use spacepoort::perception::Tracker;
+    use spacepoort::storage::Scratch;
+
+    let contact = |x: f64| ScanResult {
+        class: Class::Fighter,
+        position: vec2(x, 0.0),
+        velocity: vec2(0.0, 0.0),
+        rssi: 0.0,
+        snr: 0.0,
+    };
+    let mut tracker = Tracker::new();
+    tracker.filter = VecKalman::new(3_600.0, 0.0, 0.0).with_gate(Gate::open());
+    tracker.noise = NoiseModel {
+        position: Variance::constant(0.0),
+        velocity: Variance::constant(0.0),
+    };
+    let a = tracker.update(&contact(0.0), 0.0).unwrap();
+    let b = tracker.update(&contact(300.0), 0.0).unwrap();
+    let mut scratch = Scratch::new();
+    let created = tracker.update_all(
+        vec2(0.0, 0.0),
+        &[contact(140.0), contact(10.0), contact(5_000.0)],
+        0.1,
+        &mut scratch,
+    );
+    assert_eq!(created, 1);
+    let (a, b) = (tracker.get(a).unwrap(), tracker.get(b).unwrap());
+    assert_eq!(a.position.x, 10.0);
+    assert_eq!(b.position.x, 140.0);
+}
+
+#[test]
+fn tracker_applies_range_noise_on_every_update() {
+    use spacepoort::contact::{Class, ScanResult};
+    use spacepoort::filter::{NoiseModel, Variance, VecKalman};
+    use spacepoort::perception::Tracker;
+
+    let contact = |x: f64| ScanResult {
+        class: Class::Fighter,
+        position: vec2(x, 0.0),
+        velocity: vec2(0.0, 0.0),
+        rssi: 0.0,
+        snr: 0.0,
+    };
+    let mut tracker = Tracker::new();
+    tracker.noise = NoiseModel {
+        position: Variance::linear(10.0, 0.01),
+        velocity: Variance::linear(5.0, 0.005),
+    };
+    let id = tracker
+        .update_from(vec2(-20_000.0, 0.0), &contact(0.0), 0.0)
+        .unwrap();
+    let far = tracker.get(id).unwrap().filter.position_noise;
+    assert_eq!(far, tracker.noise.position.at(20_000.0));
+    tracker.update(&contact(1.0), 0.1);
+    let near = tracker.get(id).unwrap().filter.position_noise;
+    assert_eq!(near, tracker.noise.position.at(0.0));
+    assert!(far > near);
+    assert_eq!(
+        tracker.filter.position_noise,
+        VecKalman::default().position_noise
+    );
+}
+
+#[test]
+fn fused_reports_update_the_filter_at_the_measurement_time() {
+    use spacepoort::contact::{Class, ContactReport, ScanResult};
+    use spacepoort::perception::Tracker;
+
+    let mut tracker = Tracker::new();
+    let id = tracker
+        .update(
+            &ScanResult {
+                class: Class::Fighter,
+                position: vec2(0.0, 0.0),
+                velocity: vec2(100.0, 0.0),
+                rssi: 0.0,
+                snr: 0.0,
+            },
+            0.0,
+        )
+        .unwrap();
+    let before = tracker.get(id).unwrap().uncertainty(1.0);
+    let report = ContactReport {
+        class: Class::Fighter,
+        position: vec2(85.0, 0.0),
+        velocity: vec2(100.0, 0.0),
+    };
+    assert_eq!(tracker.fuse(&report, 7, 0.2, 1.0), Some(id));
+    let track = tracker.get(id).unwrap();
+    assert_eq!(track.updated, 0.8);
+    assert_eq!(track.shared_by, Some(7));
+    assert_eq!(track.hits, 2);
+    assert!(track.position.x > 80.0 && track.position.x < 85.0);
+    assert!(track.uncertainty(1.0) < before);
+    assert!(track.staleness(1.0) > 0.19);
+    assert_eq!(tracker.fuse(&report, 8, 0.5, 1.0), Some(id));
+    assert_eq!(tracker.get(id).unwrap().shared_by, Some(7));
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn decoys_are_flagged_until_they_accelerate() {
+    use oort_api::prelude::{Class, ScanResult};
+    use spacepoort::abilities::Abilities;
+    use spacepoort::perception::Tracker;
+    use spacepoort::sim::Body;
+    use spacepoort::weapons::Coordinator;
+
+    let contact = |class, x: f64, vx: f64| ScanResult {
+        class,
+        position: vec2(x, 0.0),
+        velocity: vec2(vx, 0.0),
+        rssi: 0.0,
+        snr: 0.0,
+    };
+    let mut tracker = Tracker::new();
+    tracker.update(&contact(Class::Torpedo, 5_000.0, -200.0), 0.0);
+    let decoy = tracker
+        .update(&contact(Class::Cruiser, 4_960.0, -200.0), 0.1)
+        .unwrap();
+    let real = tracker
+        .update(&contact(Class::Frigate, 20_000.0, 0.0), 0.1)
+        .unwrap();
+    assert!(tracker.get(decoy).unwrap().is_decoy());
+    assert!(!tracker.get(real).unwrap().is_decoy());
+
+    let coordinator = Coordinator {
+        gun_range: 1_000.0,
+        missile_range: 50_000.0,
+    };
+    let own = Body {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    let assignment = coordinator.assign(&own, tracker.tracks());
+    assert_eq!(assignment.missiles, Some(real));
+    assert_eq!(assignment.torpedoes, Some(real));
+
+    for tick in 1..=120 {
+        let t = 0.1 + tick as f64 / 60.0;
+        let velocity = -200.0 + 60.0 * (t - 0.1);
+        let x = 4_960.0 - 200.0 * (t - 0.1) + 30.0 * (t - 0.1) * (t - 0.1);
+        tracker.update(&contact(Class::Cruiser, x, velocity), t);
+    }
+    assert!(!tracker.get(decoy).unwrap().is_decoy());
+
+    let abilities = Abilities::for_class(Class::Torpedo);
+    let incoming = Body {
+        position: vec2(1_000.0, 0.0),
+        velocity: vec2(-400.0, 0.0),
+    };
+    let receding = Body {
+        position: vec2(1_000.0, 0.0),
+        velocity: vec2(400.0, 0.0),
+    };
+    let distant = Body {
+        position: vec2(5_000.0, 0.0),
+        velocity: vec2(-400.0, 0.0),
+    };
+    assert!(abilities.terminal(&own, &incoming));
+    assert!(!abilities.terminal(&own, &receding));
+    assert!(!abilities.terminal(&own, &distant));
+}
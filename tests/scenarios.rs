@@ -0,0 +1,176 @@
+#![cfg(feature = "scenarios")]
+
+use spacepoort::controllers::Gunnery;
+use spacepoort::math::*;
+use spacepoort::scenario::Scenario;
+use spacepoort::ships::Fighter;
+use spacepoort::sim::{Body, MockMotor};
+
+#[test]
+fn fighter_kills_stationary_target() {
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let mut target = Body {
+        position: vec2(2_000.0, 500.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    let outcome = Scenario::default().run(&mut ship, &mut target, &mut Gunnery::default());
+    assert!(
+        outcome.killed_at.is_some_and(|time| time < 10.0),
+        "{:?}",
+        outcome
+    );
+}
+
+#[test]
+fn fighter_kills_constant_velocity_target() {
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let mut target = Body {
+        position: vec2(3_000.0, -1_000.0),
+        velocity: vec2(-50.0, 120.0),
+    };
+    let outcome = Scenario::default().run(&mut ship, &mut target, &mut Gunnery::default());
+    assert!(
+        outcome.killed_at.is_some_and(|time| time < 30.0),
+        "{:?}",
+        outcome
+    );
+}
+
+#[test]
+fn hunting_fighter_kills_constant_velocity_target() {
+    use oort_api::prelude::Ability;
+    use spacepoort::events::{Event, EventLog};
+
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let mut target = Body {
+        position: vec2(3_000.0, -1_000.0),
+        velocity: vec2(-50.0, 120.0),
+    };
+    let mut fighter = Fighter::new();
+    fighter.events = EventLog::new(4_096);
+    let outcome = Scenario::default().run(&mut ship, &mut target, &mut fighter);
+    assert!(
+        outcome.killed_at.is_some_and(|time| time < 30.0),
+        "{:?}",
+        outcome
+    );
+    assert!(fighter
+        .events
+        .matching(|event| *event == Event::AbilityUsed(Ability::Boost))
+        .next()
+        .is_some());
+}
+
+#[test]
+fn tuner_finds_better_gains_and_round_trips_config() {
+    use spacepoort::filter::VecKalman;
+    use spacepoort::movement::Arrive;
+    use spacepoort::tuning::{arrival_cost, gunnery_cost, tracking_cost, Config, Evolution, Tuner};
+
+    let tuner = Tuner::of::<Arrive>();
+    let best = tuner.grid(6, arrival_cost).unwrap();
+    let baseline = arrival_cost(&Config::default());
+    assert!(best.cost <= baseline, "{:?} vs {}", best, baseline);
+
+    let tuner = Tuner::of::<VecKalman>();
+    let search = Evolution {
+        population: 6,
+        generations: 4,
+        ..Evolution::default()
+    };
+    let evolved = tuner.evolve(&search, tracking_cost).unwrap();
+    let worst = tracking_cost(&tuner.config(&[20_000.0]));
+    assert!(evolved.cost < worst, "{:?} vs {}", evolved, worst);
+
+    let text = format!("# tuned\n{}", evolved.config.encode());
+    let loaded = Config::parse(&text).unwrap();
+    assert_eq!(loaded, evolved.config);
+    let mut filter = VecKalman::default();
+    spacepoort::tuning::Tunable::load(&mut filter, &loaded);
+    assert_eq!(
+        Some(filter.process_noise),
+        evolved.config.get("process_noise")
+    );
+    assert!(Config::parse("gain 3").is_none());
+    assert!(gunnery_cost(&Config::default()) < 60.0);
+}
+
+#[test]
+fn fighter_hud_draws_only_when_its_layer_is_enabled() {
+    use spacepoort::context::TickContext;
+    use spacepoort::draw::{DebugDraw, Layer};
+    use spacepoort::scenario::Rig;
+    use std::cell::RefCell;
+
+    #[derive(Default)]
+    struct Recorder(RefCell<Vec<String>>);
+
+    impl DebugDraw for Recorder {
+        fn line(&self, _from: Vec2, _to: Vec2, _color: u32) {}
+        fn square(&self, _center: Vec2, _size: f64, _color: u32) {}
+        fn diamond(&self, _center: Vec2, _size: f64, _color: u32) {}
+        fn polygon(&self, _center: Vec2, _size: f64, _sides: i32, _angle: f64, _color: u32) {}
+        fn text(&self, _position: Vec2, _color: u32, text: &str) {
+            self.0.borrow_mut().push(text.to_string());
+        }
+    }
+
+    let ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let target = Body {
+        position: vec2(2_000.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    let mut rig = Rig::new(ship);
+    let mut fighter = Fighter::new();
+    let mut tick = |fighter: &mut Fighter, now: f64| {
+        rig.prepare(&ship, &target);
+        let context = TickContext::with_draw(&ship, now, Recorder::default());
+        fighter.drive(&context, &mut rig);
+        context.draw.0.into_inner()
+    };
+    assert!(tick(&mut fighter, 0.0).is_empty());
+    fighter.layers.set(Layer::Hud, true);
+    let text = tick(&mut fighter, TICK_LENGTH);
+    assert!(text[0].contains("SIGMA"), "{:?}", text);
+    assert!(text[1].contains("Fighter"), "{:?}", text);
+    assert!(text.iter().any(|line| line.contains("seek")), "{:?}", text);
+}
+
+#[test]
+fn waypoint_fighter_seeks_its_own_waypoint() {
+    use spacepoort::context::TickContext;
+    use spacepoort::draw::NoDraw;
+    use spacepoort::scenario::Rig;
+    use spacepoort::ships::Behavior;
+
+    let ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let mut rig = Rig::new(ship);
+    let mut fighter = Fighter::new();
+    fighter.behavior = Behavior::Waypoint;
+    fighter.waypoint = vec2(0.0, -1_000.0);
+    fighter.drive(&TickContext::with_draw(&ship, 0.0, NoDraw), &mut rig);
+    let output = rig.output();
+    assert!(output.linear.y < 0.0 && output.linear.x.abs() < 1e-9);
+}
+
+#[test]
+fn boost_plan_fires_only_for_long_aligned_transits() {
+    use oort_api::prelude::{Ability, Class};
+    use spacepoort::abilities::{Abilities, Spec};
+    use spacepoort::planning::BoostPlan;
+
+    let plan = BoostPlan::default();
+    assert_eq!(plan.acceleration, Spec::of(Ability::Boost).acceleration);
+    assert!(plan.worthwhile(10_000.0, 0.0, 60.0, true));
+    assert!(!plan.worthwhile(100.0, 0.0, 60.0, true));
+    let boosted = plan.boosted_time(10_000.0, 0.0, 60.0, false).unwrap();
+    let unboosted = spacepoort::planning::transit_time(10_000.0, 0.0, 60.0, false);
+    assert!(boosted < unboosted);
+
+    let ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let mut abilities = Abilities::for_class(Class::Fighter);
+    assert!(!plan.apply(&mut abilities, &ship, vec2(0.0, 10_000.0), true, 0.0));
+    assert!(plan.apply(&mut abilities, &ship, vec2(10_000.0, 0.0), true, 0.0));
+    assert!(abilities.active(Ability::Boost, 1.0));
+    assert!(!plan.apply(&mut abilities, &ship, vec2(10_000.0, 0.0), true, 1.0));
+}
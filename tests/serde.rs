@@ -0,0 +1,48 @@
+#![cfg(feature = "serde")]
+
+use spacepoort::control::Pid;
+use spacepoort::movement::SteeringLimits;
+
+#[test]
+fn controller_configs_round_trip() {
+    let pid = Pid::new(2.0, 0.5, 0.1)
+        .with_limits(-1.0, 1.0)
+        .with_integral_limit(10.0);
+    let json = serde_json::to_string(&pid).unwrap();
+    let decoded: Pid = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+
+    let limits: SteeringLimits = serde_json::from_str(
+        r#"{"stop_radius":5.0,"stop_speed":2.0,"stop_angle":0.01,"stop_rate":0.02}"#,
+    )
+    .unwrap();
+    assert_eq!(limits.stop_radius, 5.0);
+    assert_eq!(limits.slow_radius, 0.0);
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn tracks_round_trip_with_radio_class_codes() {
+    use oort_api::prelude::*;
+    use spacepoort::perception::Track;
+
+    let track = Track {
+        id: 7,
+        class: Class::Frigate,
+        position: vec2(100.0, -50.0),
+        velocity: vec2(3.0, 4.0),
+        updated: 12.5,
+        age: 0.25,
+        hits: 3,
+        shared_by: Some(2),
+        filter: Default::default(),
+        suspect: None,
+    };
+    let json = serde_json::to_string(&track).unwrap();
+    assert!(json.contains(r#""class":1"#));
+    assert!(json.contains(r#""position":[100.0,-50.0]"#));
+    let decoded: Track = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded.class, Class::Frigate);
+    assert_eq!(decoded.position.distance(track.position), 0.0);
+    assert_eq!(decoded.shared_by, Some(2));
+}
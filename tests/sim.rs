@@ -0,0 +1,720 @@
+use spacepoort::control::Attitude;
+use spacepoort::math::*;
+use spacepoort::movement::*;
+use spacepoort::sim::MockMotor;
+
+#[test]
+fn seek_closes_on_target() {
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let target = vec2(1_000.0, 500.0);
+    let start = ship.position.distance(target);
+    ship.run(&Seek { target }, 120);
+    assert!(ship.position.distance(target) < start);
+    assert!(ship.velocity.dot(target - ship.position) > 0.0);
+}
+
+#[test]
+fn align_settles_on_heading() {
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let align = Align {
+        target: 2.0,
+        feedforward: 0.0,
+        attitude: Attitude::default(),
+    };
+    ship.run(&align, 300);
+    assert!(angle_diff(ship.heading, 2.0).abs() < 0.01);
+    assert!(ship.angular_velocity.abs() < 0.01);
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn proportional_navigation_intercepts_crossing_target() {
+    use spacepoort::missile::ProportionalNavigation;
+    use spacepoort::sim::{chase, Body};
+
+    let mut missile = MockMotor::missile(vec2(0.0, 0.0), vec2(0.0, 0.0));
+    let mut target = Body {
+        position: vec2(3_000.0, 0.0),
+        velocity: vec2(0.0, 150.0),
+    };
+    chase(
+        &mut missile,
+        &mut target,
+        |target| ProportionalNavigation { target, gain: 4.0 },
+        1_200,
+    );
+    assert!(missile.position.distance(target.position) < 50.0);
+}
+
+#[test]
+fn coordinated_turn_keeps_speed_and_closes_circle() {
+    use spacepoort::sim::{CoordinatedTurn, Script};
+    let rate = 0.5;
+    let mut target = CoordinatedTurn {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(100.0, 0.0),
+        turn_rate: rate,
+    };
+    let period = std::f64::consts::TAU / rate;
+    let ticks = (period / TICK_LENGTH).round() as usize;
+    for _ in 0..ticks {
+        target.advance(TICK_LENGTH);
+    }
+    assert!((target.velocity.length() - 100.0).abs() < 1e-6);
+    assert!(target.position.length() < 5.0);
+}
+
+#[test]
+fn constant_acceleration_matches_closed_form() {
+    use spacepoort::sim::{ConstantAcceleration, Script};
+    let mut target = ConstantAcceleration {
+        position: vec2(10.0, 0.0),
+        velocity: vec2(0.0, 50.0),
+        acceleration: vec2(3.0, -1.0),
+    };
+    for _ in 0..600 {
+        target.advance(TICK_LENGTH);
+    }
+    let t = 600.0 * TICK_LENGTH;
+    let expected = vec2(10.0, 0.0) + vec2(0.0, 50.0) * t + vec2(3.0, -1.0) * (0.5 * t * t);
+    assert!(target.position.distance(expected) < 1e-6);
+}
+
+#[test]
+fn random_jink_is_deterministic_and_bounded() {
+    use spacepoort::sim::{RandomJink, Script};
+    let mut a = RandomJink::new(vec2(0.0, 0.0), vec2(200.0, 0.0), 30.0, 1.0, 7);
+    let mut b = RandomJink::new(vec2(0.0, 0.0), vec2(200.0, 0.0), 30.0, 1.0, 7);
+    for _ in 0..600 {
+        a.advance(TICK_LENGTH);
+        b.advance(TICK_LENGTH);
+        assert!(a.lateral().abs() <= 30.0);
+    }
+    assert_eq!(a.position.x, b.position.x);
+    assert_eq!(a.position.y, b.position.y);
+    assert!((a.velocity.length() - 200.0).abs() < 1.0);
+}
+
+#[test]
+fn arrive_reports_done_once_settled() {
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let arrive = Arrive::new(vec2(500.0, 0.0));
+    assert!(matches!(arrive.steer(&ship), Steering::Output(_)));
+    ship.run(&arrive, 60 * 20);
+    assert!(arrive.steer(&ship).is_done());
+}
+
+#[test]
+fn steering_limits_come_from_the_motor() {
+    let mut ship = MockMotor::fighter(vec2(40.0, 0.0));
+    let arrive = Arrive::new(vec2(0.0, 0.0));
+    assert!(!arrive.steer(&ship).is_done());
+    ship.limits.stop_radius = 50.0;
+    assert!(arrive.steer(&ship).is_done());
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn oort_turn_rate_comes_from_its_limits() {
+    use spacepoort::Oort;
+
+    let mut oort = Oort::default();
+    assert_eq!(oort.max_angular_velocity(), core::f64::consts::TAU);
+    oort.limits.max_angular_velocity = 2.0;
+    assert_eq!(oort.max_angular_velocity(), 2.0);
+}
+
+#[test]
+fn seek_onto_own_position_is_blocked() {
+    let ship = MockMotor::fighter(vec2(5.0, 5.0));
+    assert!(matches!(
+        Seek::new(vec2(5.0, 5.0)).steer(&ship),
+        Steering::Blocked(Reason::Degenerate)
+    ));
+}
+
+#[test]
+fn dynamic_and_generic_blends_agree() {
+    let ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let target = vec2(300.0, 400.0);
+    let generic = MovementBlend::new()
+        .with(Arrive::new(target), 0.5)
+        .with(Arrive::new(-target), 0.25);
+    let dynamic = MovementBlend::<Box<dyn DynMove>>::new()
+        .boxed(Arrive::new(target), 0.5)
+        .boxed(Arrive::new(-target), 0.25)
+        .boxed(Align::new(1.0), 1.0);
+    let a = generic.execute(&ship);
+    let b = dynamic.execute(&ship);
+    assert!(a.linear.distance(b.linear) < 1e-9);
+    assert!(b.angular != 0.0);
+}
+
+#[test]
+fn blend_components_are_named_and_weighted() {
+    let ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let blend = MovementBlend::<Box<dyn DynMove>>::new()
+        .boxed(Seek::new(vec2(100.0, 0.0)), 0.5)
+        .boxed(Align::new(1.0), 1.0);
+    let components = blend.components(&ship);
+    let names: Vec<&str> = components.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, ["Seek", "Align"]);
+    let seek = Seek::new(vec2(100.0, 0.0)).execute(&ship);
+    assert!(components[0].1.linear.distance(seek.linear * 0.5) < 1e-9);
+}
+
+#[test]
+fn flip_and_burn_arrives_stopped() {
+    use spacepoort::planning::{FlipAndBurn, TransitPhase};
+
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    ship.heading = 2.0;
+    let transit = FlipAndBurn::new(vec2(5_000.0, 1_000.0));
+    assert_eq!(transit.phase(&ship), TransitPhase::Burn);
+    let states = ship.run(&transit, 60 * 60);
+    assert!(states
+        .iter()
+        .any(|state| transit.phase(state) == TransitPhase::Brake));
+    assert!(
+        ship.position.distance(transit.goal) < 5.0,
+        "{:?}",
+        ship.position
+    );
+    assert!(ship.velocity.length() < 2.0);
+}
+
+#[test]
+fn enum_dispatch_matches_boxed_blend() {
+    use spacepoort::sim::Body;
+    let ship = MockMotor::fighter(vec2(10.0, -20.0));
+    let target = vec2(300.0, 400.0);
+    let boxed = MovementBlend::<Box<dyn DynMove>>::new()
+        .boxed(Seek::new(target), 0.5)
+        .boxed(KeepDistance::new(-target, 200.0), 0.25)
+        .boxed(Align::new(1.0), 1.0);
+    let static_dispatch = MovementBlend::<Strategy<Body>>::new()
+        .strategy(Seek::new(target), 0.5)
+        .strategy(KeepDistance::new(-target, 200.0), 0.25)
+        .strategy(Align::new(1.0), 1.0);
+    let a = boxed.execute(&ship);
+    let b = static_dispatch.execute(&ship);
+    assert!(a.linear.distance(b.linear) < 1e-9);
+    assert_eq!(a.angular, b.angular);
+    assert_eq!(static_dispatch.moves[2].0.name(), "Align");
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn warm_started_launch_solution_matches_cold_solve() {
+    use spacepoort::missile::{launch_solution, launch_solution_near, Performance};
+    use spacepoort::sim::Body;
+
+    let launcher = Body {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    let target = Body {
+        position: vec2(6_000.0, 2_000.0),
+        velocity: vec2(-50.0, 120.0),
+    };
+    let missile = Performance {
+        acceleration: 300.0,
+        delta_v: 3_000.0,
+        lifetime: 20.0,
+    };
+    let cold = launch_solution(&launcher, &target, &missile).unwrap();
+    let warm =
+        launch_solution_near(&launcher, &target, &missile, cold.intercept_time + 0.1).unwrap();
+    assert!((warm.intercept_time - cold.intercept_time).abs() < 1e-6);
+    let fallback = launch_solution_near(&launcher, &target, &missile, 100.0).unwrap();
+    assert!((fallback.intercept_time - cold.intercept_time).abs() < 1e-9);
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn launch_cache_warm_starts_and_evicts_pruned_tracks() {
+    use spacepoort::contact::{Class, ScanResult};
+    use spacepoort::missile::{launch_solution, LaunchCache, Performance};
+    use spacepoort::perception::Tracker;
+    use spacepoort::sim::Body;
+
+    let launcher = Body {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    let mut target = Body {
+        position: vec2(6_000.0, 2_000.0),
+        velocity: vec2(-50.0, 120.0),
+    };
+    let missile = Performance {
+        acceleration: 300.0,
+        delta_v: 3_000.0,
+        lifetime: 20.0,
+    };
+    let mut tracker = Tracker::new();
+    let contact = ScanResult {
+        class: Class::Cruiser,
+        position: target.position,
+        velocity: target.velocity,
+        rssi: 0.0,
+        snr: 0.0,
+    };
+    let id = tracker.update(&contact, 0.0).unwrap();
+    let mut cache = LaunchCache::new();
+    cache.solve(id, &launcher, &target, &missile).unwrap();
+    target.step();
+    let warm = cache.solve(id, &launcher, &target, &missile).unwrap();
+    let cold = launch_solution(&launcher, &target, &missile).unwrap();
+    assert!((warm.intercept_time - cold.intercept_time).abs() < 1e-6);
+    assert_eq!(cache.len(), 1);
+
+    assert!(tracker.prune(1.0).is_empty());
+    for &lost in tracker.prune(60.0).iter() {
+        cache.forget(lost);
+    }
+    assert!(tracker.tracks().is_empty());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn array_blend_matches_vector_blend() {
+    use spacepoort::sim::Body;
+    let ship = MockMotor::fighter(vec2(10.0, -20.0));
+    let target = vec2(300.0, 400.0);
+    let vector = MovementBlend::<Strategy<Body>>::new()
+        .strategy(Seek::new(target), 0.5)
+        .strategy(Align::new(1.0), 1.0);
+    let array = ArrayBlend::<Strategy<Body>, 2>::new([
+        (Seek::new(target).into(), 0.5),
+        (Align::new(1.0).into(), 1.0),
+    ]);
+    let a = vector.execute(&ship);
+    let b = array.execute(&ship);
+    assert!(a.linear.distance(b.linear) < 1e-12);
+    assert_eq!(a.angular, b.angular);
+    let names = array.components(&ship).map(|(name, _)| name);
+    assert_eq!(names, ["Seek", "Align"]);
+}
+
+#[test]
+fn tick_context_snapshots_actor_for_strategies() {
+    use spacepoort::context::TickContext;
+    let mut ship = MockMotor::fighter(vec2(100.0, 0.0));
+    ship.heading = 1.0;
+    ship.velocity = vec2(5.0, 5.0);
+    let mut context = TickContext::capture(&ship, 2.0).with_ship(4);
+    assert_eq!((context.tick, context.ship), (120, 4));
+    assert!(context.forward.distance(vec2(1.0_f64.cos(), 1.0_f64.sin())) < 1e-12);
+    let seek = Seek::new(vec2(0.0, 300.0));
+    assert!(
+        seek.execute(&context)
+            .linear
+            .distance(seek.execute(&ship).linear)
+            < 1e-12
+    );
+    context.blackboard.set("threat", vec2(1.0, 2.0));
+    context.blackboard.set("evading", true);
+    assert_eq!(context.blackboard.vector("threat").map(|v| v.y), Some(2.0));
+    assert!(context.blackboard.flag("evading"));
+    assert_eq!(context.blackboard.number("threat"), None);
+}
+
+#[test]
+fn typed_combinators_match_dynamic_blends() {
+    let ship = MockMotor::fighter(vec2(10.0, -20.0));
+    let target = vec2(300.0, 400.0);
+    let dynamic = MovementBlend::<Box<dyn DynMove>>::new()
+        .boxed(Seek::new(target), 0.5)
+        .boxed(KeepDistance::new(-target, 200.0), 0.25)
+        .boxed(Align::new(1.0), 1.0);
+    let typed = Blend::weighted(
+        (
+            Seek::new(target),
+            KeepDistance::new(-target, 200.0),
+            Align::new(1.0),
+        ),
+        [0.5, 0.25, 1.0],
+    );
+    let a = dynamic.execute(&ship);
+    let b = typed.execute(&ship);
+    assert!(a.linear.distance(b.linear) < 1e-12);
+    assert_eq!(a.angular, b.angular);
+
+    let settled = MockMotor::fighter(vec2(500.0, 0.0));
+    let priority = Priority::new((Arrive::new(vec2(500.0, 0.0)), Seek::new(target)));
+    let fallback = priority.execute(&settled).linear;
+    assert!(fallback.distance(Seek::new(target).execute(&settled).linear) < 1e-12);
+    let urgent = priority.execute(&ship).linear;
+    assert!(urgent.distance(Arrive::new(vec2(500.0, 0.0)).execute(&ship).linear) < 1e-12);
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn retarget_order_is_validated_and_overrides_old_seeker_lock() {
+    use oort_api::prelude::{Class, ScanResult};
+    use spacepoort::missile::{
+        retarget_solution, Datalink, Designation, Guidance, Retarget, TargetState,
+    };
+    use spacepoort::radio::encode;
+    use spacepoort::sim::Body;
+
+    let missile = Body {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(800.0, 0.0),
+    };
+    let target = TargetState {
+        position: vec2(4_000.0, 3_000.0),
+        velocity: vec2(0.0, -50.0),
+    };
+    assert!(retarget_solution(&missile, &target, 300.0, 2_000.0, 20.0).is_some());
+    assert!(retarget_solution(&missile, &target, 300.0, 5.0, 5.0).is_none());
+
+    let mut datalink = Datalink::new(2);
+    let old = ScanResult {
+        class: Class::Fighter,
+        position: vec2(9_000.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+        rssi: 0.0,
+        snr: 0.0,
+    };
+    datalink.select(Some(&old), 0.0);
+    assert_eq!(datalink.mode, Guidance::Autonomous);
+    let order = Retarget {
+        missile: 7,
+        track: 3,
+        target,
+    };
+    datalink.missile = 8;
+    datalink.accept(encode(&order, 1), 1.0);
+    assert!(datalink.pending().is_none());
+    datalink.missile = 7;
+    datalink.accept(encode(&order, 1), 1.0);
+    let order = datalink.pending().unwrap();
+    datalink.retarget(&order, 1.0);
+    assert_eq!(datalink.track(), Some(3));
+    let selected = datalink.select(Some(&old), 2.0).unwrap();
+    assert!(selected.position.distance(vec2(4_000.0, 2_950.0)) < 0.01);
+    assert_eq!(datalink.mode, Guidance::Command);
+
+    let designation = |track, position| Designation {
+        track,
+        target: TargetState {
+            position,
+            velocity: vec2(0.0, 0.0),
+        },
+    };
+    datalink.accept(encode(&designation(1, vec2(9_000.0, 0.0)), 1), 2.0);
+    let selected = datalink.select(None, 2.0).unwrap();
+    assert!(selected.position.distance(vec2(4_000.0, 2_950.0)) < 0.01);
+    datalink.accept(encode(&designation(3, vec2(4_100.0, 2_900.0)), 1), 3.0);
+    let selected = datalink.select(None, 3.0).unwrap();
+    assert!(selected.position.distance(vec2(4_100.0, 2_900.0)) < 0.01);
+    datalink.accept(encode(&order, 1), 3.0);
+    assert!(datalink.pending().is_none());
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn autonomous_missile_falls_back_to_designations_when_the_seeker_drops() {
+    use oort_api::prelude::{Class, ScanResult};
+    use spacepoort::missile::{Datalink, Designation, Guidance, Retarget, TargetState};
+    use spacepoort::radio::encode;
+
+    let mut datalink = Datalink::new(2);
+    datalink.missile = 7;
+    let target = |x: f64| TargetState {
+        position: vec2(x, 0.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    let order = Retarget {
+        missile: 7,
+        track: 3,
+        target: target(4_000.0),
+    };
+    datalink.retarget(&order, 0.0);
+    let lock = ScanResult {
+        class: Class::Fighter,
+        position: vec2(4_050.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+        rssi: 0.0,
+        snr: 0.0,
+    };
+    datalink.select(Some(&lock), 1.0);
+    assert_eq!(datalink.mode, Guidance::Autonomous);
+    let designation = |track, x| Designation {
+        track,
+        target: target(x),
+    };
+    datalink.accept(encode(&designation(3, 4_200.0), 1), 2.0);
+    datalink.accept(encode(&designation(5, 9_000.0), 1), 2.0);
+    let selected = datalink.select(None, 2.0).unwrap();
+    assert!(selected.position.distance(vec2(4_200.0, 0.0)) < 0.01);
+    assert_eq!(datalink.mode, Guidance::Autonomous);
+    let selected = datalink.select(Some(&lock), 2.5).unwrap();
+    assert_eq!(selected.position.x, 4_050.0);
+}
+
+#[test]
+fn arrive_slows_inside_the_slow_radius() {
+    let mut ship = MockMotor::fighter(vec2(40.0, 0.0));
+    ship.velocity = vec2(-60.0, 0.0);
+    let arrive = Arrive::new(vec2(0.0, 0.0)).gain(10.0);
+    assert!(arrive.execute(&ship).linear.x < 0.0);
+    ship.limits.slow_radius = 100.0;
+    assert!(arrive.execute(&ship).linear.x > 0.0);
+}
+
+#[test]
+fn follow_path_reaches_the_final_waypoint() {
+    use spacepoort::planning::FollowPath;
+
+    let path = [vec2(0.0, 0.0), vec2(1_000.0, 0.0), vec2(2_000.0, 0.0)];
+    let follow = FollowPath {
+        path: &path,
+        radius: 200.0,
+    };
+    assert_eq!(follow.current(vec2(1_250.0, 0.0)), Some(2));
+    assert_eq!(follow.current(vec2(500.0, 100.0)), Some(1));
+    let mut ship = MockMotor::fighter(path[0]);
+    let closest = ship
+        .run(&follow, 60 * 30)
+        .iter()
+        .map(|state| state.position.distance(path[2]))
+        .fold(f64::INFINITY, f64::min);
+    assert!(closest < follow.radius, "closest approach {closest}");
+}
+
+#[test]
+fn astar_routes_around_a_blocked_wall() {
+    use spacepoort::planning::{segment_clear, AStar, Grid, Obstacle};
+
+    let wall: Vec<Obstacle> = (-4..=4)
+        .map(|i| Obstacle {
+            position: vec2(0.0, i as f64 * 100.0),
+            radius: 100.0,
+        })
+        .collect();
+    let grid = Grid::build(vec2(0.0, 0.0), 1_500.0, 50.0, &wall, 50.0);
+    let (start, goal) = (vec2(-1_000.0, 0.0), vec2(1_000.0, 0.0));
+    let path = AStar::default().plan(&grid, start, goal).unwrap();
+    assert_eq!(path.first().map(|p| (p.x, p.y)), Some((start.x, start.y)));
+    assert_eq!(path.last().map(|p| (p.x, p.y)), Some((goal.x, goal.y)));
+    assert!(path
+        .windows(2)
+        .all(|leg| segment_clear(leg[0], leg[1], &wall, 0.0)));
+    assert!(path.iter().any(|point| point.y.abs() > 500.0));
+    let length: f64 = path.windows(2).map(|leg| leg[0].distance(leg[1])).sum();
+    assert!(length < 2.0 * start.distance(vec2(0.0, 600.0)) * 1.2);
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn orca_agents_both_deflect_on_a_head_on_pass() {
+    use spacepoort::avoidance::{Neighbor, Orca};
+
+    let orca = Orca::default();
+    let neighbor = |position, velocity| Neighbor {
+        position,
+        velocity,
+        radius: orca.radius,
+        responsibility: 0.5,
+    };
+    let mut agents = [
+        (vec2(-500.0, 0.0), vec2(100.0, 0.0)),
+        (vec2(500.0, 5.0), vec2(-100.0, 0.0)),
+    ];
+    let preferred = [vec2(100.0, 0.0), vec2(-100.0, 0.0)];
+    let mut closest = f64::INFINITY;
+    let mut deflection = [0.0f64; 2];
+    for _ in 0..60 * 10 {
+        let velocities = [0, 1].map(|i| {
+            let (position, velocity) = agents[i];
+            let (other, other_velocity) = agents[1 - i];
+            orca.velocity(
+                position,
+                velocity,
+                preferred[i],
+                &[neighbor(other, other_velocity)],
+            )
+        });
+        for (i, agent) in agents.iter_mut().enumerate() {
+            agent.1 = velocities[i];
+            agent.0 += velocities[i] * TICK_LENGTH;
+            deflection[i] = deflection[i].max(agent.1.y.abs());
+        }
+        closest = closest.min(agents[0].0.distance(agents[1].0));
+    }
+    assert!(closest >= 2.0 * orca.radius - 1.0, "closest {closest}");
+    assert!(deflection.iter().all(|d| *d > 5.0), "{deflection:?}");
+    assert!(agents[0].0.x > 400.0 && agents[1].0.x < -400.0);
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn orca_falls_back_to_the_least_violating_velocity_when_boxed_in() {
+    use spacepoort::avoidance::{Neighbor, Orca};
+
+    let orca = Orca::default();
+    let neighbors = [
+        vec2(30.0, 0.0),
+        vec2(-30.0, 0.0),
+        vec2(0.0, 30.0),
+        vec2(0.0, -30.0),
+    ]
+    .map(|position| Neighbor {
+        position,
+        velocity: vec2(0.0, 0.0),
+        radius: orca.radius,
+        responsibility: 0.5,
+    });
+    let chosen = orca.velocity(vec2(0.0, 0.0), vec2(0.0, 0.0), vec2(500.0, 0.0), &neighbors);
+    assert!(chosen.x.is_finite() && chosen.y.is_finite());
+    assert!(chosen.length() < 1.0, "{chosen:?}");
+}
+
+#[cfg(feature = "oort")]
+#[test]
+fn orca_responsibility_scales_the_avoidance_share() {
+    use spacepoort::avoidance::{Neighbor, Orca};
+
+    let orca = Orca::default();
+    let change = |responsibility| {
+        let neighbor = Neighbor {
+            position: vec2(300.0, 0.0),
+            velocity: vec2(0.0, 0.0),
+            radius: orca.radius,
+            responsibility,
+        };
+        let velocity = vec2(200.0, 0.0);
+        (orca.velocity(vec2(0.0, 0.0), velocity, velocity, &[neighbor]) - velocity).length()
+    };
+    assert_eq!(change(0.0), 0.0);
+    assert!((change(1.0) - 2.0 * change(0.5)).abs() < 1e-6);
+}
+
+#[test]
+fn visibility_graph_takes_the_short_way_around_one_obstacle() {
+    use spacepoort::planning::{segment_clear, Obstacle, VisibilityGraph};
+
+    let planner = VisibilityGraph::default();
+    let rock = [Obstacle {
+        position: vec2(1_000.0, 50.0),
+        radius: 300.0,
+    }];
+    let (start, goal) = (vec2(0.0, 0.0), vec2(2_000.0, 0.0));
+    let path = planner.plan(start, goal, &rock).unwrap();
+    assert!(path.len() > 2);
+    assert!(path.windows(2).all(|leg| segment_clear(
+        leg[0],
+        leg[1],
+        &rock,
+        planner.clearance - 1e-6
+    )));
+    assert!(path[1..path.len() - 1].iter().all(|corner| corner.y < 0.0));
+    let length: f64 = path.windows(2).map(|leg| leg[0].distance(leg[1])).sum();
+    let reach = rock[0].radius + planner.clearance;
+    let taut = 2.0 * vec2(1_000.0, reach - 50.0).length();
+    assert!(length < taut * 1.1, "length {length}");
+}
+
+#[test]
+fn opponent_patterns_follow_relative_geometry() {
+    use spacepoort::context::Blackboard;
+    use spacepoort::opponent::{Counter, Opponents, Pattern};
+    use spacepoort::sim::{Body, CoordinatedTurn, Script};
+
+    let own = Body {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    let mut opponents = Opponents::new();
+    let mut charger = Body {
+        position: vec2(8_000.0, 0.0),
+        velocity: vec2(-200.0, 10.0),
+    };
+    let mut orbiter = CoordinatedTurn {
+        position: vec2(0.0, -2_000.0),
+        velocity: vec2(200.0, 0.0),
+        turn_rate: 0.1,
+    };
+    let mut chaser = Body {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(150.0, 0.0),
+    };
+    let mut kiter = Body {
+        position: vec2(3_000.0, 0.0),
+        velocity: vec2(150.0, 20.0),
+    };
+    for tick in 0..120 {
+        let now = tick as f64 * TICK_LENGTH;
+        opponents.observe(1, &own, &charger, now);
+        opponents.observe(2, &own, &orbiter, now);
+        opponents.observe(3, &chaser, &kiter, now);
+        if tick < 10 {
+            assert_eq!(opponents.pattern(1), Pattern::Unknown);
+        }
+        charger.advance(TICK_LENGTH);
+        orbiter.advance(TICK_LENGTH);
+        chaser.advance(TICK_LENGTH);
+        kiter.advance(TICK_LENGTH);
+    }
+    assert_eq!(opponents.pattern(1), Pattern::Charger);
+    assert_eq!(opponents.pattern(2), Pattern::Orbiter);
+    assert_eq!(opponents.pattern(3), Pattern::Kiter);
+    assert_eq!(Pattern::Kiter.counter(), Some(Counter::Pursue));
+
+    for launch in 0..3 {
+        opponents.launch(2, 2.0 + launch as f64);
+    }
+    assert_eq!(opponents.pattern(2), Pattern::Spammer);
+    let mut blackboard = Blackboard::new();
+    opponents.pattern(2).publish(&mut blackboard);
+    assert_eq!(Pattern::read(&blackboard), Pattern::Spammer);
+
+    opponents.retain(|id| id != 1);
+    assert_eq!(opponents.pattern(1), Pattern::Unknown);
+}
+
+#[test]
+fn rollout_prefers_breaking_out_of_a_head_on_pass() {
+    use spacepoort::rollout::{Maneuver, Rollout};
+    use spacepoort::sim::Body;
+
+    let ship = MockMotor {
+        velocity: vec2(100.0, 0.0),
+        ..MockMotor::fighter(vec2(0.0, 0.0))
+    };
+    let enemy = Body {
+        position: vec2(1_500.0, 0.0),
+        velocity: vec2(-100.0, 0.0),
+    };
+    let rollout = Rollout::default();
+    let scores = rollout.evaluate(&ship, &enemy, 3);
+    assert_eq!(scores.len(), Maneuver::ALL.len());
+    assert!(scores.iter().all(|score| score.rollouts >= 1));
+    let value = |maneuver| {
+        scores
+            .iter()
+            .find(|score| score.maneuver == maneuver)
+            .unwrap()
+            .value(rollout.exposure_weight)
+    };
+    let best = rollout.best(&ship, &enemy, 3).unwrap();
+    assert_ne!(best.maneuver, Maneuver::Hold);
+    assert!(value(Maneuver::BreakLeft) > value(Maneuver::Hold));
+
+    let budgeted = Rollout {
+        budget: 1,
+        ..Rollout::default()
+    };
+    let scores = budgeted.evaluate(&ship, &enemy, 3);
+    assert!(scores.iter().all(|score| score.rollouts == 1));
+
+    let steps = 30 * Maneuver::ALL.len();
+    let budgeted = Rollout {
+        budget: 3 * steps + steps / 2,
+        ..Rollout::default()
+    };
+    let scores = budgeted.evaluate(&ship, &enemy, 3);
+    assert!(scores.iter().all(|score| score.rollouts == 3));
+}
@@ -0,0 +1,448 @@
+#![cfg(feature = "oort")]
+
+use oort_api::prelude::{Class, ScanResult};
+use spacepoort::controllers::Squadron;
+use spacepoort::math::*;
+use spacepoort::radio::{Header, Packet, VERSION};
+use spacepoort::sim::MockMotor;
+
+fn deliver(sender: u16, packets: Vec<Packet>) -> Vec<(Header, Packet)> {
+    packets
+        .into_iter()
+        .map(|packet| {
+            let header = Header {
+                version: VERSION,
+                kind: 0,
+                sender,
+                aux: 0,
+            };
+            (header, packet)
+        })
+        .collect()
+}
+
+#[test]
+fn squadron_shares_tracks_and_flies_formation() {
+    let leader_ship = MockMotor::fighter(vec2(0.0, 0.0));
+    let wingman_ship = MockMotor::fighter(vec2(-200.0, 100.0));
+    let mut leader = Squadron::new(1, 2);
+    let mut wingman = Squadron::new(2, 2);
+    let contact = ScanResult {
+        class: Class::Fighter,
+        position: vec2(2_000.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+        rssi: 0.0,
+        snr: 0.0,
+    };
+    let mut to_wingman = Vec::new();
+    let mut to_leader = Vec::new();
+    for tick in 0..10 {
+        let now = tick as f64 * TICK_LENGTH;
+        leader.tracker.update(&contact, now);
+        leader.observe(&to_leader, now);
+        wingman.observe(&to_wingman, now);
+        to_wingman = deliver(1, leader.plan(&leader_ship, 100.0, now));
+        to_leader = deliver(2, wingman.plan(&wingman_ship, 100.0, now));
+    }
+    assert!(leader.election.is_leader());
+    assert_eq!(wingman.election.leader(), 1);
+    assert!(wingman.formation.leader().is_some());
+    assert!(!wingman.tracker.tracks().is_empty());
+    assert!(leader.target().is_some());
+    assert!(wingman.target().is_some());
+    assert!(wingman.steer(&wingman_ship, 10.0 * TICK_LENGTH).is_some());
+}
+
+#[test]
+fn two_ships_triangulate_a_contact_over_radio() {
+    use spacepoort::fusion::CrossFix;
+    use spacepoort::perception::Tracker;
+    use spacepoort::radio::BearingReport;
+
+    let target = |t: f64| vec2(8_000.0, 4_000.0) + vec2(50.0, -20.0) * t;
+    let (a, b) = (vec2(0.0, 0.0), vec2(3_000.0, -2_000.0));
+    let mut left = CrossFix::new();
+    let mut right = CrossFix::new();
+    let mut tracker = Tracker::new();
+    for tick in 0..30 {
+        let now = tick as f64 * TICK_LENGTH;
+        left.observe(Class::Cruiser, a, (target(now) - a).angle(), now);
+        let packet = right.observe(Class::Cruiser, b, (target(now) - b).angle(), now);
+        let message = packet.encode(2);
+        let received = Packet::decode(message).unwrap();
+        assert_eq!(left.receive(&mut tracker, &[received], 1, now), 1);
+    }
+    let track = &tracker.tracks()[0];
+    assert_eq!(tracker.tracks().len(), 1);
+    assert!(track.position.distance(target(29.0 * TICK_LENGTH)) < 10.0);
+    assert!(track.velocity.distance(vec2(50.0, -20.0)) < 20.0);
+    let stale = Packet::Bearing(BearingReport {
+        class: Class::Cruiser,
+        origin: b,
+        bearing: 0.0,
+        time: 0.0,
+    });
+    assert_eq!(
+        left.receive(&mut tracker, &deliver(2, vec![stale]), 1, 5.0),
+        0
+    );
+}
+
+#[test]
+fn cross_fixes_pair_bearings_with_the_matching_track() {
+    use spacepoort::fusion::CrossFix;
+    use spacepoort::perception::Tracker;
+
+    let targets = [vec2(8_000.0, 4_000.0), vec2(7_000.0, -3_000.0)];
+    let velocity = vec2(30.0, 10.0);
+    let at = |target: Vec2, t: f64| target + velocity * t;
+    let (a, b) = (vec2(0.0, 0.0), vec2(3_000.0, -2_000.0));
+    let mut left = CrossFix::new();
+    let mut right = CrossFix::new();
+    let mut tracker = Tracker::new();
+    let seeded: Vec<u32> = targets
+        .iter()
+        .map(|target| {
+            let rough = ScanResult {
+                class: Class::Cruiser,
+                position: *target + vec2(120.0, -80.0),
+                velocity,
+                rssi: 0.0,
+                snr: 0.0,
+            };
+            tracker.update(&rough, 0.0).unwrap()
+        })
+        .collect();
+    for tick in 1..30 {
+        let now = tick as f64 * TICK_LENGTH;
+        let mut inbox = Vec::new();
+        for target in targets.iter().rev() {
+            left.observe(Class::Cruiser, a, (at(*target, now) - a).angle(), now);
+        }
+        for target in &targets {
+            let packet = right.observe(Class::Cruiser, b, (at(*target, now) - b).angle(), now);
+            inbox.extend(deliver(2, vec![packet]));
+        }
+        assert_eq!(left.receive(&mut tracker, &inbox, 1, now), 2);
+    }
+    assert_eq!(tracker.tracks().len(), 2);
+    let end = 29.0 * TICK_LENGTH;
+    for (id, target) in seeded.iter().zip(targets) {
+        let track = tracker.get(*id).unwrap();
+        assert!(track.position.distance(at(target, end)) < 60.0);
+    }
+    let keys: Vec<_> = left.fixes().iter().map(|fix| fix.track).collect();
+    assert_eq!(keys.len(), 2);
+    assert!(seeded.iter().all(|id| keys.contains(&Some(*id))));
+}
+
+#[test]
+fn formation_slots_stay_inside_the_arena() {
+    use spacepoort::formation::{Formation, Shape};
+    use spacepoort::planning::Arena;
+
+    let leader = MockMotor::fighter(vec2(4_900.0, 0.0));
+    let wingman = MockMotor::fighter(vec2(5_000.0, 0.0));
+    let mut formation = Formation::shaped(Shape::Wedge, 1, Class::Fighter);
+    formation.observe(
+        &deliver(1, vec![Formation::publish(&leader, 100.0)]),
+        1,
+        0.0,
+    );
+    let offset = vec2(500.0, 0.0);
+    let free = formation.steer(&wingman, offset, 0.0).unwrap();
+    assert!(free.linear.x > 0.0);
+    formation.arena = Some(Arena {
+        half_size: 5_000.0,
+        margin: 0.0,
+    });
+    let clamped = formation.steer(&wingman, offset, 0.0).unwrap();
+    assert!(clamped.linear.length() < 1e-6, "{:?}", clamped.linear);
+}
+
+#[test]
+fn volleys_need_spread_bearings_and_resolve_at_launch() {
+    use spacepoort::fleet::Friendly;
+    use spacepoort::missile::Performance;
+    use spacepoort::sim::Body;
+    use spacepoort::tactics::{VolleyPlan, VolleyTiming};
+
+    let missile = Performance {
+        acceleration: 300.0,
+        delta_v: 3_000.0,
+        lifetime: 20.0,
+    };
+    let friendly = |ship: u16, position: Vec2| {
+        (
+            ship,
+            Friendly {
+                ship,
+                health: 100.0,
+                position,
+                velocity: vec2(0.0, 0.0),
+                heard: 0.0,
+            },
+        )
+    };
+    let target = Body {
+        position: vec2(0.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    let plan = VolleyPlan::default();
+    let stacked = [
+        friendly(1, vec2(6_000.0, 0.0)),
+        friendly(2, vec2(4_000.0, 100.0)),
+    ];
+    assert!(plan.plan(&stacked, &target, &missile, 0.0).is_empty());
+
+    let spread = [
+        friendly(1, vec2(6_000.0, 0.0)),
+        friendly(2, vec2(0.0, 4_000.0)),
+    ];
+    let orders = plan.plan(&spread, &target, &missile, 0.0);
+    assert_eq!(orders.len(), 2);
+    let inbox = deliver(0, orders.into_iter().map(Packet::Volley).collect());
+    let inbox: Vec<(Header, Packet)> = inbox
+        .into_iter()
+        .map(|(_, packet)| Packet::decode(packet.encode(0)).unwrap())
+        .collect();
+    let mut timing = VolleyTiming::default();
+    timing.observe(2, &inbox);
+    let order = *timing.order().unwrap();
+    assert!(order.launch_time > 0.0);
+    let shooter = spread[1].1;
+    assert!(timing
+        .launch(&shooter, &target, &missile, order.launch_time)
+        .is_some());
+
+    timing.observe(2, &inbox);
+    let moved = Body {
+        position: vec2(0.0, -3_000.0),
+        velocity: vec2(0.0, 0.0),
+    };
+    assert!(timing
+        .launch(&shooter, &moved, &missile, order.launch_time)
+        .is_none());
+}
+
+#[test]
+fn reassembler_keeps_interleaved_messages_from_one_sender() {
+    use spacepoort::radio::{Fragmenter, Reassembler};
+
+    let mut fragmenter = Fragmenter::new();
+    assert!(fragmenter.send(&[1.0, 2.0, 3.0, 4.0]));
+    assert!(fragmenter.send(&[5.0, 6.0, 7.0, 8.0]));
+    let fragments: Vec<Packet> = core::iter::from_fn(|| fragmenter.pop()).collect();
+    assert_eq!(fragments.len(), 4);
+    let interleaved = [0, 2, 1, 3].map(|i| fragments[i]);
+    let mut reassembler = Reassembler::new();
+    let mut received = Vec::new();
+    for (tick, packet) in interleaved.into_iter().enumerate() {
+        let inbox = deliver(3, vec![packet]);
+        received.extend(reassembler.accept(&inbox, tick as f64 * TICK_LENGTH));
+    }
+    assert_eq!(
+        received,
+        vec![(3, vec![1.0, 2.0, 3.0, 4.0]), (3, vec![5.0, 6.0, 7.0, 8.0])]
+    );
+}
+
+#[test]
+fn reassembler_accepts_message_ids_after_they_wrap() {
+    use spacepoort::radio::{Fragmenter, Reassembler};
+
+    let mut fragmenter = Fragmenter::new();
+    let mut reassembler = Reassembler::new();
+    let mut received = Vec::new();
+    for tick in 0..65 {
+        assert!(fragmenter.send(&[tick as f64]));
+        let inbox = deliver(3, vec![fragmenter.pop().unwrap()]);
+        received.extend(reassembler.accept(&inbox, tick as f64 * TICK_LENGTH));
+    }
+    assert_eq!(received.len(), 65);
+    assert_eq!(received[64], (3, vec![64.0]));
+}
+
+#[test]
+fn withdrawal_posts_the_ships_nearest_the_threat_as_rearguard() {
+    use spacepoort::fleet::{Friendly, Role};
+    use spacepoort::tactics::Withdrawal;
+
+    let friendly = |ship: u16, x: f64| Friendly {
+        ship,
+        health: 100.0,
+        position: vec2(x, 0.0),
+        velocity: vec2(0.0, 0.0),
+        heard: 0.0,
+    };
+    let members = [
+        friendly(1, 1_000.0),
+        friendly(2, 4_000.0),
+        friendly(3, 2_000.0),
+        friendly(4, 3_000.0),
+    ];
+    let threat = vec2(10_000.0, 0.0);
+    let mut withdrawal = Withdrawal::new(vec2(0.0, 0.0));
+    assert!(!withdrawal.update(4, 0.0));
+    assert!(withdrawal
+        .roles(&members, threat, 0.0)
+        .iter()
+        .all(|(_, role)| *role == Role::Wingman));
+    assert!(withdrawal.update(2, 1.0));
+    let rearguard = |now: f64| -> Vec<u16> {
+        withdrawal
+            .roles(&members, threat, now)
+            .into_iter()
+            .filter(|(_, role)| *role == Role::Rearguard)
+            .map(|(ship, _)| ship)
+            .collect()
+    };
+    assert_eq!(rearguard(1.0), vec![2, 4]);
+    assert_eq!(rearguard(1.0 + withdrawal.swap_period), vec![1, 3]);
+}
+
+#[test]
+fn radio_sends_every_queued_packet_one_per_slot() {
+    use spacepoort::radio::{Fragment, Radio};
+
+    let mut radio = Radio::slotted(2, 1, 3);
+    let queued = 5;
+    for index in 0..queued {
+        radio.queue(Packet::Fragment(Fragment {
+            message: 7,
+            index,
+            count: queued,
+            words: [0.0; 3],
+        }));
+    }
+    let mut sent = Vec::new();
+    for tick in 0..3 * queued as u32 {
+        if let Some(Packet::Fragment(fragment)) = radio.flush(tick) {
+            sent.push((tick, fragment.index));
+        }
+    }
+    assert_eq!(sent, vec![(1, 0), (4, 1), (7, 2), (10, 3), (13, 4)]);
+    assert_eq!(radio.pending(), 0);
+}
+
+#[test]
+fn channel_plan_stays_on_the_band_when_no_flight_channels_remain() {
+    use spacepoort::radio::{ChannelPlan, CHANNELS};
+
+    for first_flight in [CHANNELS - 1, CHANNELS, CHANNELS + 2] {
+        let plan = ChannelPlan {
+            command: 0,
+            first_flight,
+        };
+        assert!((0..8).all(|flight| plan.flight(flight) == CHANNELS - 1));
+    }
+}
+
+#[test]
+fn auction_lower_bidder_yields_until_the_claim_times_out() {
+    use spacepoort::assignment::Auction;
+    use spacepoort::perception::Tracker;
+
+    let mut tracker = Tracker::new();
+    let contact = |x, y| ScanResult {
+        class: Class::Fighter,
+        position: vec2(x, y),
+        velocity: vec2(0.0, 0.0),
+        rssi: 0.0,
+        snr: 0.0,
+    };
+    let near = tracker.update(&contact(1_000.0, 0.0), 0.0).unwrap();
+    let far = tracker.update(&contact(1_000.0, 2_000.0), 0.0).unwrap();
+    let tracks = tracker.tracks();
+    let (first, second) = (
+        MockMotor::fighter(vec2(0.0, 0.0)),
+        MockMotor::fighter(vec2(-500.0, 0.0)),
+    );
+    let mut leader = Auction::new();
+    let mut wingman = Auction::new();
+    assert_eq!(leader.resolve(1, &first, tracks, 0.0), Some(near));
+    assert_eq!(wingman.resolve(2, &second, tracks, 0.0), Some(near));
+    let bid = leader.broadcast(tracks).unwrap();
+    wingman.receive(&deliver(1, vec![bid]), 0.0);
+    assert_eq!(wingman.resolve(2, &second, tracks, 0.0), Some(far));
+    let bid = wingman.broadcast(tracks).unwrap();
+    leader.receive(&deliver(2, vec![bid]), 0.0);
+    assert_eq!(leader.resolve(1, &first, tracks, 0.0), Some(near));
+    let later = wingman.timeout + 0.1;
+    wingman.receive(&[], later);
+    assert!(wingman.claims().is_empty());
+    assert_eq!(wingman.resolve(2, &second, tracks, later), Some(near));
+}
+
+#[test]
+fn election_follows_the_lowest_live_ship_and_its_role_orders() {
+    use spacepoort::fleet::{Election, Role};
+    use spacepoort::radio::Status;
+
+    let status = || {
+        Packet::Status(Status {
+            health: 100,
+            position: vec2(0.0, 0.0),
+            velocity: vec2(0.0, 0.0),
+        })
+    };
+    let mut leader = Election::new(1);
+    let mut wingman = Election::new(2);
+    leader.observe(&deliver(2, vec![status()]), 0.0);
+    leader.observe(&deliver(3, vec![status()]), 0.0);
+    wingman.observe(&deliver(1, vec![status()]), 0.0);
+    wingman.observe(&deliver(3, vec![status()]), 0.0);
+    assert!(leader.is_leader());
+    assert_eq!(wingman.leader(), 1);
+    assert_eq!(leader.members(), vec![1, 2, 3]);
+
+    leader.assign(|rank, _| {
+        if rank == 1 {
+            Role::Striker
+        } else {
+            Role::Screen
+        }
+    });
+    let orders: Vec<Packet> = (0..3).filter_map(|_| leader.broadcast()).collect();
+    wingman.observe(&deliver(3, orders.clone()), 0.5);
+    assert_eq!(wingman.role(), Role::Wingman);
+    wingman.observe(&deliver(1, orders), 0.5);
+    assert_eq!(wingman.role(), Role::Striker);
+    assert_eq!(wingman.role_of(3), Role::Screen);
+    assert_eq!(wingman.role_of(1), Role::Leader);
+
+    wingman.observe(&deliver(3, vec![status()]), 2.0);
+    wingman.observe(&deliver(3, vec![status()]), 3.0);
+    assert_eq!(wingman.members(), vec![2, 3]);
+    assert!(wingman.is_leader());
+    assert_eq!(wingman.role(), Role::Leader);
+}
+
+#[test]
+fn roster_tracks_heartbeats_and_reports_lost_friends() {
+    use spacepoort::fleet::Roster;
+
+    let ship = MockMotor::fighter(vec2(100.0, 200.0));
+    let mut sender = Roster::new();
+    let beat = sender.heartbeat(&ship, 75.0, 0.0).unwrap();
+    assert!(sender.heartbeat(&ship, 75.0, 0.2).is_none());
+    assert!(sender.heartbeat(&ship, 75.0, sender.period).is_some());
+
+    let mut roster = Roster::new();
+    let chatter = Packet::Status(spacepoort::radio::Status {
+        health: 10,
+        position: vec2(0.0, 0.0),
+        velocity: vec2(0.0, 0.0),
+    });
+    let lost = roster.observe(&deliver(4, vec![beat]), 0.0);
+    assert!(lost.is_empty());
+    let friend = roster.get(4).unwrap();
+    assert_eq!(friend.health, 75.0);
+    assert!(friend.position.distance(vec2(100.0, 200.0)) < 1.0);
+    roster.observe(&deliver(5, vec![chatter]), 1.0);
+    assert_eq!(roster.friends().len(), 2);
+    let lost = roster.observe(&[], 2.5);
+    assert_eq!(lost, vec![4]);
+    assert!(roster.get(4).is_none());
+    assert!(roster.get(5).is_some());
+}
@@ -0,0 +1,77 @@
+use spacepoort::math::*;
+use spacepoort::random::Rng;
+use spacepoort::targeting::lead;
+
+fn geometry(rng: &mut Rng) -> (Vec2, Vec2, f64) {
+    let offset = vec2(rng.uniform(-1e4, 1e4), rng.uniform(-1e4, 1e4));
+    let velocity = vec2(rng.uniform(-500.0, 500.0), rng.uniform(-500.0, 500.0));
+    let speed = rng.uniform(1.0, 2_000.0);
+    (offset, velocity, speed)
+}
+
+#[test]
+fn projectile_reaches_target_at_returned_time() {
+    let mut rng = Rng::new(914);
+    for _ in 0..10_000 {
+        let (offset, velocity, speed) = geometry(&mut rng);
+        let Some(solution) = lead(offset, velocity, speed) else {
+            continue;
+        };
+        let time = solution.time.get();
+        assert!(time > 0.0 && time.is_finite());
+        let projectile = vec2(speed, 0.0).rotate(solution.heading) * time;
+        let target = offset + velocity * time;
+        let scale = offset.length() + velocity.length() * time;
+        assert!(
+            projectile.distance(target) <= 1e-6 * scale.max(1.0),
+            "offset {:?} velocity {:?} speed {}",
+            offset,
+            velocity,
+            speed
+        );
+    }
+}
+
+#[test]
+fn faster_projectile_always_has_solution() {
+    let mut rng = Rng::new(915);
+    for _ in 0..10_000 {
+        let (offset, velocity, _) = geometry(&mut rng);
+        let speed = velocity.length() * rng.uniform(1.01, 10.0) + 1.0;
+        assert!(lead(offset, velocity, speed).is_some());
+    }
+}
+
+#[test]
+fn degenerate_inputs_return_none() {
+    let origin = vec2(0.0, 0.0);
+    assert!(lead(vec2(f64::NAN, 0.0), origin, 100.0).is_none());
+    assert!(lead(vec2(100.0, 0.0), vec2(f64::INFINITY, 0.0), 100.0).is_none());
+    assert!(lead(vec2(100.0, 0.0), origin, 0.0).is_none());
+    assert!(lead(vec2(100.0, 0.0), origin, -1.0).is_none());
+    assert!(lead(origin, origin, 100.0).is_none());
+    assert!(lead(vec2(100.0, 0.0), vec2(100.0, 0.0), 100.0).is_none());
+}
+
+#[test]
+fn cache_reuses_solution_within_tolerance() {
+    use spacepoort::targeting::InterceptCache;
+    let mut cache = InterceptCache::new();
+    let offset = vec2(4_000.0, 1_000.0);
+    let velocity = vec2(-100.0, 50.0);
+    let first = cache.lead(7, offset, velocity, 1_000.0).unwrap();
+    let nudged = cache
+        .lead(7, offset + vec2(0.5, 0.0), velocity, 1_000.0)
+        .unwrap();
+    assert_eq!((cache.hits, cache.misses), (1, 1));
+    assert_eq!(nudged.heading, first.heading);
+    let moved = offset + vec2(50.0, 0.0);
+    let fresh = cache.lead(7, moved, velocity, 1_000.0).unwrap();
+    assert_eq!(cache.misses, 2);
+    assert_eq!(
+        fresh.heading,
+        lead(moved, velocity, 1_000.0).unwrap().heading
+    );
+    cache.lead(8, offset, velocity, 1_000.0);
+    assert_eq!(cache.len(), 2);
+}
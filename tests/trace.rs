@@ -0,0 +1,51 @@
+use spacepoort::control::Attitude;
+use spacepoort::math::*;
+use spacepoort::movement::*;
+use spacepoort::sim::MockMotor;
+use spacepoort::trace::{replay, Trace};
+
+fn align(target: Vec2) -> Align {
+    Align {
+        target: target.angle(),
+        feedforward: 0.0,
+        attitude: Attitude::default(),
+    }
+}
+
+fn golden() -> Trace {
+    let mut ship = MockMotor::fighter(vec2(0.0, 0.0));
+    Trace::capture(&mut ship, vec2(-1_000.0, 400.0), align, 180)
+}
+
+#[test]
+fn trace_round_trips_through_text() {
+    let trace = golden();
+    let decoded = Trace::decode(&trace.encode()).unwrap();
+    assert_eq!(decoded.frames.len(), trace.frames.len());
+    assert_eq!(decoded.encode(), trace.encode());
+}
+
+#[test]
+fn seek_replays_its_own_trace() {
+    let mut ship = MockMotor::fighter(vec2(100.0, -50.0));
+    let trace = Trace::capture(
+        &mut ship,
+        vec2(2_000.0, 900.0),
+        |target| Seek { target },
+        120,
+    );
+    let template = MockMotor::fighter(vec2(0.0, 0.0));
+    assert!(replay(&trace, &template, |target| Seek { target }, 1e-4).is_empty());
+}
+
+#[test]
+fn align_matches_golden_trace() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/align.trace");
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write(path, golden().encode()).unwrap();
+    }
+    let trace = Trace::decode(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let template = MockMotor::fighter(vec2(0.0, 0.0));
+    let divergences = replay(&trace, &template, align, 1e-4);
+    assert!(divergences.is_empty(), "{:?}", divergences.first());
+}